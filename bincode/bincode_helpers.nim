@@ -4,6 +4,7 @@
 {.push raises: [], gcsafe.}
 
 import faststreams # Uses: OutputStreamHandle, write
+import std/unicode
 import stew/[endians2, leb128]
 import bincode_config
 import bincode_common
@@ -68,6 +69,8 @@ func deserializeString*(
 
   var output = newString(bytes.len)
   copyMem(output[0].addr, bytes[0].addr, bytes.len)
+  if validateUtf8(output) != -1:
+    raise newException(BincodeError, "Invalid UTF-8 encoding")
   output
 
 proc serializeInt32*(
@@ -0,0 +1,232 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: OutputStreamHandle, write
+import std/unicode
+import stew/endians2
+import bincode_config
+import bincode_common
+
+## Bare bincode primitives, written the way Rust bincode writes struct fields.
+##
+## Unlike `serializeInt32` and friends in `bincode_helpers`, these procs do not
+## wrap the value in a `Vec<u8>` envelope:
+## - Fixed encoding: integers and floats use their natural width (u16 = 2 bytes)
+## - Variable encoding: unsigned integers use the same marker scheme as
+##   `encodeLength`, signed integers are zigzag-encoded first
+## - `uint8`/`int8`/`bool` are always a single byte, chars are UTF-8
+##
+## Decoders take a cursor (`pos`) so values embedded in a larger buffer can be
+## read one after another. They are the building blocks of the codecs emitted
+## by the Rust `nim_codegen` tool.
+
+func maxVarintSize(T: typedesc): int =
+  ## Longest variable-length encoding Rust accepts for an integer type.
+  when T is uint16:
+    3
+  elif T is uint32:
+    5
+  else:
+    9
+
+proc writeFixed[T: SomeUnsignedInt](
+    stream: OutputStreamHandle, value: T, config: BincodeConfig
+) {.raises: [IOError].} =
+  let bytes =
+    case config.byteOrder
+    of LittleEndian:
+      toBytesLE(value)
+    of BigEndian:
+      toBytesBE(value)
+  stream.write(bytes.toOpenArray(0, bytes.high))
+
+func readFixed[T: SomeUnsignedInt](
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): T {.raises: [BincodeError].} =
+  checkSufficientData(data.len, pos, sizeof(T))
+  var bytes: array[sizeof(T), byte]
+  for i in 0 ..< sizeof(T):
+    bytes[i] = data[pos + i]
+  pos += sizeof(T)
+  case config.byteOrder
+  of LittleEndian:
+    fromBytesLE(T, bytes)
+  of BigEndian:
+    fromBytesBE(T, bytes)
+
+proc encodeUint*[T: uint8 | uint16 | uint32 | uint64](
+    stream: OutputStreamHandle, value: T, config: BincodeConfig = standard()
+) {.raises: [IOError].} =
+  ## Encode an unsigned integer as a bare bincode value.
+  when T is uint8:
+    stream.write(value.byte)
+  else:
+    if config.intSize > 0:
+      writeFixed(stream, value, config)
+    else:
+      encodeLength(stream, value.uint64, config)
+
+func decodeUint*[T: uint8 | uint16 | uint32 | uint64](
+    data: openArray[byte], pos: var int, config: BincodeConfig = standard()
+): T {.raises: [BincodeError].} =
+  ## Decode a bare unsigned integer at `pos` and advance past it.
+  ## Raises `BincodeError` on truncated input or a marker too wide for `T`.
+  when T is uint8:
+    checkSufficientData(data.len, pos, 1)
+    result = data[pos]
+    pos += 1
+  else:
+    if config.intSize > 0:
+      result = readFixed[T](data, pos, config)
+    else:
+      checkSufficientData(data.len, pos, 1)
+      let (value, consumed) = decodeLength(data.toOpenArray(pos, data.high), config)
+      if consumed > maxVarintSize(T) or value > T.high.uint64:
+        raise newException(BincodeError, "Invalid variable-length integer for " & $T)
+      pos += consumed
+      result = T(value)
+
+proc encodeInt*[T: int8 | int16 | int32 | int64](
+    stream: OutputStreamHandle, value: T, config: BincodeConfig = standard()
+) {.raises: [IOError].} =
+  ## Encode a signed integer as a bare bincode value (zigzag for variable encoding).
+  when T is int8:
+    stream.write(cast[byte](value))
+  else:
+    if config.intSize > 0:
+      when T is int16:
+        writeFixed(stream, cast[uint16](value), config)
+      elif T is int32:
+        writeFixed(stream, cast[uint32](value), config)
+      else:
+        writeFixed(stream, cast[uint64](value), config)
+    else:
+      encodeLength(stream, zigzagEncode(value.int64), config)
+
+func decodeInt*[T: int8 | int16 | int32 | int64](
+    data: openArray[byte], pos: var int, config: BincodeConfig = standard()
+): T {.raises: [BincodeError].} =
+  ## Decode a bare signed integer at `pos` and advance past it.
+  when T is int8:
+    result = cast[int8](decodeUint[uint8](data, pos, config))
+  elif T is int16:
+    let raw = decodeUint[uint16](data, pos, config)
+    result = if config.intSize > 0: cast[int16](raw) else: zigzagDecode(raw.uint64).int16
+  elif T is int32:
+    let raw = decodeUint[uint32](data, pos, config)
+    result = if config.intSize > 0: cast[int32](raw) else: zigzagDecode(raw.uint64).int32
+  else:
+    let raw = decodeUint[uint64](data, pos, config)
+    result = if config.intSize > 0: cast[int64](raw) else: zigzagDecode(raw)
+
+proc encodeFloat*[T: float32 | float64](
+    stream: OutputStreamHandle, value: T, config: BincodeConfig = standard()
+) {.raises: [IOError].} =
+  ## Encode a float as its IEEE 754 bits in the configured byte order.
+  ## Floats never use variable-length encoding.
+  when T is float32:
+    writeFixed(stream, cast[uint32](value), config)
+  else:
+    writeFixed(stream, cast[uint64](value), config)
+
+func decodeFloat*[T: float32 | float64](
+    data: openArray[byte], pos: var int, config: BincodeConfig = standard()
+): T {.raises: [BincodeError].} =
+  ## Decode a float at `pos` and advance past it.
+  when T is float32:
+    cast[float32](readFixed[uint32](data, pos, config))
+  else:
+    cast[float64](readFixed[uint64](data, pos, config))
+
+proc encodeBool*(
+    stream: OutputStreamHandle, value: bool, config: BincodeConfig = standard()
+) {.raises: [IOError].} =
+  ## Encode a bool as a single `0` or `1` byte.
+  stream.write(if value: 1'u8 else: 0'u8)
+
+func decodeBool*(
+    data: openArray[byte], pos: var int, config: BincodeConfig = standard()
+): bool {.raises: [BincodeError].} =
+  ## Decode a bool at `pos`; any byte other than `0` or `1` is rejected.
+  case decodeUint[uint8](data, pos, config)
+  of 0:
+    false
+  of 1:
+    true
+  else:
+    raise newException(BincodeError, "Invalid bool value")
+
+proc encodeChar*(
+    stream: OutputStreamHandle, value: Rune, config: BincodeConfig = standard()
+) {.raises: [IOError].} =
+  ## Encode a char as its 1 to 4 UTF-8 bytes, with no length prefix.
+  let s = value.toUTF8
+  stream.write(s.toOpenArray(0, s.high))
+
+func decodeChar*(
+    data: openArray[byte], pos: var int, config: BincodeConfig = standard()
+): Rune {.raises: [BincodeError].} =
+  ## Decode a UTF-8 encoded char at `pos` and advance past it.
+  checkSufficientData(data.len, pos, 1)
+  let first = data[pos]
+  let width =
+    if first < 0x80'u8:
+      1
+    elif (first and 0xE0'u8) == 0xC0'u8:
+      2
+    elif (first and 0xF0'u8) == 0xE0'u8:
+      3
+    elif (first and 0xF8'u8) == 0xF0'u8:
+      4
+    else:
+      raise newException(BincodeError, "Invalid char encoding")
+  checkSufficientData(data.len, pos, width)
+  var s = newString(width)
+  for i in 0 ..< width:
+    s[i] = char(data[pos + i])
+  if validateUtf8(s) != -1:
+    raise newException(BincodeError, "Invalid char encoding")
+  pos += width
+  s.runeAt(0)
+
+func decodeCollectionLength*(
+    data: openArray[byte], pos: var int, config: BincodeConfig = standard()
+): int {.raises: [BincodeError].} =
+  ## Decode a collection length prefix at `pos` and advance past it.
+  ## Raises `BincodeError` if the length exceeds the configured size limit.
+  checkSufficientData(data.len, pos, 1)
+  let (length, consumed) = decodeLength(data.toOpenArray(pos, data.high), config)
+  checkLengthLimit(length, config.sizeLimit)
+  if length > int.high.uint64:
+    raise newException(BincodeError, "Length value exceeds maximum int size")
+  pos += consumed
+  length.int
+
+func decodeBytes*(
+    data: openArray[byte], pos: var int, config: BincodeConfig = standard()
+): seq[byte] {.raises: [BincodeError].} =
+  ## Decode a length-prefixed `Vec<u8>` at `pos` and advance past it.
+  let length = decodeCollectionLength(data, pos, config)
+  checkSufficientData(data.len, pos, length)
+  result = newSeq[byte](length)
+  if length > 0:
+    copyMem(result[0].addr, data[pos].unsafeAddr, length)
+  pos += length
+
+func decodeString*(
+    data: openArray[byte], pos: var int, config: BincodeConfig = standard()
+): string {.raises: [BincodeError].} =
+  ## Decode a length-prefixed UTF-8 string at `pos` and advance past it.
+  ## Raises `BincodeError` if the bytes are not valid UTF-8, as Rust does.
+  let length = decodeCollectionLength(data, pos, config)
+  checkSufficientData(data.len, pos, length)
+  result = newString(length)
+  if length > 0:
+    copyMem(result[0].addr, data[pos].unsafeAddr, length)
+  if validateUtf8(result) != -1:
+    raise newException(BincodeError, "Invalid UTF-8 encoding")
+  pos += length
+
+{.pop.}
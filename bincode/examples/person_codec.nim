@@ -0,0 +1,48 @@
+# Generated by nim_codegen from a bincode type schema. Do not edit.
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, OutputStreamHandle, write, getOutput
+import nim_bincode
+import bincode_config
+
+type
+  Person* = object
+    name*: string
+    age*: uint32
+    email*: string
+
+proc encodePerson*(
+    stream: OutputStreamHandle, value: Person, config: BincodeConfig
+) {.raises: [BincodeError, IOError].} =
+  ## Encode a `Person` and write it to `stream`.
+  serializeString(stream, value.name, config)
+  encodeUint(stream, value.age, config)
+  serializeString(stream, value.email, config)
+
+func decodePerson*(
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): Person {.raises: [BincodeError].} =
+  ## Decode a `Person` at `pos` and advance past it.
+  result = Person()
+  result.name = decodeString(data, pos, config)
+  result.age = decodeUint[uint32](data, pos, config)
+  result.email = decodeString(data, pos, config)
+
+proc serializePerson*(
+    value: Person, config: BincodeConfig = standard()
+): seq[byte] {.raises: [BincodeError, IOError].} =
+  ## Serialize a `Person` to bincode format.
+  var stream = memoryOutput()
+  encodePerson(stream, value, config)
+  stream.getOutput()
+
+func deserializePerson*(
+    data: openArray[byte], config: BincodeConfig = standard()
+): Person {.raises: [BincodeError].} =
+  ## Deserialize a `Person`; all input bytes must be consumed.
+  var pos = 0
+  result = decodePerson(data, pos, config)
+  checkNoTrailingBytes(data.len, pos, 0)
+
+{.pop.}
@@ -0,0 +1,76 @@
+# Generated by nim_codegen from a bincode type schema. Do not edit.
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, OutputStreamHandle, write, getOutput
+import nim_bincode
+import bincode_config
+
+type
+  SignedTransaction* = object
+    nonce*: uint64
+    gasPrice*: array[4, uint64]
+    gasLimit*: uint64
+    to*: array[20, uint8]
+    value*: array[4, uint64]
+    data*: seq[byte]
+    v*: uint64
+    r*: array[32, uint8]
+    s*: array[32, uint8]
+
+proc encodeSignedTransaction*(
+    stream: OutputStreamHandle, value: SignedTransaction, config: BincodeConfig
+) {.raises: [BincodeError, IOError].} =
+  ## Encode a `SignedTransaction` and write it to `stream`.
+  encodeUint(stream, value.nonce, config)
+  for item1 in value.gasPrice:
+    encodeUint(stream, item1, config)
+  encodeUint(stream, value.gasLimit, config)
+  for item2 in value.to:
+    encodeUint(stream, item2, config)
+  for item3 in value.value:
+    encodeUint(stream, item3, config)
+  serialize(stream, value.data, config)
+  encodeUint(stream, value.v, config)
+  for item4 in value.r:
+    encodeUint(stream, item4, config)
+  for item5 in value.s:
+    encodeUint(stream, item5, config)
+
+func decodeSignedTransaction*(
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): SignedTransaction {.raises: [BincodeError].} =
+  ## Decode a `SignedTransaction` at `pos` and advance past it.
+  result = SignedTransaction()
+  result.nonce = decodeUint[uint64](data, pos, config)
+  for i6 in 0 ..< 4:
+    result.gasPrice[i6] = decodeUint[uint64](data, pos, config)
+  result.gasLimit = decodeUint[uint64](data, pos, config)
+  for i7 in 0 ..< 20:
+    result.to[i7] = decodeUint[uint8](data, pos, config)
+  for i8 in 0 ..< 4:
+    result.value[i8] = decodeUint[uint64](data, pos, config)
+  result.data = decodeBytes(data, pos, config)
+  result.v = decodeUint[uint64](data, pos, config)
+  for i9 in 0 ..< 32:
+    result.r[i9] = decodeUint[uint8](data, pos, config)
+  for i10 in 0 ..< 32:
+    result.s[i10] = decodeUint[uint8](data, pos, config)
+
+proc serializeSignedTransaction*(
+    value: SignedTransaction, config: BincodeConfig = standard()
+): seq[byte] {.raises: [BincodeError, IOError].} =
+  ## Serialize a `SignedTransaction` to bincode format.
+  var stream = memoryOutput()
+  encodeSignedTransaction(stream, value, config)
+  stream.getOutput()
+
+func deserializeSignedTransaction*(
+    data: openArray[byte], config: BincodeConfig = standard()
+): SignedTransaction {.raises: [BincodeError].} =
+  ## Deserialize a `SignedTransaction`; all input bytes must be consumed.
+  var pos = 0
+  result = decodeSignedTransaction(data, pos, config)
+  checkNoTrailingBytes(data.len, pos, 0)
+
+{.pop.}
@@ -6,79 +6,7 @@
 import faststreams # Uses: memoryOutput, getOutput
 import ../nim_bincode
 import ../bincode_config
-
-type Person* = object
-  name*: string
-  age*: uint32
-  email*: string
-
-func personToBytes(p: Person): seq[byte] =
-  var nameLenBytes =
-    @[
-      byte(p.name.len and 0xFF),
-      byte((p.name.len shr 8) and 0xFF),
-      byte((p.name.len shr 16) and 0xFF),
-      byte((p.name.len shr 24) and 0xFF),
-    ]
-  var nameBytes = newSeq[byte](p.name.len)
-  for i in 0 ..< p.name.len:
-    nameBytes[i] = byte(p.name[i])
-
-  var ageBytes =
-    @[
-      byte(p.age and 0xFF),
-      byte((p.age shr 8) and 0xFF),
-      byte((p.age shr 16) and 0xFF),
-      byte((p.age shr 24) and 0xFF),
-    ]
-
-  var emailLenBytes =
-    @[
-      byte(p.email.len and 0xFF),
-      byte((p.email.len shr 8) and 0xFF),
-      byte((p.email.len shr 16) and 0xFF),
-      byte((p.email.len shr 24) and 0xFF),
-    ]
-  var emailBytes = newSeq[byte](p.email.len)
-  for i in 0 ..< p.email.len:
-    emailBytes[i] = byte(p.email[i])
-
-  nameLenBytes & nameBytes & ageBytes & emailLenBytes & emailBytes
-
-func bytesToPerson(data: openArray[byte]): Person =
-  var offset = 0
-  var person: Person
-
-  if data.len >= offset + 4:
-    let nameLen =
-      (data[offset].uint32) or (data[offset + 1].uint32 shl 8) or
-      (data[offset + 2].uint32 shl 16) or (data[offset + 3].uint32 shl 24)
-    offset += 4
-
-    if data.len >= offset + int(nameLen):
-      person.name = newString(int(nameLen))
-      for i in 0 ..< int(nameLen):
-        person.name[i] = char(data[offset + i])
-      offset += int(nameLen)
-
-  if data.len >= offset + 4:
-    person.age =
-      (data[offset].uint32) or (data[offset + 1].uint32 shl 8) or
-      (data[offset + 2].uint32 shl 16) or (data[offset + 3].uint32 shl 24)
-    offset += 4
-
-  if data.len >= offset + 4:
-    let emailLen =
-      (data[offset].uint32) or (data[offset + 1].uint32 shl 8) or
-      (data[offset + 2].uint32 shl 16) or (data[offset + 3].uint32 shl 24)
-    offset += 4
-
-    if data.len >= offset + int(emailLen):
-      person.email = newString(int(emailLen))
-      for i in 0 ..< int(emailLen):
-        person.email[i] = char(data[offset + i])
-
-  person
+import person_codec # Generated by `make nim-codegen` from the Rust `Person` struct
 
 proc main() {.raises: [BincodeError, IOError].} =
   echo "=== Struct Example (like Rust direct_example.rs) ===\n"
@@ -90,12 +18,12 @@ proc main() {.raises: [BincodeError, IOError].} =
   echo "  age: ", person.age
   echo "  email: ", person.email
 
-  let encoded = serializeType(person, personToBytes)
+  let encoded = serializePerson(person)
 
   echo "\nSerialized length: ", encoded.len, " bytes"
   echo "Serialized bytes: ", encoded
 
-  let decoded = deserializeType(encoded, bytesToPerson)
+  let decoded = deserializePerson(encoded)
 
   echo "\nDeserialized person:"
   echo "  name: ", decoded.name
@@ -6,14 +6,17 @@
 import faststreams # Uses: memoryOutput, getOutput
 import bincode_common
 import bincode_helpers
+import bincode_primitives
 export bincode_common
 export bincode_helpers
+export bincode_primitives
 
 ## Native Nim implementation of a subset of the bincode v2 format.
 ##
 ## This module provides the main public API by re-exporting functionality from:
 ## - `bincode_common`: Core byte serialization/deserialization
 ## - `bincode_helpers`: String and integer serialization/deserialization
+## - `bincode_primitives`: Bare integers, floats, bools and chars (struct fields)
 ##
 ## For `Vec[byte]` / strings the format matches Rust bincode v2 with:
 ## - little- or big-endian configurable byte order
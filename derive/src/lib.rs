@@ -0,0 +1,86 @@
+//! `#[derive(BincodeSchema)]` for the `bincode_wrapper` crate.
+//!
+//! Fields are described in declaration order and enum variants get their
+//! declaration index, matching what `#[derive(bincode::Encode)]` writes.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields};
+
+#[proc_macro_derive(BincodeSchema)]
+pub fn derive_bincode_schema(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let name_str = name.to_string();
+
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::bincode_wrapper::schema::BincodeSchema));
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = fields_schema(&data.fields);
+            quote! {
+                ::bincode_wrapper::schema::Schema::Struct {
+                    name: #name_str.to_string(),
+                    fields: #fields,
+                }
+            }
+        }
+        Data::Enum(data) => {
+            let variants = data.variants.iter().enumerate().map(|(index, variant)| {
+                let variant_name = variant.ident.unraw().to_string();
+                let index = index as u32;
+                let fields = fields_schema(&variant.fields);
+                quote! {
+                    ::bincode_wrapper::schema::Variant {
+                        name: #variant_name.to_string(),
+                        index: #index,
+                        fields: #fields,
+                    }
+                }
+            });
+            quote! {
+                ::bincode_wrapper::schema::Schema::Enum {
+                    name: #name_str.to_string(),
+                    variants: vec![#(#variants),*],
+                }
+            }
+        }
+        Data::Union(_) => {
+            return syn::Error::new_spanned(name, "BincodeSchema cannot be derived for unions")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    quote! {
+        impl #impl_generics ::bincode_wrapper::schema::BincodeSchema for #name #ty_generics #where_clause {
+            fn schema() -> ::bincode_wrapper::schema::Schema {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+/// `vec![Field { .. }, ..]` for named, tuple (named `0`, `1`, ...) or unit fields
+fn fields_schema(fields: &Fields) -> TokenStream2 {
+    let fields = fields.iter().enumerate().map(|(index, field)| {
+        let field_name = match &field.ident {
+            Some(ident) => ident.unraw().to_string(),
+            None => index.to_string(),
+        };
+        let ty = &field.ty;
+        quote! {
+            ::bincode_wrapper::schema::Field {
+                name: #field_name.to_string(),
+                schema: <#ty as ::bincode_wrapper::schema::BincodeSchema>::schema(),
+            }
+        }
+    });
+    quote! { vec![#(#fields),*] }
+}
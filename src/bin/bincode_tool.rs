@@ -0,0 +1,127 @@
+use std::fs;
+use std::io::Read;
+use std::process::ExitCode;
+
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::detect::{ExpectedType, Outcome};
+use bincode_wrapper::reference::{reference_schema, reference_schemas};
+use bincode_wrapper::schema::Schema;
+use bincode_wrapper::transcode::{bincode_to_json, json_to_bincode};
+use bincode_wrapper::vectors::nim_vector_module;
+
+const USAGE: &str = "Usage:
+  bincode_tool detect <Vec<u8>|String> <file>   Rank the configs that decode <file>
+  bincode_tool schema <type>                    Print the JSON schema of a reference type
+  bincode_tool to-json <schema> <config> <file|->
+                                                Print a bincode blob as JSON
+  bincode_tool from-json <schema> <config> <file.json|-> -o <file>
+                                                Encode a JSON document as bincode
+  bincode_tool nim-vectors [-o <module.nim>]    Render the cross-verification vectors as Nim
+
+<schema> is a reference type name or a schema JSON file.
+<config> is one of variable, fixed8, variable_be, fixed8_be.";
+
+fn read_input(path: &str) -> std::io::Result<Vec<u8>> {
+    if path == "-" {
+        let mut bytes = Vec::new();
+        std::io::stdin().read_to_end(&mut bytes)?;
+        Ok(bytes)
+    } else {
+        fs::read(path)
+    }
+}
+
+fn load_schema(name_or_path: &str) -> Result<Schema, Box<dyn std::error::Error>> {
+    match reference_schema(name_or_path) {
+        Some(schema) => Ok(schema),
+        None => Ok(Schema::from_json(&fs::read_to_string(name_or_path)?)?),
+    }
+}
+
+fn parse_config(name: &str) -> Result<KnownConfig, String> {
+    KnownConfig::from_name(name).ok_or_else(|| {
+        let known: Vec<&str> = KnownConfig::ALL.iter().map(|config| config.name()).collect();
+        format!("Unknown config '{}': expected one of {}", name, known.join(", "))
+    })
+}
+
+fn detect(type_name: &str, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let expected = ExpectedType::from_name(type_name)
+        .ok_or_else(|| format!("Unknown type '{}': expected Vec<u8> or String", type_name))?;
+    let bytes = fs::read(path)?;
+
+    println!("{} ({} bytes) as {}:", path, bytes.len(), type_name);
+    println!("{:<4} {:<12} {:<32} nim config", "rank", "config", "outcome");
+    for (rank, candidate) in expected.detect(&bytes).iter().enumerate() {
+        let outcome = match &candidate.outcome {
+            Outcome::Clean => format!("clean ({} bytes)", bytes.len()),
+            Outcome::Trailing { bytes_read } => {
+                format!("trailing ({} of {} bytes)", bytes_read, bytes.len())
+            }
+            Outcome::Failed(e) => format!("failed: {}", e),
+        };
+        println!(
+            "{:<4} {:<12} {:<32} {}",
+            rank + 1,
+            candidate.config.name(),
+            outcome,
+            candidate.config.nim_config()
+        );
+    }
+    Ok(())
+}
+
+fn schema(type_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let schema = reference_schema(type_name).ok_or_else(|| {
+        let known: Vec<&str> = reference_schemas().iter().map(|(name, _)| *name).collect();
+        format!("Unknown type '{}': expected one of {}", type_name, known.join(", "))
+    })?;
+    println!("{}", schema.to_json());
+    Ok(())
+}
+
+fn to_json(schema: &str, config: &str, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (schema, config) = (load_schema(schema)?, parse_config(config)?);
+    let json = bincode_to_json(&read_input(path)?, &schema, config)?;
+    println!("{}", serde_json::to_string_pretty(&json)?);
+    Ok(())
+}
+
+fn from_json(schema: &str, config: &str, path: &str, output: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (schema, config) = (load_schema(schema)?, parse_config(config)?);
+    let json = serde_json::from_slice(&read_input(path)?)?;
+    fs::write(output, json_to_bincode(&json, &schema, config)?)?;
+    Ok(())
+}
+
+fn nim_vectors(output: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let module = nim_vector_module();
+    match output {
+        Some(path) => fs::write(path, module)?,
+        None => print!("{}", module),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["detect", type_name, path] => detect(type_name, path),
+        ["schema", type_name] => schema(type_name),
+        ["to-json", schema, config, path] => to_json(schema, config, path),
+        ["from-json", schema, config, path, "-o", output] => from_json(schema, config, path, output),
+        ["nim-vectors"] => nim_vectors(None),
+        ["nim-vectors", "-o", output] => nim_vectors(Some(output)),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
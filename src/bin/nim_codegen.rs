@@ -0,0 +1,46 @@
+use std::fs;
+use std::io::Read;
+use std::process::ExitCode;
+
+use bincode_wrapper::codegen::nim::generate_nim_module;
+use bincode_wrapper::schema::Schema;
+
+const USAGE: &str = "Usage:
+  nim_codegen <schema.json|-> [-o <module.nim>]   Generate a Nim codec module from a schema
+
+Schemas can be exported with `bincode_tool schema <type>`.";
+
+fn run(input: &str, output: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let json = if input == "-" {
+        let mut json = String::new();
+        std::io::stdin().read_to_string(&mut json)?;
+        json
+    } else {
+        fs::read_to_string(input)?
+    };
+    let module = generate_nim_module(&Schema::from_json(&json)?)?;
+    match output {
+        Some(path) => fs::write(path, module)?,
+        None => print!("{}", module),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [input] => run(input, None),
+        [input, "-o", output] => run(input, Some(output)),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::io::Read;
+use std::process::ExitCode;
+
+use bincode_wrapper::codegen::rust::{generate_rust_module, parse_nim_objects};
+
+const USAGE: &str = "Usage:
+  rust_codegen <module.nim|-> [-o <module.rs>]   Generate Rust structs from Nim object declarations
+
+Only `type X* = object` declarations are read; other declarations are skipped.";
+
+fn run(input: &str, output: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let source = if input == "-" {
+        let mut source = String::new();
+        std::io::stdin().read_to_string(&mut source)?;
+        source
+    } else {
+        fs::read_to_string(input)?
+    };
+    let objects = parse_nim_objects(&source)?;
+    if objects.is_empty() {
+        return Err(format!("no object declarations found in {}", input).into());
+    }
+    let module = generate_rust_module(&objects)?;
+    match output {
+        Some(path) => fs::write(path, module)?,
+        None => print!("{}", module),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [input] => run(input, None),
+        [input, "-o", output] => run(input, Some(output)),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
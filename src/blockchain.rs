@@ -0,0 +1,120 @@
+//! Blockchain primitive vectors: hashes, addresses, U256 limbs, Merkle proofs.
+//!
+//! Fixed-size arrays are written element by element with no length prefix:
+//! a [`Hash32`] is exactly 32 bytes and an [`Address`] exactly 20 under every
+//! config. A `Vec<Hash32>` Merkle proof carries a length prefix, then 32 bytes
+//! per node. A [`U256`] is four u64 limbs, each written as a bincode u64, so it
+//! is 32 bytes under fixed encoding but a varint per limb under variable
+//! encoding. Code that hashes encodings depends on these layouts.
+//!
+//! [`chain_cases`] lists the sample values exchanged with `test_blockchain.nim`
+//! in both directions. The samples are built from byte formulas that the Nim
+//! test repeats.
+
+use bincode::error::{DecodeError, EncodeError};
+
+use crate::config::KnownConfig;
+use crate::reference::{Address, Hash32, SignedTransaction, U256};
+
+/// Configs in the cross-verification files
+pub const CHAIN_CONFIGS: [KnownConfig; 2] = [KnownConfig::Variable, KnownConfig::Fixed8];
+
+/// Limbs covering zero, the varint markers and the extremes
+pub const SAMPLE_U256: U256 = [u64::MAX, 0, 251, 1 << 63];
+
+/// Hash whose byte `i` is `first + i`
+pub fn sample_hash(first: u8) -> Hash32 {
+    std::array::from_fn(|i| first.wrapping_add(i as u8))
+}
+
+/// Address `a0 a1 .. b3`
+pub fn sample_address() -> Address {
+    std::array::from_fn(|i| 0xa0 + i as u8)
+}
+
+/// Proof of `len` nodes; node `k` is `sample_hash(32 * k)`
+pub fn sample_proof(len: usize) -> Vec<Hash32> {
+    (0..len).map(|k| sample_hash((32 * k) as u8)).collect()
+}
+
+/// A token transfer signed with a fake (r, s, v)
+pub fn sample_transaction() -> SignedTransaction {
+    SignedTransaction {
+        nonce: 9,
+        gas_price: [20_000_000_000, 0, 0, 0],
+        gas_limit: 21_000,
+        to: sample_address(),
+        value: [1_000_000_000_000_000_000, 0, 0, 0],
+        data: vec![0xa9, 0x05, 0x9c, 0xbb],
+        v: 37,
+        r: sample_hash(0x40),
+        s: sample_hash(0x80),
+    }
+}
+
+/// One sample value of one of the primitive types
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainValue {
+    Hash(Hash32),
+    Address(Address),
+    U256(U256),
+    Proof(Vec<Hash32>),
+    Transaction(SignedTransaction),
+}
+
+impl ChainValue {
+    /// Reference type name, as in [`reference_schemas`](crate::reference::reference_schemas)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ChainValue::Hash(_) => "[u8; 32]",
+            ChainValue::Address(_) => "[u8; 20]",
+            ChainValue::U256(_) => "[u64; 4]",
+            ChainValue::Proof(_) => "Vec<[u8; 32]>",
+            ChainValue::Transaction(_) => "SignedTransaction",
+        }
+    }
+
+    pub fn encode(&self, config: KnownConfig) -> Result<Vec<u8>, EncodeError> {
+        match self {
+            ChainValue::Hash(value) => config.encode(value),
+            ChainValue::Address(value) => config.encode(value),
+            ChainValue::U256(value) => config.encode(value),
+            ChainValue::Proof(value) => config.encode(value),
+            ChainValue::Transaction(value) => config.encode(value),
+        }
+    }
+
+    /// Decode `bytes` as a value of the same type, requiring every byte to be used
+    pub fn decode_same(&self, bytes: &[u8], config: KnownConfig) -> Result<ChainValue, DecodeError> {
+        let (value, bytes_read) = match self {
+            ChainValue::Hash(_) => config.decode(bytes).map(|(v, n)| (ChainValue::Hash(v), n))?,
+            ChainValue::Address(_) => config.decode(bytes).map(|(v, n)| (ChainValue::Address(v), n))?,
+            ChainValue::U256(_) => config.decode(bytes).map(|(v, n)| (ChainValue::U256(v), n))?,
+            ChainValue::Proof(_) => config.decode(bytes).map(|(v, n)| (ChainValue::Proof(v), n))?,
+            ChainValue::Transaction(_) => config.decode(bytes).map(|(v, n)| (ChainValue::Transaction(v), n))?,
+        };
+        if bytes_read != bytes.len() {
+            return Err(DecodeError::OtherString(format!("{} trailing bytes", bytes.len() - bytes_read)));
+        }
+        Ok(value)
+    }
+}
+
+/// The values exchanged with `test_blockchain.nim`, by name
+pub fn chain_cases() -> Vec<(&'static str, ChainValue)> {
+    vec![
+        ("hash", ChainValue::Hash(sample_hash(0))),
+        ("hash_zero", ChainValue::Hash([0; 32])),
+        ("address", ChainValue::Address(sample_address())),
+        ("u256", ChainValue::U256(SAMPLE_U256)),
+        ("u256_zero", ChainValue::U256([0; 4])),
+        ("proof", ChainValue::Proof(sample_proof(3))),
+        ("proof_empty", ChainValue::Proof(Vec::new())),
+        ("transaction", ChainValue::Transaction(sample_transaction())),
+    ]
+}
+
+/// `<side>_chain_<name>_<config>.bin`
+pub fn chain_file(side: &str, name: &str, config: KnownConfig) -> String {
+    format!("{}_chain_{}_{}.bin", side, name, config)
+}
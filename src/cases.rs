@@ -0,0 +1,243 @@
+//! Hand-written cross-verification cases loaded from TOML.
+//!
+//! Each `[[case]]` names a reference type, the configs to run, an input and
+//! the expected outcome:
+//!
+//! ```toml
+//! [[case]]
+//! name = "vec_u8_small"
+//! type = "Vec<u8>"
+//! configs = ["variable", "fixed8"]
+//! value = [1, 2, 3]                    # encode this value...
+//! expect.variable = "03 01 02 03"      # ...and expect these bytes per config
+//! expect.fixed8 = "03 00 00 00 00 00 00 00 01 02 03"
+//!
+//! [[case]]
+//! name = "string_truncated"
+//! type = "String"
+//! configs = ["variable"]
+//! bytes = "05 68 65"                   # decode these bytes...
+//! expect.error = "UnexpectedEnd"       # ...and expect this error kind
+//! ```
+//!
+//! Values use the JSON shapes of [`crate::transcode`]. A decode case expects
+//! either `expect.value` or `expect.error`; an encode case expects bytes for
+//! every config or `expect.error`. Error kinds are `DecodeError` variant
+//! names, `TrailingBytes`, `InvalidValue` (the value does not fit the type) or
+//! `EncodeError` variant names.
+
+use std::fmt::Write;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::config::KnownConfig;
+use crate::mutation::error_kind;
+use crate::reference::{reference_schema, reference_schemas};
+use crate::schema::Schema;
+use crate::transcode::{bincode_to_json, json_to_bincode, TranscodeError};
+
+/// What a case feeds to the harness
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    /// Encode this value
+    Value(Value),
+    /// Decode these bytes
+    Bytes(Vec<u8>),
+}
+
+/// What a case expects back
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expect {
+    /// Encoded bytes, one entry per config of the case
+    Bytes(Vec<(KnownConfig, Vec<u8>)>),
+    /// Decoded value
+    Value(Value),
+    /// Error kind, for every config of the case
+    Error(String),
+}
+
+/// One loaded case
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub name: String,
+    pub type_name: String,
+    pub schema: Schema,
+    pub configs: Vec<KnownConfig>,
+    pub input: Input,
+    pub expect: Expect,
+}
+
+/// Result of running a case with one config
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checked {
+    pub config: KnownConfig,
+    /// Encoded bytes or decoded value, or the error kind
+    pub output: Result<Output, String>,
+    /// How the output differs from the expectation, if it does
+    pub problem: Option<String>,
+}
+
+/// Successful case output
+#[derive(Debug, Clone, PartialEq)]
+pub enum Output {
+    Bytes(Vec<u8>),
+    Value(Value),
+}
+
+#[derive(Deserialize)]
+struct CaseFile {
+    #[serde(rename = "case", default)]
+    cases: Vec<RawCase>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCase {
+    name: String,
+    #[serde(rename = "type")]
+    type_name: String,
+    configs: Vec<String>,
+    value: Option<toml::Value>,
+    bytes: Option<String>,
+    expect: toml::Table,
+}
+
+/// Parse whitespace-separated (or contiguous) hex byte pairs
+pub fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits: String = text.split_whitespace().collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("odd number of hex digits in '{}'", text));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            let pair = &digits[i..i + 2];
+            u8::from_str_radix(pair, 16).map_err(|_| format!("invalid hex byte '{}'", pair))
+        })
+        .collect()
+}
+
+fn to_json(value: &toml::Value) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
+
+/// Load every case in a TOML document
+pub fn load_cases(toml: &str) -> Result<Vec<Case>, String> {
+    let file: CaseFile = toml::from_str(toml).map_err(|e| e.to_string())?;
+    let mut cases: Vec<Case> = Vec::new();
+    for raw in file.cases {
+        let name = raw.name.clone();
+        let case = load_case(raw).map_err(|e| format!("case '{}': {}", name, e))?;
+        if cases.iter().any(|other| other.name == case.name) {
+            return Err(format!("case '{}': duplicate name", case.name));
+        }
+        cases.push(case);
+    }
+    Ok(cases)
+}
+
+fn load_case(raw: RawCase) -> Result<Case, String> {
+    let schema = reference_schema(&raw.type_name).ok_or_else(|| {
+        let known: Vec<&str> = reference_schemas().into_iter().map(|(name, _)| name).collect();
+        format!("unknown type '{}' (known: {})", raw.type_name, known.join(", "))
+    })?;
+    if raw.configs.is_empty() {
+        return Err("no configs".to_string());
+    }
+    let configs = raw
+        .configs
+        .iter()
+        .map(|config| KnownConfig::from_name(config).ok_or_else(|| format!("unknown config '{}'", config)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let input = match (&raw.value, &raw.bytes) {
+        (Some(value), None) => Input::Value(to_json(value)?),
+        (None, Some(hex)) => Input::Bytes(parse_hex(hex)?),
+        _ => return Err("needs exactly one of `value` and `bytes`".to_string()),
+    };
+
+    let expect = if let Some(kind) = raw.expect.get("error") {
+        if raw.expect.len() > 1 {
+            return Err("`expect.error` cannot be combined with other expectations".to_string());
+        }
+        let kind = kind.as_str().ok_or_else(|| "`expect.error` must be a string".to_string())?;
+        Expect::Error(kind.to_string())
+    } else if let Input::Bytes(_) = input {
+        match (raw.expect.get("value"), raw.expect.len()) {
+            (Some(value), 1) => Expect::Value(to_json(value)?),
+            _ => return Err("a decode case expects `expect.value` or `expect.error`".to_string()),
+        }
+    } else {
+        if let Some(key) = raw.expect.keys().find(|key| !configs.iter().any(|config| config.name() == *key)) {
+            return Err(format!("`expect.{}` is not one of the case's configs", key));
+        }
+        let mut bytes = Vec::new();
+        for &config in &configs {
+            let key = config.name();
+            let hex = raw.expect.get(key).ok_or_else(|| format!("no expected bytes for config '{}'", key))?;
+            let hex = hex.as_str().ok_or_else(|| format!("`expect.{}` must be a hex string", key))?;
+            bytes.push((config, parse_hex(hex).map_err(|e| format!("expect.{}: {}", key, e))?));
+        }
+        Expect::Bytes(bytes)
+    };
+
+    Ok(Case { name: raw.name, type_name: raw.type_name, schema, configs, input, expect })
+}
+
+/// Error kind of a transcoding failure, as written in `expect.error`
+pub fn transcode_error_kind(e: &TranscodeError) -> String {
+    match e {
+        TranscodeError::Decode(e) => error_kind(e),
+        TranscodeError::TrailingBytes { .. } => "TrailingBytes".to_string(),
+        TranscodeError::Json { .. } => "InvalidValue".to_string(),
+        TranscodeError::Encode(e) => format!("{:?}", e).chars().take_while(char::is_ascii_alphanumeric).collect(),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" ")
+}
+
+impl Case {
+    /// Run the case with each of its configs
+    pub fn run(&self) -> Vec<Checked> {
+        self.configs.iter().map(|&config| self.run_with(config)).collect()
+    }
+
+    fn run_with(&self, config: KnownConfig) -> Checked {
+        let output = match &self.input {
+            Input::Value(value) => json_to_bincode(value, &self.schema, config).map(Output::Bytes),
+            Input::Bytes(bytes) => bincode_to_json(bytes, &self.schema, config).map(Output::Value),
+        };
+        let output = output.map_err(|e| transcode_error_kind(&e));
+
+        let mut problem = String::new();
+        match (&self.expect, &output) {
+            (Expect::Error(expected), Err(kind)) if expected == kind => {}
+            (Expect::Error(expected), Err(kind)) => {
+                let _ = write!(problem, "expected error {}, got error {}", expected, kind);
+            }
+            (Expect::Error(expected), Ok(_)) => {
+                let _ = write!(problem, "expected error {}, but it succeeded", expected);
+            }
+            (_, Err(kind)) => {
+                let _ = write!(problem, "failed with {}", kind);
+            }
+            (Expect::Bytes(expected), Ok(Output::Bytes(bytes))) => {
+                let expected = expected.iter().find(|(c, _)| *c == config).map(|(_, bytes)| bytes);
+                if expected != Some(bytes) {
+                    let expected = expected.map_or_else(String::new, |bytes| hex(bytes));
+                    let _ = write!(problem, "expected bytes [{}], got [{}]", expected, hex(bytes));
+                }
+            }
+            (Expect::Value(expected), Ok(Output::Value(value))) => {
+                if expected != value {
+                    let _ = write!(problem, "expected value {}, got {}", expected, value);
+                }
+            }
+            (_, Ok(_)) => problem.push_str("expectation does not match the case direction"),
+        }
+        Checked { config, output, problem: (!problem.is_empty()).then_some(problem) }
+    }
+}
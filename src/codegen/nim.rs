@@ -0,0 +1,531 @@
+//! Nim codec generator.
+//!
+//! [`generate_nim_module`] turns a [`Schema`] into a Nim module with one
+//! `object` (or `enum`) per named type and, for each of them:
+//!
+//! - `encodeT(stream, value, config)` / `decodeT(data, pos, config)` for values
+//!   embedded in a larger message
+//! - `serializeT(value, config)` / `deserializeT(data, config)` for whole messages
+//!
+//! The procs use the `nim_bincode` primitives (`encodeLength`,
+//! `serializeString`, `encodeUint`, ...) and take the same `BincodeConfig`,
+//! so the Nim wire format follows the Rust struct definitions.
+
+use std::fmt::Write;
+
+use super::{camel_case, lower_first, upper_first, CodegenError};
+use crate::schema::{Field, Schema, Variant};
+
+const NIM_KEYWORDS: &[&str] = &[
+    "addr", "and", "as", "asm", "bind", "block", "break", "case", "cast", "concept", "const",
+    "continue", "converter", "defer", "discard", "distinct", "div", "do", "elif", "else", "end",
+    "enum", "except", "export", "finally", "for", "from", "func", "if", "import", "in", "include",
+    "interface", "is", "isnot", "iterator", "let", "macro", "method", "mixin", "mod", "nil", "not",
+    "notin", "object", "of", "or", "out", "proc", "ptr", "raise", "ref", "return", "shl", "shr",
+    "static", "template", "try", "tuple", "type", "using", "var", "when", "while", "xor", "yield",
+];
+
+/// Generate a Nim module for every struct and enum reachable from `schema`
+pub fn generate_nim_module(schema: &Schema) -> Result<String, CodegenError> {
+    let mut named = Vec::new();
+    collect_named_types(schema, &mut named)?;
+    if named.is_empty() {
+        return Err(CodegenError(
+            "schema contains no struct or enum to generate a Nim type for".to_string(),
+        ));
+    }
+
+    let mut generator = NimGenerator { out: String::new(), counter: 0 };
+    generator.header(schema);
+    generator.line(0, "type");
+    for (i, ty) in named.iter().enumerate() {
+        if i > 0 {
+            generator.blank();
+        }
+        generator.type_decl(ty)?;
+    }
+    for ty in &named {
+        generator.blank();
+        generator.encode_proc(ty)?;
+        generator.blank();
+        generator.decode_func(ty)?;
+        generator.blank();
+        generator.convenience_procs(ty);
+    }
+    generator.blank();
+    generator.line(0, "{.pop.}");
+    Ok(generator.out)
+}
+
+/// Named types in dependency order (a type comes after the types it uses)
+fn collect_named_types<'a>(
+    schema: &'a Schema,
+    named: &mut Vec<&'a Schema>,
+) -> Result<(), CodegenError> {
+    match schema {
+        Schema::Seq { item } | Schema::Array { item, .. } | Schema::Option { item } => {
+            collect_named_types(item, named)
+        }
+        Schema::Map { key, value } => {
+            collect_named_types(key, named)?;
+            collect_named_types(value, named)
+        }
+        Schema::Tuple { items } => items.iter().try_for_each(|item| collect_named_types(item, named)),
+        Schema::Struct { name, fields } => {
+            fields.iter().try_for_each(|field| collect_named_types(&field.schema, named))?;
+            add_named_type(name, schema, named)
+        }
+        Schema::Enum { name, variants } => {
+            variants
+                .iter()
+                .flat_map(|variant| &variant.fields)
+                .try_for_each(|field| collect_named_types(&field.schema, named))?;
+            add_named_type(name, schema, named)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn add_named_type<'a>(
+    name: &str,
+    schema: &'a Schema,
+    named: &mut Vec<&'a Schema>,
+) -> Result<(), CodegenError> {
+    match named.iter().find(|existing| type_name(existing) == Some(name)) {
+        Some(existing) if *existing != schema => Err(CodegenError(format!(
+            "type '{}' appears with two different shapes",
+            name
+        ))),
+        Some(_) => Ok(()),
+        None => {
+            named.push(schema);
+            Ok(())
+        }
+    }
+}
+
+fn type_name(schema: &Schema) -> Option<&str> {
+    match schema {
+        Schema::Struct { name, .. } | Schema::Enum { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+/// Whether every variant is a unit variant (maps to a plain Nim enum)
+fn is_unit_enum(variants: &[Variant]) -> bool {
+    variants.iter().all(|variant| variant.fields.is_empty())
+}
+
+/// Nim identifier for a struct field
+fn field_ident(name: &str) -> String {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        return format!("field{}", name);
+    }
+    let ident = camel_case(name);
+    if NIM_KEYWORDS.contains(&ident.as_str()) {
+        format!("`{}`", ident)
+    } else {
+        ident
+    }
+}
+
+/// Nim identifier for a field of an enum variant; unique across all branches
+fn variant_field_ident(variant: &Variant, field: &Field) -> String {
+    format!("{}{}", lower_first(&variant.name), upper_first(&camel_case(&field.name)))
+}
+
+/// Nim type expression for a schema
+fn nim_type(schema: &Schema) -> Result<String, CodegenError> {
+    Ok(match schema {
+        Schema::Bool => "bool".to_string(),
+        Schema::U8 => "uint8".to_string(),
+        Schema::U16 => "uint16".to_string(),
+        Schema::U32 => "uint32".to_string(),
+        Schema::U64 => "uint64".to_string(),
+        Schema::I8 => "int8".to_string(),
+        Schema::I16 => "int16".to_string(),
+        Schema::I32 => "int32".to_string(),
+        Schema::I64 => "int64".to_string(),
+        Schema::F32 => "float32".to_string(),
+        Schema::F64 => "float64".to_string(),
+        Schema::Char => "Rune".to_string(),
+        Schema::String => "string".to_string(),
+        Schema::NonZero { item } => nim_type(item)?,
+        Schema::Seq { item } if **item == Schema::U8 => "seq[byte]".to_string(),
+        Schema::Seq { item } => format!("seq[{}]", nim_type(item)?),
+        Schema::Array { len, item } => format!("array[{}, {}]", len, nim_type(item)?),
+        Schema::Map { key, value } => {
+            format!("OrderedTable[{}, {}]", nim_type(key)?, nim_type(value)?)
+        }
+        Schema::Option { item } => format!("Option[{}]", nim_type(item)?),
+        Schema::Tuple { items } => {
+            let items = items.iter().map(nim_type).collect::<Result<Vec<_>, _>>()?;
+            format!("({})", items.join(", "))
+        }
+        Schema::Struct { name, .. } | Schema::Enum { name, .. } => name.clone(),
+        Schema::Unit | Schema::U128 | Schema::I128 | Schema::Ref { .. } => {
+            return Err(CodegenError(format!("{:?} has no Nim counterpart", schema)));
+        }
+    })
+}
+
+/// Collect what the module needs from the standard library
+fn uses(schema: &Schema, imports: &mut [bool; 3]) {
+    match schema {
+        Schema::Option { item } => {
+            imports[0] = true;
+            uses(item, imports);
+        }
+        Schema::Map { key, value } => {
+            imports[1] = true;
+            uses(key, imports);
+            uses(value, imports);
+        }
+        Schema::Char => imports[2] = true,
+        Schema::Seq { item } | Schema::Array { item, .. } => uses(item, imports),
+        Schema::Tuple { items } => items.iter().for_each(|item| uses(item, imports)),
+        Schema::Struct { fields, .. } => fields.iter().for_each(|field| uses(&field.schema, imports)),
+        Schema::Enum { variants, .. } => variants
+            .iter()
+            .flat_map(|variant| &variant.fields)
+            .for_each(|field| uses(&field.schema, imports)),
+        _ => {}
+    }
+}
+
+struct NimGenerator {
+    out: String,
+    counter: usize,
+}
+
+impl NimGenerator {
+    fn line(&mut self, indent: usize, text: &str) {
+        let _ = writeln!(self.out, "{:width$}{}", "", text, width = indent * 2);
+    }
+
+    fn blank(&mut self) {
+        self.out.push('\n');
+    }
+
+    /// Fresh local variable name
+    fn local(&mut self, prefix: &str) -> String {
+        self.counter += 1;
+        format!("{}{}", prefix, self.counter)
+    }
+
+    fn header(&mut self, schema: &Schema) {
+        let mut imports = [false; 3];
+        uses(schema, &mut imports);
+        let std_imports: Vec<&str> = ["options", "tables", "unicode"]
+            .iter()
+            .zip(imports)
+            .filter(|(_, used)| *used)
+            .map(|(name, _)| *name)
+            .collect();
+
+        self.line(0, "# Generated by nim_codegen from a bincode type schema. Do not edit.");
+        self.blank();
+        self.line(0, "{.push raises: [], gcsafe.}");
+        self.blank();
+        self.line(0, "import faststreams # Uses: memoryOutput, OutputStreamHandle, write, getOutput");
+        if !std_imports.is_empty() {
+            self.line(0, &format!("import std/[{}]", std_imports.join(", ")));
+        }
+        self.line(0, "import nim_bincode");
+        self.line(0, "import bincode_config");
+        self.blank();
+    }
+
+    fn type_decl(&mut self, schema: &Schema) -> Result<(), CodegenError> {
+        match schema {
+            Schema::Struct { name, fields } => {
+                self.line(1, &format!("{}* = object", name));
+                for field in fields {
+                    let line = format!("{}*: {}", field_ident(&field.name), nim_type(&field.schema)?);
+                    self.line(2, &line);
+                }
+            }
+            Schema::Enum { name, variants } if is_unit_enum(variants) => {
+                self.line(1, &format!("{}* {{.pure.}} = enum", name));
+                for variant in variants {
+                    self.line(2, &format!("{} = {}", variant.name, variant.index));
+                }
+            }
+            Schema::Enum { name, variants } => {
+                self.line(1, &format!("{}Kind* {{.pure.}} = enum", name));
+                for variant in variants {
+                    self.line(2, &format!("{} = {}", variant.name, variant.index));
+                }
+                self.blank();
+                self.line(1, &format!("{}* = object", name));
+                self.line(2, &format!("case kind*: {}Kind", name));
+                for variant in variants {
+                    self.line(2, &format!("of {}Kind.{}:", name, variant.name));
+                    if variant.fields.is_empty() {
+                        self.line(3, "discard");
+                    }
+                    for field in &variant.fields {
+                        let line = format!(
+                            "{}*: {}",
+                            variant_field_ident(variant, field),
+                            nim_type(&field.schema)?
+                        );
+                        self.line(3, &line);
+                    }
+                }
+            }
+            _ => unreachable!("only named types are declared"),
+        }
+        Ok(())
+    }
+
+    fn encode_proc(&mut self, schema: &Schema) -> Result<(), CodegenError> {
+        let name = type_name(schema).expect("named type");
+        self.line(0, &format!("proc encode{}*(", name));
+        self.line(2, &format!("stream: OutputStreamHandle, value: {}, config: BincodeConfig", name));
+        self.line(0, ") {.raises: [BincodeError, IOError].} =");
+        self.line(1, &format!("## Encode a `{}` and write it to `stream`.", name));
+        match schema {
+            Schema::Struct { fields, .. } => {
+                if fields.is_empty() {
+                    self.line(1, "discard");
+                }
+                for field in fields {
+                    let expr = format!("value.{}", field_ident(&field.name));
+                    self.encode(&field.schema, &expr, 1)?;
+                }
+            }
+            Schema::Enum { variants, .. } if is_unit_enum(variants) => {
+                self.line(1, "encodeUint(stream, value.ord.uint32, config)");
+            }
+            Schema::Enum { variants, .. } => {
+                self.line(1, "encodeUint(stream, value.kind.ord.uint32, config)");
+                self.line(1, "case value.kind");
+                for variant in variants {
+                    self.line(1, &format!("of {}Kind.{}:", name, variant.name));
+                    if variant.fields.is_empty() {
+                        self.line(2, "discard");
+                    }
+                    for field in &variant.fields {
+                        let expr = format!("value.{}", variant_field_ident(variant, field));
+                        self.encode(&field.schema, &expr, 2)?;
+                    }
+                }
+            }
+            _ => unreachable!("only named types get codecs"),
+        }
+        Ok(())
+    }
+
+    fn decode_func(&mut self, schema: &Schema) -> Result<(), CodegenError> {
+        let name = type_name(schema).expect("named type");
+        self.line(0, &format!("func decode{}*(", name));
+        self.line(2, "data: openArray[byte], pos: var int, config: BincodeConfig");
+        self.line(0, &format!("): {} {{.raises: [BincodeError].}} =", name));
+        self.line(1, &format!("## Decode a `{}` at `pos` and advance past it.", name));
+        match schema {
+            Schema::Struct { fields, .. } => {
+                self.line(1, &format!("result = {}()", name));
+                for field in fields {
+                    let target = format!("result.{}", field_ident(&field.name));
+                    self.decode(&field.schema, &target, 1)?;
+                }
+            }
+            Schema::Enum { variants, .. } => {
+                let unit = is_unit_enum(variants);
+                self.line(1, "let index = decodeUint[uint32](data, pos, config)");
+                self.line(1, "case index");
+                for variant in variants {
+                    self.line(1, &format!("of {}'u32:", variant.index));
+                    if unit {
+                        self.line(2, &format!("result = {}.{}", name, variant.name));
+                        continue;
+                    }
+                    self.line(2, &format!("result = {}(kind: {}Kind.{})", name, name, variant.name));
+                    for field in &variant.fields {
+                        let target = format!("result.{}", variant_field_ident(variant, field));
+                        self.decode(&field.schema, &target, 2)?;
+                    }
+                }
+                self.line(1, "else:");
+                self.line(
+                    2,
+                    &format!(
+                        "raise newException(BincodeError, \"Unexpected variant index for {}: \" & $index)",
+                        name
+                    ),
+                );
+            }
+            _ => unreachable!("only named types get codecs"),
+        }
+        Ok(())
+    }
+
+    fn convenience_procs(&mut self, schema: &Schema) {
+        let name = type_name(schema).expect("named type");
+        self.line(0, &format!("proc serialize{}*(", name));
+        self.line(2, &format!("value: {}, config: BincodeConfig = standard()", name));
+        self.line(0, "): seq[byte] {.raises: [BincodeError, IOError].} =");
+        self.line(1, &format!("## Serialize a `{}` to bincode format.", name));
+        self.line(1, "var stream = memoryOutput()");
+        self.line(1, &format!("encode{}(stream, value, config)", name));
+        self.line(1, "stream.getOutput()");
+        self.blank();
+        self.line(0, &format!("func deserialize{}*(", name));
+        self.line(2, "data: openArray[byte], config: BincodeConfig = standard()");
+        self.line(0, &format!("): {} {{.raises: [BincodeError].}} =", name));
+        self.line(1, &format!("## Deserialize a `{}`; all input bytes must be consumed.", name));
+        self.line(1, "var pos = 0");
+        self.line(1, &format!("result = decode{}(data, pos, config)", name));
+        self.line(1, "checkNoTrailingBytes(data.len, pos, 0)");
+    }
+
+    /// Statements writing `expr` to `stream`
+    fn encode(&mut self, schema: &Schema, expr: &str, indent: usize) -> Result<(), CodegenError> {
+        let call = |proc_name: &str| format!("{}(stream, {}, config)", proc_name, expr);
+        match schema {
+            Schema::Bool => self.line(indent, &call("encodeBool")),
+            Schema::U8 | Schema::U16 | Schema::U32 | Schema::U64 => {
+                self.line(indent, &call("encodeUint"))
+            }
+            Schema::I8 | Schema::I16 | Schema::I32 | Schema::I64 => {
+                self.line(indent, &call("encodeInt"))
+            }
+            Schema::F32 | Schema::F64 => self.line(indent, &call("encodeFloat")),
+            Schema::Char => self.line(indent, &call("encodeChar")),
+            Schema::String => self.line(indent, &call("serializeString")),
+            Schema::NonZero { item } => {
+                self.line(indent, &format!("if {} == 0:", expr));
+                self.line(indent + 1, "raise newException(BincodeError, \"NonZero value is zero\")");
+                self.encode(item, expr, indent)?;
+            }
+            Schema::Seq { item } if **item == Schema::U8 => self.line(indent, &call("serialize")),
+            Schema::Seq { item } => {
+                let var = self.local("item");
+                self.line(indent, &format!("encodeLength(stream, {}.len.uint64, config)", expr));
+                self.line(indent, &format!("for {} in {}:", var, expr));
+                self.encode(item, &var, indent + 1)?;
+            }
+            Schema::Array { item, .. } => {
+                let var = self.local("item");
+                self.line(indent, &format!("for {} in {}:", var, expr));
+                self.encode(item, &var, indent + 1)?;
+            }
+            Schema::Map { key, value } => {
+                let key_var = self.local("key");
+                let value_var = self.local("value");
+                self.line(indent, &format!("encodeLength(stream, {}.len.uint64, config)", expr));
+                self.line(indent, &format!("for {}, {} in {}.pairs:", key_var, value_var, expr));
+                self.encode(key, &key_var, indent + 1)?;
+                self.encode(value, &value_var, indent + 1)?;
+            }
+            Schema::Option { item } => {
+                self.line(indent, &format!("if {}.isSome:", expr));
+                self.line(indent + 1, "stream.write(1'u8)");
+                self.encode(item, &format!("{}.get", expr), indent + 1)?;
+                self.line(indent, "else:");
+                self.line(indent + 1, "stream.write(0'u8)");
+            }
+            Schema::Tuple { items } => {
+                for (i, item) in items.iter().enumerate() {
+                    self.encode(item, &format!("{}[{}]", expr, i), indent)?;
+                }
+            }
+            Schema::Struct { name, .. } | Schema::Enum { name, .. } => {
+                self.line(indent, &call(&format!("encode{}", name)))
+            }
+            Schema::Unit | Schema::U128 | Schema::I128 | Schema::Ref { .. } => {
+                return Err(CodegenError(format!("{:?} has no Nim counterpart", schema)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Statements decoding a value at `pos` into `target`
+    fn decode(&mut self, schema: &Schema, target: &str, indent: usize) -> Result<(), CodegenError> {
+        let assign = |expr: String| format!("{} = {}", target, expr);
+        match schema {
+            Schema::Bool => self.line(indent, &assign("decodeBool(data, pos, config)".to_string())),
+            Schema::U8 | Schema::U16 | Schema::U32 | Schema::U64 => {
+                let expr = format!("decodeUint[{}](data, pos, config)", nim_type(schema)?);
+                self.line(indent, &assign(expr));
+            }
+            Schema::I8 | Schema::I16 | Schema::I32 | Schema::I64 => {
+                let expr = format!("decodeInt[{}](data, pos, config)", nim_type(schema)?);
+                self.line(indent, &assign(expr));
+            }
+            Schema::F32 | Schema::F64 => {
+                let expr = format!("decodeFloat[{}](data, pos, config)", nim_type(schema)?);
+                self.line(indent, &assign(expr));
+            }
+            Schema::Char => self.line(indent, &assign("decodeChar(data, pos, config)".to_string())),
+            Schema::String => {
+                self.line(indent, &assign("decodeString(data, pos, config)".to_string()))
+            }
+            Schema::NonZero { item } => {
+                self.decode(item, target, indent)?;
+                self.line(indent, &format!("if {} == 0:", target));
+                self.line(indent + 1, "raise newException(BincodeError, \"NonZero value is zero\")");
+            }
+            Schema::Seq { item } if **item == Schema::U8 => {
+                self.line(indent, &assign("decodeBytes(data, pos, config)".to_string()))
+            }
+            Schema::Seq { item } => {
+                // Grow while decoding: the length prefix is untrusted input
+                let len_var = self.local("len");
+                let item_var = self.local("item");
+                self.line(indent, &format!("let {} = decodeCollectionLength(data, pos, config)", len_var));
+                self.line(indent, &assign(format!("newSeq[{}]()", nim_type(item)?)));
+                self.line(indent, &format!("for _ in 0 ..< {}:", len_var));
+                self.line(indent + 1, &format!("var {}: {}", item_var, nim_type(item)?));
+                self.decode(item, &item_var, indent + 1)?;
+                self.line(indent + 1, &format!("{}.add({})", target, item_var));
+            }
+            Schema::Array { len, item } => {
+                let index_var = self.local("i");
+                self.line(indent, &format!("for {} in 0 ..< {}:", index_var, len));
+                self.decode(item, &format!("{}[{}]", target, index_var), indent + 1)?;
+            }
+            Schema::Map { key, value } => {
+                let len_var = self.local("len");
+                let key_var = self.local("key");
+                let value_var = self.local("value");
+                self.line(indent, &format!("let {} = decodeCollectionLength(data, pos, config)", len_var));
+                self.line(
+                    indent,
+                    &assign(format!("initOrderedTable[{}, {}]()", nim_type(key)?, nim_type(value)?)),
+                );
+                self.line(indent, &format!("for _ in 0 ..< {}:", len_var));
+                self.line(indent + 1, &format!("var {}: {}", key_var, nim_type(key)?));
+                self.line(indent + 1, &format!("var {}: {}", value_var, nim_type(value)?));
+                self.decode(key, &key_var, indent + 1)?;
+                self.decode(value, &value_var, indent + 1)?;
+                self.line(indent + 1, &format!("{}[{}] = {}", target, key_var, value_var));
+            }
+            Schema::Option { item } => {
+                let some_var = self.local("some");
+                self.line(indent, "case decodeUint[uint8](data, pos, config)");
+                self.line(indent, "of 0:");
+                self.line(indent + 1, &assign(format!("none({})", nim_type(item)?)));
+                self.line(indent, "of 1:");
+                self.line(indent + 1, &format!("var {}: {}", some_var, nim_type(item)?));
+                self.decode(item, &some_var, indent + 1)?;
+                self.line(indent + 1, &assign(format!("some({})", some_var)));
+                self.line(indent, "else:");
+                self.line(indent + 1, "raise newException(BincodeError, \"Invalid Option tag\")");
+            }
+            Schema::Tuple { items } => {
+                for (i, item) in items.iter().enumerate() {
+                    self.decode(item, &format!("{}[{}]", target, i), indent)?;
+                }
+            }
+            Schema::Struct { name, .. } | Schema::Enum { name, .. } => {
+                self.line(indent, &assign(format!("decode{}(data, pos, config)", name)))
+            }
+            Schema::Unit | Schema::U128 | Schema::I128 | Schema::Ref { .. } => {
+                return Err(CodegenError(format!("{:?} has no Nim counterpart", schema)));
+            }
+        }
+        Ok(())
+    }
+}
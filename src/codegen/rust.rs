@@ -0,0 +1,390 @@
+//! Rust struct generator from Nim object declarations.
+//!
+//! [`parse_nim_objects`] reads a restricted subset of Nim: `type X* = object`
+//! declarations (in a `type` section or one per line) whose fields are
+//! integers, `byte`, `char`, `bool`, floats, `string`, `Rune`, `seq[T]`,
+//! `array[N, T]`, `Option[T]` or another object from the same source. Each
+//! object becomes a [`Schema::Struct`] with fields in declaration order.
+//!
+//! [`generate_rust_module`] turns those schemas into Rust structs deriving
+//! `Encode`/`Decode` (and `BincodeSchema`), so a message type first defined in
+//! Nim gets a Rust counterpart with the same wire layout.
+//!
+//! Type mapping follows the Nim side: `int`/`uint` are 64-bit, `char` is a
+//! byte, `float` is `f64`. Other declarations (enums, aliases, `ref object`,
+//! object variants) are skipped; generic objects are rejected. Field names
+//! become snake_case, with keywords written as raw identifiers (or with a
+//! trailing `_` for `self`, `super` and `crate`, which cannot be raw).
+
+use std::fmt::Write;
+
+use super::CodegenError;
+use crate::schema::{Field, Schema};
+
+/// Keywords and reserved words, written as raw identifiers
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be raw identifiers; these get a trailing `_`
+const RUST_PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// A Nim object before nested object names are resolved
+struct NimObject {
+    name: String,
+    fields: Vec<(String, NimType)>,
+}
+
+/// A Nim field type before nested object names are resolved
+#[derive(Clone)]
+enum NimType {
+    Schema(Schema),
+    Object(String),
+    Seq(Box<NimType>),
+    Array(usize, Box<NimType>),
+    Option(Box<NimType>),
+}
+
+/// Parse every supported `object` declaration in `source`, in source order
+pub fn parse_nim_objects(source: &str) -> Result<Vec<Schema>, CodegenError> {
+    let objects = parse_objects(source)?;
+    let mut resolved: Vec<Schema> = Vec::new();
+    for object in &objects {
+        resolved.push(resolve_object(object, &objects, &mut Vec::new())?);
+    }
+    Ok(resolved)
+}
+
+/// Generate Rust structs for the given struct schemas
+pub fn generate_rust_module(structs: &[Schema]) -> Result<String, CodegenError> {
+    let mut out = String::new();
+    let _ = writeln!(out, "// Generated by rust_codegen from Nim object declarations. Do not edit.");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "use bincode::{{Decode, Encode}};");
+    let _ = writeln!(out, "use bincode_wrapper::schema::BincodeSchema;");
+    for schema in structs {
+        let Schema::Struct { name, fields } = schema else {
+            return Err(CodegenError(format!("{:?} is not a struct", schema)));
+        };
+        let _ = writeln!(out);
+        let _ = writeln!(out, "#[derive(Debug, Clone, PartialEq, Encode, Decode, BincodeSchema)]");
+        let _ = writeln!(out, "pub struct {} {{", name);
+        for field in fields {
+            let _ = writeln!(out, "    pub {}: {},", rust_ident(&field.name), rust_type(&field.schema)?);
+        }
+        let _ = writeln!(out, "}}");
+    }
+    Ok(out)
+}
+
+/// `firstName` -> `first_name`; a run of capitals is one word, so `senderID`
+/// -> `sender_id` and `HTTPServer` -> `http_server`
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = i.checked_sub(1).map(|j| chars[j]);
+            let next = chars.get(i + 1);
+            let starts_word = match prev {
+                Some(p) if p.is_ascii_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_ascii_uppercase() => next.is_some_and(|n| n.is_ascii_lowercase()),
+                _ => false,
+            };
+            if starts_word {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn rust_ident(name: &str) -> String {
+    if RUST_PATH_KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else if RUST_KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn rust_type(schema: &Schema) -> Result<String, CodegenError> {
+    Ok(match schema {
+        Schema::Bool => "bool".to_string(),
+        Schema::U8 => "u8".to_string(),
+        Schema::U16 => "u16".to_string(),
+        Schema::U32 => "u32".to_string(),
+        Schema::U64 => "u64".to_string(),
+        Schema::I8 => "i8".to_string(),
+        Schema::I16 => "i16".to_string(),
+        Schema::I32 => "i32".to_string(),
+        Schema::I64 => "i64".to_string(),
+        Schema::F32 => "f32".to_string(),
+        Schema::F64 => "f64".to_string(),
+        Schema::Char => "char".to_string(),
+        Schema::String => "String".to_string(),
+        Schema::Seq { item } => format!("Vec<{}>", rust_type(item)?),
+        Schema::Array { len, item } => format!("[{}; {}]", rust_type(item)?, len),
+        Schema::Option { item } => format!("Option<{}>", rust_type(item)?),
+        Schema::Struct { name, .. } => name.clone(),
+        _ => return Err(CodegenError(format!("{:?} cannot come from a Nim object field", schema))),
+    })
+}
+
+fn resolve_object(
+    object: &NimObject,
+    objects: &[NimObject],
+    stack: &mut Vec<String>,
+) -> Result<Schema, CodegenError> {
+    if stack.contains(&object.name) {
+        return Err(CodegenError(format!("object '{}' contains itself", object.name)));
+    }
+    stack.push(object.name.clone());
+    let fields = object
+        .fields
+        .iter()
+        .map(|(name, ty)| {
+            Ok(Field { name: snake_case(name), schema: resolve_type(ty, objects, stack)? })
+        })
+        .collect::<Result<Vec<_>, CodegenError>>()?;
+    stack.pop();
+    Ok(Schema::Struct { name: object.name.clone(), fields })
+}
+
+fn resolve_type(
+    ty: &NimType,
+    objects: &[NimObject],
+    stack: &mut Vec<String>,
+) -> Result<Schema, CodegenError> {
+    Ok(match ty {
+        NimType::Schema(schema) => schema.clone(),
+        NimType::Object(name) => {
+            let object = objects
+                .iter()
+                .find(|object| object.name == *name)
+                .ok_or_else(|| CodegenError(format!("unknown type '{}'", name)))?;
+            resolve_object(object, objects, stack)?
+        }
+        NimType::Seq(item) => Schema::Seq { item: Box::new(resolve_type(item, objects, stack)?) },
+        NimType::Array(len, item) => {
+            Schema::Array { len: *len, item: Box::new(resolve_type(item, objects, stack)?) }
+        }
+        NimType::Option(item) => {
+            Schema::Option { item: Box::new(resolve_type(item, objects, stack)?) }
+        }
+    })
+}
+
+/// Strip a `#` comment (Nim strings in type sections are not expected)
+fn strip_comment(line: &str) -> &str {
+    match line.find('#') {
+        Some(i) => &line[..i],
+        None => line,
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// `Person*`, `Person* {.pure.}` or `Person` -> `Person`
+fn decl_name(lhs: &str) -> &str {
+    let lhs = lhs.split("{.").next().unwrap_or(lhs).trim();
+    lhs.trim_end_matches('*').trim()
+}
+
+fn parse_objects(source: &str) -> Result<Vec<NimObject>, CodegenError> {
+    let lines: Vec<&str> = source.lines().map(strip_comment).collect();
+    let mut objects = Vec::new();
+    let mut in_type_section = false;
+    let mut section_indent = 0;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+        i += 1;
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = indent_of(line);
+        if in_type_section && indent <= section_indent {
+            in_type_section = false;
+        }
+
+        let decl = if trimmed == "type" {
+            in_type_section = true;
+            section_indent = indent;
+            continue;
+        } else if let Some(rest) = trimmed.strip_prefix("type ") {
+            rest.trim()
+        } else if in_type_section {
+            trimmed
+        } else {
+            continue;
+        };
+
+        let Some((lhs, rhs)) = decl.split_once('=') else {
+            continue;
+        };
+        if rhs.trim() != "object" {
+            // Enums, aliases, distinct types, ref objects and inheritance are out of scope
+            continue;
+        }
+
+        let name = decl_name(lhs);
+        if let Some((base, _)) = name.split_once('[') {
+            return Err(CodegenError(format!(
+                "generic objects are not supported (in '{}')",
+                base.trim_end_matches('*').trim()
+            )));
+        }
+        let mut object = NimObject { name: name.to_string(), fields: Vec::new() };
+        let mut field_indent = None;
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                i += 1;
+                continue;
+            }
+            if indent_of(line) <= indent || field_indent.is_some_and(|f| indent_of(line) < f) {
+                break;
+            }
+            field_indent.get_or_insert(indent_of(line));
+            i += 1;
+            if trimmed.starts_with("case ") {
+                return Err(CodegenError(format!(
+                    "object variants are not supported (in '{}')",
+                    object.name
+                )));
+            }
+            let (names, ty) = trimmed.split_once(':').ok_or_else(|| {
+                CodegenError(format!("expected `name: Type` in '{}', found `{}`", object.name, trimmed))
+            })?;
+            let ty = TypeParser { input: ty.trim(), pos: 0 }.parse_all()?;
+            let names: Vec<&str> = names.split(',').map(|name| name.trim().trim_end_matches('*').trim_matches('`')).collect();
+            for name in names {
+                object.fields.push((name.to_string(), ty.clone()));
+            }
+        }
+        objects.push(object);
+    }
+    Ok(objects)
+}
+
+/// Recursive-descent parser for Nim type expressions such as `seq[array[4, byte]]`
+struct TypeParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl TypeParser<'_> {
+    fn parse_all(mut self) -> Result<NimType, CodegenError> {
+        let ty = self.parse_type()?;
+        self.skip_ws();
+        if self.pos != self.input.len() {
+            return Err(self.error("unexpected trailing input"));
+        }
+        Ok(ty)
+    }
+
+    fn error(&self, message: &str) -> CodegenError {
+        CodegenError(format!("{} in type `{}`", message, self.input))
+    }
+
+    fn skip_ws(&mut self) {
+        while self.input[self.pos..].starts_with(' ') {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), CodegenError> {
+        self.skip_ws();
+        if self.input[self.pos..].starts_with(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", c)))
+        }
+    }
+
+    fn word(&mut self) -> &str {
+        self.skip_ws();
+        let start = self.pos;
+        let rest = &self.input[start..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &self.input[start..start + len]
+    }
+
+    fn parse_type(&mut self) -> Result<NimType, CodegenError> {
+        let name = self.word().to_string();
+        let schema = match name.as_str() {
+            "int" | "int64" => Schema::I64,
+            "int8" => Schema::I8,
+            "int16" => Schema::I16,
+            "int32" => Schema::I32,
+            "uint" | "uint64" => Schema::U64,
+            "uint8" | "byte" | "char" => Schema::U8,
+            "uint16" => Schema::U16,
+            "uint32" => Schema::U32,
+            "bool" => Schema::Bool,
+            "float32" => Schema::F32,
+            "float" | "float64" => Schema::F64,
+            "string" => Schema::String,
+            "Rune" => Schema::Char,
+            "seq" => {
+                self.expect('[')?;
+                let item = self.parse_type()?;
+                self.expect(']')?;
+                return Ok(NimType::Seq(Box::new(item)));
+            }
+            "Option" => {
+                self.expect('[')?;
+                let item = self.parse_type()?;
+                self.expect(']')?;
+                return Ok(NimType::Option(Box::new(item)));
+            }
+            "array" => {
+                self.expect('[')?;
+                let len = self.parse_array_len()?;
+                self.expect(',')?;
+                let item = self.parse_type()?;
+                self.expect(']')?;
+                return Ok(NimType::Array(len, Box::new(item)));
+            }
+            "" => return Err(self.error("expected a type name")),
+            _ if name.starts_with(|c: char| c.is_ascii_uppercase()) => {
+                return Ok(NimType::Object(name));
+            }
+            _ => return Err(self.error(&format!("unsupported type `{}`", name))),
+        };
+        Ok(NimType::Schema(schema))
+    }
+
+    /// `4` or `0..3`
+    fn parse_array_len(&mut self) -> Result<usize, CodegenError> {
+        let bound = self.word().to_string();
+        let parse = |s: &str| s.parse::<usize>().map_err(|_| self.error("expected an array length"));
+        match bound.split_once("..") {
+            Some((low, high)) => {
+                let (low, high) = (parse(low)?, parse(high)?);
+                if high < low {
+                    return Err(self.error("empty array range"));
+                }
+                Ok(high - low + 1)
+            }
+            None => parse(&bound),
+        }
+    }
+}
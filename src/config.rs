@@ -0,0 +1,145 @@
+//! Bincode configurations known to the Rust harness.
+//!
+//! bincode v2 configs are type-level, so each one is exposed as a function
+//! returning its `Configuration`, plus a [`KnownConfig`] value for runtime
+//! selection.
+
+use bincode::config::{BigEndian, Configuration, Fixint, Limit, LittleEndian, Varint};
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+
+/// Size limit used by the cross-verification configs (4 GiB + length prefix)
+pub const CROSS_SIZE_LIMIT: usize = 4294967305;
+
+/// Variable-length encoding config (LEB128)
+pub fn variable_config() -> Configuration<LittleEndian, Varint, Limit<CROSS_SIZE_LIMIT>> {
+    bincode::config::standard()
+        .with_little_endian()
+        .with_variable_int_encoding()
+        .with_limit::<CROSS_SIZE_LIMIT>()
+}
+
+/// Fixed 8-byte encoding config
+pub fn fixed8_config() -> Configuration<LittleEndian, Fixint, Limit<CROSS_SIZE_LIMIT>> {
+    bincode::config::standard()
+        .with_little_endian()
+        .with_fixed_int_encoding()
+        .with_limit::<CROSS_SIZE_LIMIT>()
+}
+
+/// Variable-length encoding config, big-endian marker payloads
+pub fn variable_be_config() -> Configuration<BigEndian, Varint, Limit<CROSS_SIZE_LIMIT>> {
+    bincode::config::standard()
+        .with_big_endian()
+        .with_variable_int_encoding()
+        .with_limit::<CROSS_SIZE_LIMIT>()
+}
+
+/// Fixed 8-byte encoding config, big-endian
+pub fn fixed8_be_config() -> Configuration<BigEndian, Fixint, Limit<CROSS_SIZE_LIMIT>> {
+    bincode::config::standard()
+        .with_big_endian()
+        .with_fixed_int_encoding()
+        .with_limit::<CROSS_SIZE_LIMIT>()
+}
+
+/// Runtime handle for one of the harness configs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownConfig {
+    Variable,
+    Fixed8,
+    VariableBigEndian,
+    Fixed8BigEndian,
+}
+
+/// Run `$body` with `$config` bound to the type-level config for `$known`,
+/// optionally with a `limit = N` size limit instead of [`CROSS_SIZE_LIMIT`].
+macro_rules! with_known_config {
+    ($known:expr, |$config:ident| $body:expr) => {
+        $crate::config::with_known_config!($known, limit = $crate::config::CROSS_SIZE_LIMIT, |$config| $body)
+    };
+    ($known:expr, limit = $limit:expr, |$config:ident| $body:expr) => {
+        match $known {
+            $crate::config::KnownConfig::Variable => {
+                let $config = $crate::config::variable_config().with_limit::<{ $limit }>();
+                $body
+            }
+            $crate::config::KnownConfig::Fixed8 => {
+                let $config = $crate::config::fixed8_config().with_limit::<{ $limit }>();
+                $body
+            }
+            $crate::config::KnownConfig::VariableBigEndian => {
+                let $config = $crate::config::variable_be_config().with_limit::<{ $limit }>();
+                $body
+            }
+            $crate::config::KnownConfig::Fixed8BigEndian => {
+                let $config = $crate::config::fixed8_be_config().with_limit::<{ $limit }>();
+                $body
+            }
+        }
+    };
+}
+pub(crate) use with_known_config;
+
+impl KnownConfig {
+    /// Every config, in the order used for reporting
+    pub const ALL: [KnownConfig; 4] = [
+        KnownConfig::Variable,
+        KnownConfig::Fixed8,
+        KnownConfig::VariableBigEndian,
+        KnownConfig::Fixed8BigEndian,
+    ];
+
+    /// Short name used in file names and on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            KnownConfig::Variable => "variable",
+            KnownConfig::Fixed8 => "fixed8",
+            KnownConfig::VariableBigEndian => "variable_be",
+            KnownConfig::Fixed8BigEndian => "fixed8_be",
+        }
+    }
+
+    /// Look up a config by its short name
+    pub fn from_name(name: &str) -> Option<KnownConfig> {
+        KnownConfig::ALL.into_iter().find(|config| config.name() == name)
+    }
+
+    /// The Nim `BincodeConfig` expression that produces the same wire format.
+    ///
+    /// Nim always writes variable-length markers little-endian, so only
+    /// payloads shorter than 251 bytes match `VariableBigEndian`.
+    pub fn nim_config(self) -> &'static str {
+        match self {
+            KnownConfig::Variable => "standard().withVariableIntEncoding()",
+            KnownConfig::Fixed8 => "standard().withFixedIntEncoding(8)",
+            KnownConfig::VariableBigEndian => "standard().withBigEndian().withVariableIntEncoding()",
+            KnownConfig::Fixed8BigEndian => "standard().withBigEndian().withFixedIntEncoding(8)",
+        }
+    }
+
+    /// Encode `value` with this config
+    pub fn encode<T: Encode>(self, value: &T) -> Result<Vec<u8>, EncodeError> {
+        with_known_config!(self, |config| bincode::encode_to_vec(value, config))
+    }
+
+    /// Decode a `T` from the start of `bytes`, returning it with the number of bytes read
+    pub fn decode<T: Decode<()>>(self, bytes: &[u8]) -> Result<(T, usize), DecodeError> {
+        with_known_config!(self, |config| bincode::decode_from_slice(bytes, config))
+    }
+
+    /// [`decode`](Self::decode), but with a `LIMIT`-byte size limit instead of
+    /// [`CROSS_SIZE_LIMIT`]
+    pub fn decode_with_limit<T: Decode<()>, const LIMIT: usize>(
+        self,
+        bytes: &[u8],
+    ) -> Result<(T, usize), DecodeError> {
+        with_known_config!(self, limit = LIMIT, |config| bincode::decode_from_slice(bytes, config))
+    }
+}
+
+impl std::fmt::Display for KnownConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,317 @@
+//! Recursive reference types, depth-bomb vectors and depth-limited decoding.
+//!
+//! [`Tree`] and [`Chain`] nest without bound, and bincode decodes them by
+//! recursion: an input of 100k nested nodes (a few hundred KiB) overflows the
+//! stack. [`decode_with_max_depth`] decodes them with a [`DepthLimit`] context
+//! and fails with [`DepthError::DepthLimitExceeded`] instead.
+//!
+//! Depth counts the nested `Node`s of a [`Tree`] or `Some`s of a [`Chain`]:
+//! `Leaf` and `Chain(None)` are depth 0, `Node(Leaf, Leaf)` is depth 1. An input
+//! of depth `max_depth` decodes; one level deeper fails as soon as the decoder
+//! enters that level, before reading the rest of the input.
+//!
+//! [`depth_vectors`] builds inputs at [`DEPTHS`] without recursion, and
+//! [`render_tsv`] records the outcome of each under every [`MAX_DEPTHS`]
+//! limit, so a Nim decoder for recursive types can apply the same limit.
+
+use std::fmt;
+
+use bincode::de::Decoder;
+use bincode::error::{AllowedEnumVariants, DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+
+use crate::config::{with_known_config, KnownConfig};
+use crate::mutation::{error_kind, Outcome};
+use crate::schema::{BincodeSchema, Field, Schema, Variant};
+use crate::vectors::fnv1a64;
+
+/// Manifest of the exported vectors, in the shared test-data directory
+pub const DEPTH_FILE: &str = "depth_vectors.tsv";
+
+/// Nesting depths of the vectors
+pub const DEPTHS: [usize; 3] = [1, 100, 100_000];
+
+/// Limit [`decode_with_max_depth`] callers should start from
+pub const DEFAULT_MAX_DEPTH: usize = 1024;
+
+/// Limits each vector is decoded under in the manifest
+pub const MAX_DEPTHS: [usize; 3] = [1, 100, DEFAULT_MAX_DEPTH];
+
+/// Configs in the vectors
+pub const DEPTH_CONFIGS: [KnownConfig; 2] = [KnownConfig::Variable, KnownConfig::Fixed8];
+
+/// Binary tree; the variant index is written as a `u32`
+#[derive(Debug, Clone, PartialEq, Eq, Encode)]
+pub enum Tree {
+    Leaf,
+    Node(Box<Tree>, Box<Tree>),
+}
+
+/// Linked chain of `Option<Box<...>>`; the option tag is one byte
+#[derive(Debug, Clone, PartialEq, Eq, Encode)]
+pub struct Chain(pub Option<Box<Chain>>);
+
+impl BincodeSchema for Tree {
+    fn schema() -> Schema {
+        let tree = || Schema::Ref { name: "Tree".to_string() };
+        let field = |name: &str| Field { name: name.to_string(), schema: tree() };
+        Schema::Enum {
+            name: "Tree".to_string(),
+            variants: vec![
+                Variant { name: "Leaf".to_string(), index: 0, fields: Vec::new() },
+                Variant { name: "Node".to_string(), index: 1, fields: vec![field("0"), field("1")] },
+            ],
+        }
+    }
+}
+
+impl BincodeSchema for Chain {
+    fn schema() -> Schema {
+        let next = Schema::Option { item: Box::new(Schema::Ref { name: "Chain".to_string() }) };
+        Schema::Struct { name: "Chain".to_string(), fields: vec![Field { name: "0".to_string(), schema: next }] }
+    }
+}
+
+/// Decode context that tracks nesting depth
+pub trait DepthContext {
+    /// Enter one nesting level; fails past the limit
+    fn enter(&mut self) -> Result<(), DecodeError>;
+    /// Leave the level entered last
+    fn leave(&mut self);
+}
+
+/// No limit: plain `bincode::decode_from_slice` recurses as deep as the input
+impl DepthContext for () {
+    fn enter(&mut self) -> Result<(), DecodeError> {
+        Ok(())
+    }
+
+    fn leave(&mut self) {}
+}
+
+/// Depth limit for [`decode_with_max_depth`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthLimit {
+    pub max_depth: usize,
+    depth: usize,
+    exceeded: bool,
+}
+
+impl DepthLimit {
+    pub fn new(max_depth: usize) -> DepthLimit {
+        DepthLimit { max_depth, depth: 0, exceeded: false }
+    }
+
+    /// Whether a decode with this context went past the limit
+    pub fn exceeded(&self) -> bool {
+        self.exceeded
+    }
+}
+
+impl DepthContext for DepthLimit {
+    fn enter(&mut self) -> Result<(), DecodeError> {
+        if self.depth >= self.max_depth {
+            self.exceeded = true;
+            return Err(DecodeError::OtherString(format!("nesting depth exceeds {}", self.max_depth)));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+impl<T: DepthContext> DepthContext for &mut T {
+    fn enter(&mut self) -> Result<(), DecodeError> {
+        (**self).enter()
+    }
+
+    fn leave(&mut self) {
+        (**self).leave()
+    }
+}
+
+impl<Context: DepthContext> Decode<Context> for Tree {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(Tree::Leaf),
+            1 => {
+                decoder.context().enter()?;
+                let left = Tree::decode(decoder)?;
+                let right = Tree::decode(decoder)?;
+                decoder.context().leave();
+                Ok(Tree::Node(Box::new(left), Box::new(right)))
+            }
+            found => Err(DecodeError::UnexpectedVariant {
+                type_name: "Tree",
+                allowed: &AllowedEnumVariants::Range { min: 0, max: 1 },
+                found,
+            }),
+        }
+    }
+}
+
+impl<Context: DepthContext> Decode<Context> for Chain {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u8::decode(decoder)? {
+            0 => Ok(Chain(None)),
+            1 => {
+                decoder.context().enter()?;
+                let next = Chain::decode(decoder)?;
+                decoder.context().leave();
+                Ok(Chain(Some(Box::new(next))))
+            }
+            found => Err(DecodeError::UnexpectedVariant {
+                type_name: "Option<Box<Chain>>",
+                allowed: &AllowedEnumVariants::Range { min: 0, max: 1 },
+                found: u32::from(found),
+            }),
+        }
+    }
+}
+
+/// Error from [`decode_with_max_depth`]
+#[derive(Debug)]
+pub enum DepthError {
+    /// The input nests deeper than `max_depth`
+    DepthLimitExceeded { max_depth: usize },
+    Decode(DecodeError),
+}
+
+impl DepthError {
+    /// `DepthLimitExceeded`, or the `DecodeError` variant name
+    pub fn kind(&self) -> String {
+        match self {
+            DepthError::DepthLimitExceeded { .. } => "DepthLimitExceeded".to_string(),
+            DepthError::Decode(e) => error_kind(e),
+        }
+    }
+}
+
+impl fmt::Display for DepthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DepthError::DepthLimitExceeded { max_depth } => write!(f, "nesting depth exceeds {}", max_depth),
+            DepthError::Decode(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for DepthError {}
+
+/// Decode a `T` from the start of `bytes`, failing once the input nests
+/// deeper than `max_depth`
+pub fn decode_with_max_depth<T>(bytes: &[u8], config: KnownConfig, max_depth: usize) -> Result<(T, usize), DepthError>
+where
+    T: for<'a> Decode<&'a mut DepthLimit>,
+{
+    let mut limit = DepthLimit::new(max_depth);
+    let result = with_known_config!(config, |config| {
+        bincode::decode_from_slice_with_context(bytes, config, &mut limit)
+    });
+    result.map_err(|e| if limit.exceeded() { DepthError::DepthLimitExceeded { max_depth } } else { DepthError::Decode(e) })
+}
+
+/// [`decode_with_max_depth`], returning the value re-encoded
+fn decode_reencoded<T>(bytes: &[u8], config: KnownConfig, max_depth: usize) -> Result<(Vec<u8>, usize), DepthError>
+where
+    T: for<'a> Decode<&'a mut DepthLimit> + Encode,
+{
+    let (value, bytes_read) = decode_with_max_depth::<T>(bytes, config, max_depth)?;
+    Ok((config.encode(&value).expect("a decoded value re-encodes"), bytes_read))
+}
+
+/// Recursive type of a vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthType {
+    Tree,
+    Chain,
+}
+
+impl DepthType {
+    pub const ALL: [DepthType; 2] = [DepthType::Tree, DepthType::Chain];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DepthType::Tree => "Tree",
+            DepthType::Chain => "Chain",
+        }
+    }
+}
+
+/// A value of `depth` nesting levels, as bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthVector {
+    pub depth_type: DepthType,
+    pub config: KnownConfig,
+    pub depth: usize,
+}
+
+impl DepthVector {
+    /// The encoding of `depth` nested nodes, built without recursion. A
+    /// [`Tree`] nests each deeper node on the left of a leaf; a [`Chain`] nests
+    /// `Some`s around a `None`.
+    pub fn bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        let (node, end) = match self.depth_type {
+            // Preorder: every node, then the innermost leaf, then each node's right leaf
+            DepthType::Tree => (self.config.encode(&1u32)?, self.config.encode(&0u32)?.repeat(self.depth + 1)),
+            DepthType::Chain => (vec![1u8], vec![0u8]),
+        };
+        let mut out = node.repeat(self.depth);
+        out.extend_from_slice(&end);
+        Ok(out)
+    }
+
+    /// Decode the bytes under `max_depth` and label the result
+    pub fn outcome(&self, max_depth: usize) -> Outcome {
+        let bytes = self.bytes().expect("variant tags encode");
+        let decoded = match self.depth_type {
+            DepthType::Tree => decode_reencoded::<Tree>(&bytes, self.config, max_depth),
+            DepthType::Chain => decode_reencoded::<Chain>(&bytes, self.config, max_depth),
+        };
+        match decoded {
+            Ok((reencoded, bytes_read)) if bytes_read == bytes.len() => Outcome::Accepted { hash: fnv1a64(&reencoded) },
+            Ok(_) => Outcome::Rejected { kind: "TrailingBytes".to_string() },
+            Err(e) => Outcome::Rejected { kind: e.kind() },
+        }
+    }
+
+    /// `depth_<type>_<config>_<depth>.bin`
+    pub fn file(&self) -> String {
+        format!("depth_{}_{}_{}.bin", self.depth_type.name().to_lowercase(), self.config, self.depth)
+    }
+}
+
+/// Every type × config × depth
+pub fn depth_vectors() -> Vec<DepthVector> {
+    let mut vectors = Vec::new();
+    for depth_type in DepthType::ALL {
+        for config in DEPTH_CONFIGS {
+            for depth in DEPTHS {
+                vectors.push(DepthVector { depth_type, config, depth });
+            }
+        }
+    }
+    vectors
+}
+
+/// Each vector under each of [`MAX_DEPTHS`] as TSV: type, config, depth, max depth, file, outcome
+pub fn render_tsv(vectors: &[DepthVector]) -> String {
+    let mut out = String::from("# type\tconfig\tdepth\tmax_depth\tfile\toutcome\n");
+    for vector in vectors {
+        for max_depth in MAX_DEPTHS {
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\n",
+                vector.depth_type.name(),
+                vector.config,
+                vector.depth,
+                max_depth,
+                vector.file(),
+                vector.outcome(max_depth)
+            ));
+        }
+    }
+    out
+}
@@ -0,0 +1,91 @@
+//! Config auto-detection for bincode blobs of unknown origin.
+//!
+//! Every [`KnownConfig`] is tried against an expected type and the candidates
+//! are ranked: clean decodes (all input consumed) first, then decodes that
+//! leave trailing bytes, then failures.
+
+use bincode::error::DecodeError;
+use bincode::Decode;
+
+use crate::config::KnownConfig;
+
+/// Result of decoding the input with one config
+#[derive(Debug)]
+pub enum Outcome {
+    /// Decoded and consumed every input byte
+    Clean,
+    /// Decoded, but `bytes_read` is less than the input length
+    Trailing { bytes_read: usize },
+    /// Decoding failed
+    Failed(DecodeError),
+}
+
+/// One config tried by [`detect_config`]
+#[derive(Debug)]
+pub struct Candidate {
+    pub config: KnownConfig,
+    pub outcome: Outcome,
+}
+
+impl Candidate {
+    /// Whether the whole input decoded with no trailing bytes
+    pub fn is_clean(&self) -> bool {
+        matches!(self.outcome, Outcome::Clean)
+    }
+
+    /// Sort key: clean decodes first, then the longest partial decode, then failures
+    fn rank_key(&self) -> (u8, std::cmp::Reverse<usize>) {
+        match self.outcome {
+            Outcome::Clean => (0, std::cmp::Reverse(0)),
+            Outcome::Trailing { bytes_read } => (1, std::cmp::Reverse(bytes_read)),
+            Outcome::Failed(_) => (2, std::cmp::Reverse(0)),
+        }
+    }
+}
+
+/// Try every known config against `bytes` decoded as a `T`, best candidate first.
+///
+/// Ties keep the order of [`KnownConfig::ALL`].
+pub fn detect_config<T: Decode<()>>(bytes: &[u8]) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = KnownConfig::ALL
+        .into_iter()
+        .map(|config| {
+            let outcome = match config.decode::<T>(bytes) {
+                Ok((_, bytes_read)) if bytes_read == bytes.len() => Outcome::Clean,
+                Ok((_, bytes_read)) => Outcome::Trailing { bytes_read },
+                Err(e) => Outcome::Failed(e),
+            };
+            Candidate { config, outcome }
+        })
+        .collect();
+    candidates.sort_by_key(Candidate::rank_key);
+    candidates
+}
+
+/// Expected payload types selectable at runtime (e.g. from the command line)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedType {
+    /// `Vec<u8>`, as produced by Nim `serialize`
+    Bytes,
+    /// `String`, as produced by Nim `serializeString`
+    String,
+}
+
+impl ExpectedType {
+    /// Parse a type name such as `Vec<u8>` or `String`
+    pub fn from_name(name: &str) -> Option<ExpectedType> {
+        match name {
+            "Vec<u8>" | "vec-u8" | "bytes" => Some(ExpectedType::Bytes),
+            "String" | "string" => Some(ExpectedType::String),
+            _ => None,
+        }
+    }
+
+    /// Run [`detect_config`] for this type
+    pub fn detect(self, bytes: &[u8]) -> Vec<Candidate> {
+        match self {
+            ExpectedType::Bytes => detect_config::<Vec<u8>>(bytes),
+            ExpectedType::String => detect_config::<String>(bytes),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Pre-encode size limit, matching Nim's `checkSizeLimit`.
+//!
+//! Nim's `serialize` and `serializeString` refuse to encode a payload longer
+//! than `config.sizeLimit` bytes, before writing anything. The length prefix is
+//! not counted, so a payload of exactly `sizeLimit` bytes is accepted. Rust
+//! bincode only applies its limit when decoding. [`encode_checked`] performs
+//! the same check as Nim and fails with [`LimitedEncodeError::SizeLimitExceeded`].
+//!
+//! [`limit_vectors`] records, for payload sizes around a set of limits, whether
+//! the Rust side accepts the encode and the hash of the bytes it writes.
+//! `test_encode_limits.nim` checks that Nim makes the same decision.
+
+use std::fmt;
+
+use bincode::error::EncodeError;
+use bincode::Encode;
+
+use crate::config::KnownConfig;
+use crate::mutation::Outcome;
+use crate::runtime_config::RuntimeConfig;
+use crate::vectors::fnv1a64;
+
+/// File name used in the shared test-data directory
+pub const LIMIT_FILE: &str = "encode_limits.tsv";
+
+/// Limits around which payload sizes are sampled
+pub const EDGE_LIMITS: [u64; 6] = [0, 1, 250, 251, 65535, 65536];
+
+/// Payload sizes from `limit - SIZE_DELTA` to `limit + SIZE_DELTA` are sampled
+pub const SIZE_DELTA: u64 = 2;
+
+/// Configs in the vectors; `variable_be` is left out because Nim writes
+/// variable-length markers little-endian
+pub const LIMIT_CONFIGS: [KnownConfig; 3] = [KnownConfig::Variable, KnownConfig::Fixed8, KnownConfig::Fixed8BigEndian];
+
+/// Byte every payload is filled with (`a`, so payloads are also valid strings)
+pub const FILL_BYTE: u8 = b'a';
+
+/// Error from [`encode_checked`]
+#[derive(Debug)]
+pub enum LimitedEncodeError {
+    /// The payload is longer than the config's size limit
+    SizeLimitExceeded { len: u64, limit: u64 },
+    Encode(EncodeError),
+}
+
+impl fmt::Display for LimitedEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitedEncodeError::SizeLimitExceeded { len, limit } => {
+                write!(f, "payload of {} bytes exceeds the size limit of {} bytes", len, limit)
+            }
+            LimitedEncodeError::Encode(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for LimitedEncodeError {}
+
+/// A value Nim checks against the size limit before encoding
+pub trait LimitedPayload: Encode {
+    /// Number of bytes compared with the limit: the byte count for byte
+    /// sequences, the UTF-8 byte count for strings
+    fn payload_len(&self) -> usize;
+}
+
+impl LimitedPayload for [u8] {
+    fn payload_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl LimitedPayload for Vec<u8> {
+    fn payload_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl LimitedPayload for str {
+    fn payload_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl LimitedPayload for String {
+    fn payload_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Encode `value` like Nim's `serialize`/`serializeString`: refuse payloads
+/// longer than `config.size_limit`, then write the prefix and payload
+/// without further limits
+pub fn encode_checked<T: LimitedPayload + ?Sized>(
+    value: &T,
+    config: &RuntimeConfig,
+) -> Result<Vec<u8>, LimitedEncodeError> {
+    let len = value.payload_len() as u64;
+    if len > config.size_limit {
+        return Err(LimitedEncodeError::SizeLimitExceeded { len, limit: config.size_limit });
+    }
+    config.with_limit(u64::MAX).encode(value).map_err(LimitedEncodeError::Encode)
+}
+
+/// Payload type of a vector, named as in the type column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadType {
+    /// `Vec<u8>`, Nim's `serialize`
+    Bytes,
+    /// `String`, Nim's `serializeString`
+    String,
+}
+
+impl PayloadType {
+    pub fn name(self) -> &'static str {
+        match self {
+            PayloadType::Bytes => "Vec<u8>",
+            PayloadType::String => "String",
+        }
+    }
+}
+
+/// One encode attempt and the Rust outcome
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitVector {
+    pub config: KnownConfig,
+    pub payload_type: PayloadType,
+    pub limit: u64,
+    pub len: u64,
+    /// FNV-1a of the encoding, or `err:SizeLimitExceeded`
+    pub outcome: Outcome,
+}
+
+/// Encode `len` fill bytes as `payload_type` with `config` limited to `limit`
+pub fn limit_outcome(config: KnownConfig, payload_type: PayloadType, limit: u64, len: u64) -> Outcome {
+    let runtime = RuntimeConfig::from(config).with_limit(limit);
+    let bytes = vec![FILL_BYTE; len as usize];
+    let encoded = match payload_type {
+        PayloadType::Bytes => encode_checked(&bytes, &runtime),
+        PayloadType::String => encode_checked(&String::from_utf8(bytes).expect("fill byte is ASCII"), &runtime),
+    };
+    match encoded {
+        Ok(encoded) => Outcome::Accepted { hash: fnv1a64(&encoded) },
+        Err(LimitedEncodeError::SizeLimitExceeded { .. }) => Outcome::Rejected { kind: "SizeLimitExceeded".to_string() },
+        Err(LimitedEncodeError::Encode(e)) => {
+            Outcome::Rejected { kind: format!("{:?}", e).chars().take_while(char::is_ascii_alphanumeric).collect() }
+        }
+    }
+}
+
+/// Every config × payload type × limit × size around the limit
+pub fn limit_vectors() -> Vec<LimitVector> {
+    let mut vectors = Vec::new();
+    for config in LIMIT_CONFIGS {
+        for payload_type in [PayloadType::Bytes, PayloadType::String] {
+            for limit in EDGE_LIMITS {
+                for len in limit.saturating_sub(SIZE_DELTA)..=limit + SIZE_DELTA {
+                    let outcome = limit_outcome(config, payload_type, limit, len);
+                    vectors.push(LimitVector { config, payload_type, limit, len, outcome });
+                }
+            }
+        }
+    }
+    vectors
+}
+
+/// The vectors as TSV: config, type, limit, len, outcome
+pub fn render_tsv(vectors: &[LimitVector]) -> String {
+    let mut out = String::from("# config\ttype\tlimit\tlen\toutcome\n");
+    for vector in vectors {
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            vector.config,
+            vector.payload_type.name(),
+            vector.limit,
+            vector.len,
+            vector.outcome
+        ));
+    }
+    out
+}
@@ -0,0 +1,288 @@
+//! The `Vec<u8>` envelope written by Nim's integer helpers.
+//!
+//! `serializeInt32`, `serializeUint32` and `serializeInt64` in
+//! `bincode_helpers.nim` do not write a bare bincode integer. They write a
+//! byte vector (length prefix plus payload) whose payload is:
+//!
+//! - fixed encoding: the value widened to 64 bits (sign-extended for signed
+//!   types) in the config's byte order, 8 bytes
+//! - variable encoding: the value as unsigned LEB128, zigzag-encoded first for
+//!   signed types. This is not bincode's varint: 300 is `ac 02`, not `fb 2c 01`
+//!
+//! [`IntEnvelope`] encodes and decodes that format with any bincode config.
+//! Decoding is strict: the payload must be exactly 8 bytes or one canonical
+//! LEB128 value, and the value must fit the type. Nim ignores extra payload
+//! bytes and truncates out-of-range fixed values instead.
+//!
+//! [`envelope_vectors`] lists the values exchanged with
+//! `test_int_envelopes.nim` in both directions.
+
+use std::fmt;
+
+use bincode::config::{Config, Endianness, IntEncoding};
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+
+use crate::config::KnownConfig;
+
+/// Manifest of the exchanged files, in the shared test-data directory
+pub const ENVELOPE_FILE: &str = "int_envelopes.tsv";
+
+/// An integer wrapped the way Nim's `serializeInt32`/`serializeUint32`/`serializeInt64` wrap it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntEnvelope<T>(pub T);
+
+/// Integer types with a Nim envelope helper
+pub trait EnvelopeInt: Copy + fmt::Display {
+    /// Rust type name, e.g. `i32`
+    const TYPE_NAME: &'static str;
+    /// Nim helper that writes this envelope, e.g. `serializeInt32`
+    const NIM_HELPER: &'static str;
+
+    /// The value widened to 64 bits, as written under fixed encoding
+    fn to_wide(self) -> u64;
+    /// The inverse of [`to_wide`](Self::to_wide), if the value fits
+    fn from_wide(wide: u64) -> Option<Self>;
+    /// The value written as LEB128 under variable encoding
+    fn to_leb128_value(self) -> u64;
+    /// The inverse of [`to_leb128_value`](Self::to_leb128_value), if the value fits
+    fn from_leb128_value(value: u64) -> Option<Self>;
+}
+
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+impl EnvelopeInt for i32 {
+    const TYPE_NAME: &'static str = "i32";
+    const NIM_HELPER: &'static str = "serializeInt32";
+
+    fn to_wide(self) -> u64 {
+        i64::from(self) as u64
+    }
+
+    fn from_wide(wide: u64) -> Option<i32> {
+        i32::try_from(wide as i64).ok()
+    }
+
+    fn to_leb128_value(self) -> u64 {
+        zigzag_encode(i64::from(self))
+    }
+
+    fn from_leb128_value(value: u64) -> Option<i32> {
+        i32::try_from(zigzag_decode(value)).ok()
+    }
+}
+
+impl EnvelopeInt for u32 {
+    const TYPE_NAME: &'static str = "u32";
+    const NIM_HELPER: &'static str = "serializeUint32";
+
+    fn to_wide(self) -> u64 {
+        u64::from(self)
+    }
+
+    fn from_wide(wide: u64) -> Option<u32> {
+        u32::try_from(wide).ok()
+    }
+
+    fn to_leb128_value(self) -> u64 {
+        u64::from(self)
+    }
+
+    fn from_leb128_value(value: u64) -> Option<u32> {
+        u32::try_from(value).ok()
+    }
+}
+
+impl EnvelopeInt for i64 {
+    const TYPE_NAME: &'static str = "i64";
+    const NIM_HELPER: &'static str = "serializeInt64";
+
+    fn to_wide(self) -> u64 {
+        self as u64
+    }
+
+    fn from_wide(wide: u64) -> Option<i64> {
+        Some(wide as i64)
+    }
+
+    fn to_leb128_value(self) -> u64 {
+        zigzag_encode(self)
+    }
+
+    fn from_leb128_value(value: u64) -> Option<i64> {
+        Some(zigzag_decode(value))
+    }
+}
+
+/// Unsigned LEB128 encoding of `value`
+pub fn leb128_encode(mut value: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return out;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Decode `bytes` as exactly one canonical unsigned LEB128 value
+pub fn leb128_decode(bytes: &[u8]) -> Option<u64> {
+    let (last, init) = bytes.split_last()?;
+    if last & 0x80 != 0 || init.iter().any(|b| b & 0x80 == 0) || bytes.len() > 10 {
+        return None;
+    }
+    if bytes.len() > 1 && *last == 0 {
+        return None; // overlong
+    }
+    let mut value = 0u64;
+    for (i, b) in bytes.iter().enumerate() {
+        let bits = u64::from(b & 0x7f);
+        if i == 9 && bits > 1 {
+            return None;
+        }
+        value |= bits << (7 * i);
+    }
+    Some(value)
+}
+
+/// Envelope payload for `value` under `config`
+fn payload<T: EnvelopeInt, C: Config>(value: T, config: &C) -> Vec<u8> {
+    if matches!(config.int_encoding(), IntEncoding::Variable) {
+        leb128_encode(value.to_leb128_value())
+    } else if matches!(config.endianness(), Endianness::Big) {
+        value.to_wide().to_be_bytes().to_vec()
+    } else {
+        value.to_wide().to_le_bytes().to_vec()
+    }
+}
+
+impl<T: EnvelopeInt> Encode for IntEnvelope<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        payload(self.0, encoder.config()).encode(encoder)
+    }
+}
+
+impl<T: EnvelopeInt, Context> Decode<Context> for IntEnvelope<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let bytes = Vec::<u8>::decode(decoder)?;
+        let config = decoder.config();
+        let value = if matches!(config.int_encoding(), IntEncoding::Variable) {
+            let raw = leb128_decode(&bytes).ok_or(DecodeError::Other("invalid LEB128 envelope payload"))?;
+            T::from_leb128_value(raw)
+        } else {
+            let wide: [u8; 8] = bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| DecodeError::OtherString(format!("envelope payload of {} bytes, expected 8", bytes.len())))?;
+            T::from_wide(if matches!(config.endianness(), Endianness::Big) {
+                u64::from_be_bytes(wide)
+            } else {
+                u64::from_le_bytes(wide)
+            })
+        };
+        value
+            .map(IntEnvelope)
+            .ok_or_else(|| DecodeError::OtherString(format!("envelope value out of range for {}", T::TYPE_NAME)))
+    }
+}
+
+/// One value exchanged with the Nim helpers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvelopeVector {
+    /// `i32`, `u32` or `i64`
+    pub type_name: &'static str,
+    /// The value, widened to i128 so one field holds every type
+    pub value: i128,
+    pub config: KnownConfig,
+    /// Position of the value in its type's list
+    pub index: usize,
+}
+
+/// i32 values: zero, LEB128 and zigzag boundaries, extremes
+pub const I32_VALUES: &[i32] = &[0, 1, -1, 63, -64, 64, -65, 300, -300, i32::MIN, i32::MAX];
+
+/// u32 values: zero, LEB128 boundaries, bincode varint markers, extremes
+pub const U32_VALUES: &[u32] = &[0, 1, 127, 128, 250, 251, 16383, 16384, 65536, u32::MAX];
+
+/// i64 values: zero, zigzag boundaries, past 32 bits, extremes
+pub const I64_VALUES: &[i64] = &[0, 1, -1, 1 << 35, -(1 << 35), i64::MIN, i64::MAX];
+
+impl EnvelopeVector {
+    /// The envelope encoded with this vector's config
+    pub fn encode(&self) -> Result<Vec<u8>, EncodeError> {
+        match self.type_name {
+            "i32" => self.config.encode(&IntEnvelope(self.value as i32)),
+            "u32" => self.config.encode(&IntEnvelope(self.value as u32)),
+            _ => self.config.encode(&IntEnvelope(self.value as i64)),
+        }
+    }
+
+    /// Decode an envelope of this vector's type, returning the value widened to i128
+    pub fn decode(&self, bytes: &[u8]) -> Result<(i128, usize), DecodeError> {
+        match self.type_name {
+            "i32" => self.config.decode::<IntEnvelope<i32>>(bytes).map(|(v, n)| (i128::from(v.0), n)),
+            "u32" => self.config.decode::<IntEnvelope<u32>>(bytes).map(|(v, n)| (i128::from(v.0), n)),
+            _ => self.config.decode::<IntEnvelope<i64>>(bytes).map(|(v, n)| (i128::from(v.0), n)),
+        }
+    }
+
+    /// `<side>_envelope_<type>_<config>_<nn>.bin`
+    fn file(&self, side: &str) -> String {
+        format!("{}_envelope_{}_{}_{:02}.bin", side, self.type_name, self.config, self.index)
+    }
+
+    /// File the Rust tests write
+    pub fn rust_file(&self) -> String {
+        self.file("rust")
+    }
+
+    /// File the Nim tests write
+    pub fn nim_file(&self) -> String {
+        self.file("nim")
+    }
+}
+
+/// Every type × config × value
+pub fn envelope_vectors() -> Vec<EnvelopeVector> {
+    let values: [(&'static str, Vec<i128>); 3] = [
+        (i32::TYPE_NAME, I32_VALUES.iter().map(|&v| i128::from(v)).collect()),
+        (u32::TYPE_NAME, U32_VALUES.iter().map(|&v| i128::from(v)).collect()),
+        (i64::TYPE_NAME, I64_VALUES.iter().map(|&v| i128::from(v)).collect()),
+    ];
+    let mut vectors = Vec::new();
+    for (type_name, values) in values {
+        for config in KnownConfig::ALL {
+            for (index, &value) in values.iter().enumerate() {
+                vectors.push(EnvelopeVector { type_name, value, config, index });
+            }
+        }
+    }
+    vectors
+}
+
+/// The vectors as TSV: type, config, value, Rust file, Nim file
+pub fn render_tsv(vectors: &[EnvelopeVector]) -> String {
+    let mut out = String::from("# type\tconfig\tvalue\trust_file\tnim_file\n");
+    for vector in vectors {
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            vector.type_name,
+            vector.config,
+            vector.value,
+            vector.rust_file(),
+            vector.nim_file()
+        ));
+    }
+    out
+}
@@ -0,0 +1,138 @@
+//! Resource-exhaustion vectors: tiny inputs that claim huge collections.
+//!
+//! Each vector is a length prefix claiming [`CLAIMED_LENGTHS`] elements of a
+//! collection type, followed by [`TAIL_LEN`] bytes of data. Decoded with a
+//! [`DECODE_LIMIT`]-byte limit, bincode charges the claimed length against
+//! the limit before allocating anything and fails with `LimitExceeded`, so
+//! the decode is fast and its allocation stays small. [`decode_value`] does
+//! not rely on a limit: it reads strings and collections one item at a time,
+//! so the same inputs run out of data instead.
+//!
+//! Nim's `deserialize` allocates `newSeq[byte](length)` straight from the
+//! prefix (guarded only by the size limit). These vectors are the reference
+//! for a Nim collection decoder that must not preallocate from an untrusted
+//! length. `tests/exhaustion.rs` installs a counting global allocator to fill
+//! in the peak bytes allocated while decoding each one.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use bincode::Decode;
+
+use crate::config::KnownConfig;
+use crate::golden::DECODE_LIMIT;
+use crate::mutation::{error_kind, Outcome};
+use crate::schema::{BincodeSchema, Schema};
+use crate::value::{decode_value, encode_value};
+use crate::vectors::fnv1a64;
+
+/// File name used in the shared test-data directory
+pub const EXHAUSTION_FILE: &str = "exhaustion.tsv";
+
+/// Element counts claimed by the prefixes: 2^32, 2^40 and the largest u64
+pub const CLAIMED_LENGTHS: [u64; 3] = [1 << 32, 1 << 40, u64::MAX];
+
+/// Bytes of element data after the prefix
+pub const TAIL_LEN: usize = 8;
+
+/// Configs in the vectors
+pub const EXHAUSTION_CONFIGS: [KnownConfig; 2] = [KnownConfig::Variable, KnownConfig::Fixed8];
+
+/// Collection types in the vectors
+pub const EXHAUSTION_TYPES: [&str; 5] = ["Vec<u8>", "Vec<u64>", "String", "Vec<String>", "BTreeMap<u32, u32>"];
+
+/// One tiny input claiming `claimed` elements of `type_name`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExhaustionVector {
+    pub type_name: &'static str,
+    pub config: KnownConfig,
+    pub claimed: u64,
+    /// Prefix plus [`TAIL_LEN`] bytes
+    pub bytes: Vec<u8>,
+}
+
+/// Decode `bytes` as `T` under the decode limit and label the result
+fn limited_outcome<T: Decode<()> + bincode::Encode>(bytes: &[u8], config: KnownConfig) -> Outcome {
+    match config.decode_with_limit::<T, DECODE_LIMIT>(bytes) {
+        Ok((value, bytes_read)) if bytes_read == bytes.len() => {
+            Outcome::Accepted { hash: fnv1a64(&config.encode(&value).expect("a decoded value re-encodes")) }
+        }
+        Ok(_) => Outcome::Rejected { kind: "TrailingBytes".to_string() },
+        Err(e) => Outcome::Rejected { kind: error_kind(&e) },
+    }
+}
+
+impl ExhaustionVector {
+    /// Decode the input as its type with a [`DECODE_LIMIT`]-byte limit
+    pub fn decode(&self) -> Outcome {
+        match self.type_name {
+            "Vec<u8>" => limited_outcome::<Vec<u8>>(&self.bytes, self.config),
+            "Vec<u64>" => limited_outcome::<Vec<u64>>(&self.bytes, self.config),
+            "String" => limited_outcome::<String>(&self.bytes, self.config),
+            "Vec<String>" => limited_outcome::<Vec<String>>(&self.bytes, self.config),
+            "BTreeMap<u32, u32>" => limited_outcome::<BTreeMap<u32, u32>>(&self.bytes, self.config),
+            other => panic!("unknown exhaustion type: {}", other),
+        }
+    }
+
+    /// Decode the input through [`decode_value`] with its type's schema
+    pub fn decode_value(&self) -> Outcome {
+        let schema = self.schema();
+        match decode_value(&self.bytes, &schema, self.config) {
+            Ok((value, bytes_read)) if bytes_read == self.bytes.len() => Outcome::Accepted {
+                hash: fnv1a64(&encode_value(&value, &schema, self.config).expect("a decoded value re-encodes")),
+            },
+            Ok(_) => Outcome::Rejected { kind: "TrailingBytes".to_string() },
+            Err(e) => Outcome::Rejected { kind: error_kind(&e) },
+        }
+    }
+
+    fn schema(&self) -> Schema {
+        match self.type_name {
+            "Vec<u8>" => Vec::<u8>::schema(),
+            "Vec<u64>" => Vec::<u64>::schema(),
+            "String" => String::schema(),
+            "Vec<String>" => Vec::<String>::schema(),
+            "BTreeMap<u32, u32>" => BTreeMap::<u32, u32>::schema(),
+            other => panic!("unknown exhaustion type: {}", other),
+        }
+    }
+}
+
+/// A vector with what decoding it cost
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measured {
+    pub vector: ExhaustionVector,
+    pub outcome: Outcome,
+    /// Peak bytes allocated during the decode
+    pub peak_bytes: usize,
+    pub elapsed: Duration,
+}
+
+/// Every type × config × claimed length
+pub fn exhaustion_vectors() -> Vec<ExhaustionVector> {
+    let mut vectors = Vec::new();
+    for type_name in EXHAUSTION_TYPES {
+        for config in EXHAUSTION_CONFIGS {
+            for claimed in CLAIMED_LENGTHS {
+                let mut bytes = config.encode(&claimed).expect("a u64 encodes");
+                bytes.extend_from_slice(&[0x01; TAIL_LEN]);
+                vectors.push(ExhaustionVector { type_name, config, claimed, bytes });
+            }
+        }
+    }
+    vectors
+}
+
+/// The measured vectors as TSV: type, config, claimed, input hex, outcome, peak bytes
+pub fn render_tsv(measured: &[Measured]) -> String {
+    let mut out = String::from("# type\tconfig\tclaimed\tinput\toutcome\tpeak_bytes\n");
+    for row in measured {
+        let input: String = row.vector.bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\n",
+            row.vector.type_name, row.vector.config, row.vector.claimed, input, row.outcome, row.peak_bytes
+        ));
+    }
+    out
+}
@@ -0,0 +1,307 @@
+//! Committed golden corpus guarding against wire-format drift.
+//!
+//! Cargo.toml asks for `bincode = "2.0"`, so `cargo update` can move to a newer
+//! 2.x release. The cross-verification vectors are regenerated on every run
+//! and would follow such a change silently. The golden corpus is checked in
+//! under `tests/golden/` instead: one `.bin` file per case and config, plus a
+//! `manifest.json` recording the bincode version that produced them.
+//!
+//! [`check_corpus`] re-encodes every case with the current crate and reports
+//! each file whose bytes differ. Set `UPDATE_GOLDEN=1` to record a new corpus
+//! with [`write_corpus`] after reviewing an intentional change.
+
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::num::{NonZeroU32, NonZeroU64, Wrapping};
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::blockchain::{sample_address, sample_hash, sample_proof, sample_transaction, SAMPLE_U256};
+use crate::config::KnownConfig;
+use crate::nim_native::{Color, NimChar, NimCharSet, NimOrderedTable, NimRange, NimSet64, UserId};
+use crate::reference::Person;
+use crate::snapshot::diff_hex;
+
+/// Environment variable that makes the golden test rewrite the corpus
+pub const UPDATE_ENV: &str = "UPDATE_GOLDEN";
+
+/// Name of the manifest file inside the corpus directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Size limit for [`GoldenCase::decode_canonical`]: above every case, but low
+/// enough that a corrupted length prefix cannot allocate gigabytes
+pub const DECODE_LIMIT: usize = 1 << 20;
+
+/// `tests/golden/manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// bincode crate version (from Cargo.lock) that produced the corpus
+    pub bincode_version: String,
+    pub cases: Vec<ManifestEntry>,
+}
+
+/// One committed file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path relative to the corpus directory, e.g. `variable/vec_u8_010.bin`
+    pub file: String,
+    pub config: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub description: String,
+    pub len: usize,
+}
+
+type EncodeFn = Box<dyn Fn(KnownConfig) -> Result<Vec<u8>, EncodeError>>;
+type DecodeFn = Box<dyn Fn(&[u8], KnownConfig) -> Result<(Vec<u8>, usize), DecodeError>>;
+
+/// A value encoded with every [`KnownConfig`]
+pub struct GoldenCase {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub description: String,
+    encode: EncodeFn,
+    decode: DecodeFn,
+}
+
+impl GoldenCase {
+    fn new<T: Encode + Decode<()> + 'static>(
+        name: &'static str,
+        type_name: &'static str,
+        description: impl Into<String>,
+        value: T,
+    ) -> GoldenCase {
+        GoldenCase {
+            name,
+            type_name,
+            description: description.into(),
+            encode: Box::new(move |config| config.encode(&value)),
+            decode: Box::new(|bytes, config| {
+                let (value, bytes_read) = config.decode_with_limit::<T, DECODE_LIMIT>(bytes)?;
+                let canonical = config
+                    .encode(&value)
+                    .map_err(|e| DecodeError::OtherString(format!("cannot re-encode decoded value: {}", e)))?;
+                Ok((canonical, bytes_read))
+            }),
+        }
+    }
+
+    /// Encode the case with `config` using the current bincode crate
+    pub fn encode(&self, config: KnownConfig) -> Result<Vec<u8>, EncodeError> {
+        (self.encode)(config)
+    }
+
+    /// Decode `bytes` as this case's type with `config` (limited to
+    /// [`DECODE_LIMIT`]), returning the value re-encoded and the number of bytes read
+    pub fn decode_canonical(&self, bytes: &[u8], config: KnownConfig) -> Result<(Vec<u8>, usize), DecodeError> {
+        (self.decode)(bytes, config)
+    }
+
+    /// Path of the committed file, relative to the corpus directory
+    pub fn file(&self, config: KnownConfig) -> String {
+        format!("{}/{}.bin", config.name(), self.name)
+    }
+}
+
+/// Every case in the corpus.
+///
+/// The `vec_u8_*` cases cover the cross-verification lengths (zero-filled, as
+/// first recorded); the rest cover integer widths, the reference struct and
+/// the standard-library types bincode has impls for (`std_*`) and the
+/// blockchain primitives of [`crate::blockchain`] (`chain_*`) and the
+/// stand-ins for Nim-native types of [`crate::nim_native`] (`nim_*`).
+pub fn golden_cases() -> Vec<GoldenCase> {
+    vec![
+        GoldenCase::new("vec_u8_001", "Vec<u8>", "[1, 2, 3, 4, 5]", vec![1u8, 2, 3, 4, 5]),
+        GoldenCase::new("vec_u8_002", "Vec<u8>", "empty", Vec::<u8>::new()),
+        GoldenCase::new("vec_u8_003", "Vec<u8>", "[0, 255, 128, 64]", vec![0u8, 255, 128, 64]),
+        GoldenCase::new("vec_u8_004", "Vec<u8>", "\"Hello, World!\" bytes", b"Hello, World!".to_vec()),
+        GoldenCase::new("vec_u8_005", "Vec<u8>", "[42]", vec![42u8]),
+        GoldenCase::new(
+            "vec_u8_006",
+            "Vec<u8>",
+            "\"Test with émojis 🚀\" bytes",
+            "Test with émojis 🚀".as_bytes().to_vec(),
+        ),
+        GoldenCase::new("vec_u8_007", "Vec<u8>", "100 x 0x01", vec![1u8; 100]),
+        GoldenCase::new("vec_u8_008", "Vec<u8>", "20 KiB of zeros", vec![0u8; 20 * 1024]),
+        GoldenCase::new("vec_u8_009", "Vec<u8>", "250 zeros, last single-byte length", vec![0u8; 250]),
+        GoldenCase::new("vec_u8_010", "Vec<u8>", "251 zeros, first 0xfb length", vec![0u8; 251]),
+        GoldenCase::new("vec_u8_011", "Vec<u8>", "65535 zeros, last 0xfb length", vec![0u8; 65535]),
+        GoldenCase::new("vec_u8_012", "Vec<u8>", "65536 zeros, first 0xfc length", vec![0u8; 65536]),
+        GoldenCase::new("string_empty", "String", "\"\"", String::new()),
+        GoldenCase::new("string_utf8", "String", "\"héllo 🚀\"", "héllo 🚀".to_string()),
+        GoldenCase::new("u32_250", "u32", "250, last single-byte varint", 250u32),
+        GoldenCase::new("u32_251", "u32", "251, first 0xfb varint", 251u32),
+        GoldenCase::new("u32_65536", "u32", "65536, first 0xfc varint", 65536u32),
+        GoldenCase::new("u32_max", "u32", "u32::MAX", u32::MAX),
+        GoldenCase::new("u64_4294967296", "u64", "2^32, first 0xfd varint", 1u64 << 32),
+        GoldenCase::new("u64_max", "u64", "u64::MAX", u64::MAX),
+        GoldenCase::new("i32_minus_1", "i32", "-1 (zigzag 1)", -1i32),
+        GoldenCase::new("i32_min", "i32", "i32::MIN", i32::MIN),
+        GoldenCase::new("i32_max", "i32", "i32::MAX", i32::MAX),
+        GoldenCase::new(
+            "person",
+            "Person",
+            "Person { name: \"Alice\", age: 30, email: \"alice@example.com\" }",
+            Person { name: "Alice".to_string(), age: 30, email: "alice@example.com".to_string() },
+        ),
+        GoldenCase::new("std_duration", "Duration", "90.5 s: secs u64, nanos u32", Duration::new(90, 500_000_000)),
+        GoldenCase::new(
+            "std_system_time",
+            "SystemTime",
+            "UNIX_EPOCH + 1700000000.000000123 s, as a Duration",
+            SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123),
+        ),
+        GoldenCase::new("std_ipv4_addr", "Ipv4Addr", "192.168.1.1, 4 raw octets", Ipv4Addr::new(192, 168, 1, 1)),
+        GoldenCase::new(
+            "std_ipv6_addr",
+            "Ipv6Addr",
+            "2001:db8::1, 16 raw octets",
+            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+        ),
+        GoldenCase::new("std_ip_addr_v4", "IpAddr", "V4(10.0.0.1): u32 tag 0", IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
+        GoldenCase::new("std_ip_addr_v6", "IpAddr", "V6(::1): u32 tag 1", IpAddr::V6(Ipv6Addr::LOCALHOST)),
+        GoldenCase::new(
+            "std_socket_addr_v4",
+            "SocketAddr",
+            "127.0.0.1:8080: u32 tag 0, octets, u16 port",
+            SocketAddr::from(([127, 0, 0, 1], 8080)),
+        ),
+        GoldenCase::new(
+            "std_socket_addr_v6",
+            "SocketAddr",
+            "[::1]:443: u32 tag 1, octets, u16 port",
+            SocketAddr::from((Ipv6Addr::LOCALHOST, 443)),
+        ),
+        GoldenCase::new("std_non_zero_u32", "NonZeroU32", "42", NonZeroU32::new(42).unwrap()),
+        GoldenCase::new("std_non_zero_u64", "NonZeroU64", "u64::MAX", NonZeroU64::MAX),
+        GoldenCase::new("std_range", "Range<u32>", "3..300: start, end", 3u32..300),
+        GoldenCase::new("std_range_inclusive", "RangeInclusive<u32>", "1..=255: start, end", 1u32..=255),
+        GoldenCase::new("std_bound_unbounded", "Bound<u32>", "Unbounded: u32 tag 0", Bound::<u32>::Unbounded),
+        GoldenCase::new("std_bound_included", "Bound<u32>", "Included(5): u32 tag 1", Bound::Included(5u32)),
+        GoldenCase::new("std_bound_excluded", "Bound<u32>", "Excluded(5): u32 tag 2", Bound::Excluded(5u32)),
+        GoldenCase::new("std_wrapping", "Wrapping<u32>", "Wrapping(u32::MAX), as a bare u32", Wrapping(u32::MAX)),
+        GoldenCase::new("std_cow_str", "Cow<str>", "Borrowed(\"cow\"), as a String", Cow::<'static, str>::Borrowed("cow")),
+        GoldenCase::new("std_rc", "Rc<String>", "Rc(\"shared\"), as the inner String", Rc::new("shared".to_string())),
+        GoldenCase::new("std_arc", "Arc<u64>", "Arc(1 << 40), as the inner u64", Arc::new(1u64 << 40)),
+        GoldenCase::new("std_path_buf", "PathBuf", "\"/var/lib/data.bin\", as a String", PathBuf::from("/var/lib/data.bin")),
+        GoldenCase::new("chain_hash", "[u8; 32]", "bytes 00..1f, no length prefix", sample_hash(0)),
+        GoldenCase::new("chain_address", "[u8; 20]", "bytes a0..b3, no length prefix", sample_address()),
+        GoldenCase::new("chain_u256", "[u64; 4]", "limbs [u64::MAX, 0, 251, 2^63], one u64 each", SAMPLE_U256),
+        GoldenCase::new("chain_merkle_proof", "Vec<[u8; 32]>", "3 hashes after a length prefix", sample_proof(3)),
+        GoldenCase::new("chain_signed_transaction", "SignedTransaction", "sample transfer", sample_transaction()),
+        GoldenCase::new(
+            "nim_set64",
+            "NimSet64",
+            "set[0..63] {0, 1, 7, 63}, a u64 bitmask",
+            NimSet64::from_elements(&[0, 1, 7, 63]).unwrap(),
+        ),
+        GoldenCase::new(
+            "nim_char_set",
+            "NimCharSet",
+            "set[char] {'\\0', 'A', 'z', '\\xff'}, 32 bitmask bytes",
+            NimCharSet::from_elements(&[0, b'A', b'z', 0xff]),
+        ),
+        GoldenCase::new("nim_range", "NimRange<-10, 1000>", "range[-10..1000] low end, an i64", NimRange::<-10, 1000>(-10)),
+        GoldenCase::new("nim_distinct", "UserId", "distinct uint64 300, as the base u64", UserId(300)),
+        GoldenCase::new("nim_enum_hole", "Color", "blue = 10 of {1, 5, 10}, ordinal as u32", Color::Blue),
+        GoldenCase::new(
+            "nim_ordered_table",
+            "NimOrderedTable<String, u32>",
+            "{\"zeta\": 1, \"alpha\": 2} in insertion order",
+            NimOrderedTable(vec![("zeta".to_string(), 1u32), ("alpha".to_string(), 2)]),
+        ),
+        GoldenCase::new("nim_char", "NimChar", "'\\xe9', one raw byte", NimChar(0xe9)),
+    ]
+}
+
+/// Version of the `bincode` package recorded in a Cargo.lock file
+pub fn locked_bincode_version(cargo_lock: &str) -> Option<String> {
+    let mut lines = cargo_lock.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() == "name = \"bincode\"" {
+            let version = lines.next()?.trim().strip_prefix("version = \"")?.strip_suffix('"')?;
+            return Some(version.to_string());
+        }
+    }
+    None
+}
+
+/// Encode every case with every config and write the corpus plus its manifest into `dir`
+pub fn write_corpus(dir: &Path, bincode_version: &str) -> io::Result<Manifest> {
+    let mut manifest = Manifest { bincode_version: bincode_version.to_string(), cases: Vec::new() };
+    for case in golden_cases() {
+        for config in KnownConfig::ALL {
+            let bytes = case.encode(config).map_err(io::Error::other)?;
+            let file = case.file(config);
+            let path = dir.join(&file);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, &bytes)?;
+            manifest.cases.push(ManifestEntry {
+                file,
+                config: config.name().to_string(),
+                type_name: case.type_name.to_string(),
+                description: case.description.clone(),
+                len: bytes.len(),
+            });
+        }
+    }
+    let json = serde_json::to_string_pretty(&manifest).map_err(io::Error::other)?;
+    fs::write(dir.join(MANIFEST_FILE), json + "\n")?;
+    Ok(manifest)
+}
+
+/// Read the manifest in `dir`
+pub fn read_manifest(dir: &Path) -> io::Result<Manifest> {
+    let json = fs::read_to_string(dir.join(MANIFEST_FILE))?;
+    serde_json::from_str(&json).map_err(io::Error::other)
+}
+
+/// Re-encode every case and compare with the corpus in `dir`.
+///
+/// Returns one message per problem: a file whose bytes changed, a case
+/// missing from the corpus, or a committed file no case produces any more.
+pub fn check_corpus(dir: &Path) -> io::Result<Vec<String>> {
+    let manifest = read_manifest(dir)?;
+    let mut problems = Vec::new();
+    let mut expected_files = Vec::new();
+    for case in golden_cases() {
+        for config in KnownConfig::ALL {
+            let file = case.file(config);
+            let current = case.encode(config).map_err(io::Error::other)?;
+            match fs::read(dir.join(&file)) {
+                Ok(committed) if committed == current => {}
+                Ok(committed) => problems.push(format!(
+                    "{} ({} {}) changed:\n{}",
+                    file,
+                    case.type_name,
+                    case.description,
+                    diff_hex(&committed, &current)
+                )),
+                Err(e) => problems.push(format!("{}: {}", file, e)),
+            }
+            if !manifest.cases.iter().any(|entry| entry.file == file) {
+                problems.push(format!("{}: not listed in {}", file, MANIFEST_FILE));
+            }
+            expected_files.push(file);
+        }
+    }
+    for entry in &manifest.cases {
+        if !expected_files.contains(&entry.file) {
+            problems.push(format!("{}: listed in {} but no case produces it", entry.file, MANIFEST_FILE));
+        }
+    }
+    Ok(problems)
+}
@@ -0,0 +1,148 @@
+//! Table of encoded length prefixes, for checking Nim's `encodeLength` and
+//! `decodeLength` without allocating payloads.
+//!
+//! The table covers every length in `0..=70000` (past both varint marker
+//! thresholds) plus lengths sampled around each power of two up to
+//! `u64::MAX`, encoded with each of [`TABLE_CONFIGS`]. A `Vec<u8>` of length
+//! `n` starts with the encoding of `n as u64`, so only the prefix is stored.
+//!
+//! Binary layout, all integers little-endian:
+//!
+//! ```text
+//! "LPT1"                          magic
+//! u8 config count, then per config: u8 name length, name bytes
+//! u64 row count
+//! per row: u64 length, then per config: u8 prefix length, prefix bytes
+//! ```
+
+use bincode::error::EncodeError;
+
+use crate::config::KnownConfig;
+
+/// File name used in the shared test-data directory
+pub const TABLE_FILE: &str = "length_prefixes.bin";
+
+/// Magic bytes at the start of the table
+pub const MAGIC: &[u8; 4] = b"LPT1";
+
+/// Every length up to this one is in the table
+pub const EXHAUSTIVE_MAX: u64 = 70_000;
+
+/// Configs in table column order
+pub const TABLE_CONFIGS: [KnownConfig; 3] =
+    [KnownConfig::Variable, KnownConfig::Fixed8, KnownConfig::Fixed8BigEndian];
+
+/// One table row: a length and its prefix under each of [`TABLE_CONFIGS`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub length: u64,
+    pub prefixes: Vec<Vec<u8>>,
+}
+
+/// Lengths above [`EXHAUSTIVE_MAX`]: `2^k - 1`, `2^k`, `2^k + 1` and `3 * 2^(k-1)`
+/// for every `k` that reaches past it, plus `u64::MAX`
+pub fn sampled_lengths() -> Vec<u64> {
+    let mut lengths = Vec::new();
+    for k in 16..64 {
+        let power = 1u64 << k;
+        lengths.extend([power - 1, power, power + 1, power + (power >> 1)]);
+    }
+    lengths.push(u64::MAX);
+    lengths.retain(|&length| length > EXHAUSTIVE_MAX);
+    lengths.sort_unstable();
+    lengths.dedup();
+    lengths
+}
+
+/// Every length in the table, in row order
+pub fn table_lengths() -> impl Iterator<Item = u64> {
+    (0..=EXHAUSTIVE_MAX).chain(sampled_lengths())
+}
+
+/// The length prefix `config` writes before a payload of `length` bytes
+pub fn encode_prefix(length: u64, config: KnownConfig) -> Result<Vec<u8>, EncodeError> {
+    config.encode(&length)
+}
+
+/// Build the full table
+pub fn build_table() -> Result<Vec<Row>, EncodeError> {
+    table_lengths()
+        .map(|length| {
+            let prefixes =
+                TABLE_CONFIGS.iter().map(|&config| encode_prefix(length, config)).collect::<Result<_, _>>()?;
+            Ok(Row { length, prefixes })
+        })
+        .collect()
+}
+
+/// Serialize `rows` in the layout described in the module docs
+pub fn write_table(rows: &[Row]) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.push(TABLE_CONFIGS.len() as u8);
+    for config in TABLE_CONFIGS {
+        out.push(config.name().len() as u8);
+        out.extend_from_slice(config.name().as_bytes());
+    }
+    out.extend_from_slice(&(rows.len() as u64).to_le_bytes());
+    for row in rows {
+        out.extend_from_slice(&row.length.to_le_bytes());
+        for prefix in &row.prefixes {
+            out.push(prefix.len() as u8);
+            out.extend_from_slice(prefix);
+        }
+    }
+    out
+}
+
+/// Parse a table written by [`write_table`]
+pub fn parse_table(bytes: &[u8]) -> Result<Vec<Row>, String> {
+    let mut cursor = Cursor { bytes, pos: 0 };
+    if cursor.take(MAGIC.len())? != MAGIC {
+        return Err("not a length-prefix table (bad magic)".to_string());
+    }
+    let config_count = cursor.take(1)?[0] as usize;
+    let mut names = Vec::with_capacity(config_count);
+    for _ in 0..config_count {
+        let len = cursor.take(1)?[0] as usize;
+        names.push(String::from_utf8_lossy(cursor.take(len)?).into_owned());
+    }
+    let expected: Vec<&str> = TABLE_CONFIGS.iter().map(|config| config.name()).collect();
+    if names != expected {
+        return Err(format!("table configs {:?}, expected {:?}", names, expected));
+    }
+    let row_count = cursor.u64()?;
+    let mut rows = Vec::new();
+    for _ in 0..row_count {
+        let length = cursor.u64()?;
+        let mut prefixes = Vec::with_capacity(config_count);
+        for _ in 0..config_count {
+            let len = cursor.take(1)?[0] as usize;
+            prefixes.push(cursor.take(len)?.to_vec());
+        }
+        rows.push(Row { length, prefixes });
+    }
+    if cursor.pos != bytes.len() {
+        return Err(format!("{} trailing bytes after {} rows", bytes.len() - cursor.pos, row_count));
+    }
+    Ok(rows)
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or_else(|| format!("table truncated at offset {:#x}", self.pos))?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("took 8 bytes")))
+    }
+}
@@ -0,0 +1,33 @@
+//! Rust-side tooling for the nim-bincode cross-verification harness.
+//!
+//! The Nim library in `bincode/` is the product; this crate holds the Rust
+//! reference behaviour it is checked against, plus small tools for working
+//! with bincode blobs produced by either side.
+
+// Lets `#[derive(BincodeSchema)]` refer to `::bincode_wrapper` from inside this crate
+extern crate self as bincode_wrapper;
+
+pub mod blockchain;
+pub mod cases;
+pub mod codegen;
+pub mod config;
+pub mod depth;
+pub mod detect;
+pub mod encode_limit;
+pub mod envelope;
+pub mod exhaustion;
+pub mod golden;
+pub mod length_prefix;
+pub mod matrix;
+pub mod mutation;
+pub mod nim_native;
+pub mod opaque;
+pub mod reference;
+pub mod runtime_config;
+pub mod schema;
+pub mod snapshot;
+pub mod test_data;
+pub mod transcode;
+pub mod utf8;
+pub mod value;
+pub mod vectors;
@@ -0,0 +1,193 @@
+//! Run one test body under every config combination.
+//!
+//! bincode v2 configs are type-level, so a test cannot loop over them at
+//! runtime. [`config_matrix!`](crate::config_matrix) expands its body once
+//! per combination of endianness × int encoding × limit (see
+//! [`COMBINATIONS`]), each time with a concretely typed config, and collects
+//! the outcomes in a [`MatrixReport`]:
+//!
+//! ```
+//! use bincode_wrapper::config_matrix;
+//!
+//! let report = config_matrix!(|config, combo| {
+//!     let encoded = bincode::encode_to_vec(42u32, config).map_err(|e| e.to_string())?;
+//!     let (decoded, _): (u32, _) = bincode::decode_from_slice(&encoded, config).map_err(|e| e.to_string())?;
+//!     assert_eq!(decoded, 42, "{}", combo);
+//!     Ok(())
+//! });
+//! report.assert_passed();
+//! ```
+//!
+//! The body must evaluate to `Result<(), String>`; `?` and panics fail only the
+//! current combination. A new combination added here is picked up by every
+//! test that uses the macro.
+
+use std::fmt;
+
+use crate::config::CROSS_SIZE_LIMIT;
+
+#[doc(hidden)]
+pub use bincode as __bincode;
+
+/// Byte order of multi-byte integers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// How integers (and length prefixes) are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntEncoding {
+    /// Single byte below 251, otherwise a 0xfb/0xfc/0xfd marker and payload
+    Variable,
+    /// Native width; lengths are 8 bytes
+    Fixed,
+}
+
+/// Size limit of the Nim `standard()` config
+pub const NIM_DEFAULT_LIMIT: usize = 65536;
+
+/// Limits in the matrix: none, Nim's default and the cross-verification limit
+pub const MATRIX_LIMITS: [Option<usize>; 3] = [None, Some(NIM_DEFAULT_LIMIT), Some(CROSS_SIZE_LIMIT)];
+
+/// One cell of the config matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Combination {
+    pub endian: Endian,
+    pub int_encoding: IntEncoding,
+    /// `None` for no limit
+    pub limit: Option<usize>,
+}
+
+impl Combination {
+    /// Whether a value of `len` encoded bytes stays within the limit
+    pub fn allows(&self, len: usize) -> bool {
+        self.limit.is_none_or(|limit| len <= limit)
+    }
+}
+
+impl fmt::Display for Combination {
+    /// e.g. `le/varint/limit=65536` or `be/fixed/unlimited`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let endian = match self.endian {
+            Endian::Little => "le",
+            Endian::Big => "be",
+        };
+        let ints = match self.int_encoding {
+            IntEncoding::Variable => "varint",
+            IntEncoding::Fixed => "fixed",
+        };
+        match self.limit {
+            Some(limit) => write!(f, "{}/{}/limit={}", endian, ints, limit),
+            None => write!(f, "{}/{}/unlimited", endian, ints),
+        }
+    }
+}
+
+/// Every combination, in the order [`config_matrix!`](crate::config_matrix) runs them
+pub const COMBINATIONS: [Combination; 12] = {
+    let mut out = [Combination { endian: Endian::Little, int_encoding: IntEncoding::Variable, limit: None }; 12];
+    let endians = [Endian::Little, Endian::Big];
+    let encodings = [IntEncoding::Variable, IntEncoding::Fixed];
+    let mut i = 0;
+    while i < 12 {
+        out[i] = Combination { endian: endians[i / 6], int_encoding: encodings[i / 3 % 2], limit: MATRIX_LIMITS[i % 3] };
+        i += 1;
+    }
+    out
+};
+
+/// Outcome of a test body under each combination
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatrixReport {
+    pub results: Vec<(Combination, Result<(), String>)>,
+}
+
+impl MatrixReport {
+    /// Record the outcome for `combo`
+    pub fn record(&mut self, combo: Combination, result: Result<(), String>) {
+        self.results.push((combo, result));
+    }
+
+    /// Combinations whose body failed, with the failure message
+    pub fn failures(&self) -> Vec<(Combination, &str)> {
+        self.results
+            .iter()
+            .filter_map(|(combo, result)| result.as_ref().err().map(|e| (*combo, e.as_str())))
+            .collect()
+    }
+
+    /// Panic listing every failed combination, if any
+    #[track_caller]
+    pub fn assert_passed(&self) {
+        let failures = self.failures();
+        if !failures.is_empty() {
+            panic!("{} of {} config combinations failed:\n{}", failures.len(), self.results.len(), self);
+        }
+    }
+}
+
+impl fmt::Display for MatrixReport {
+    /// One `ok`/`FAIL` line per combination
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (combo, result) in &self.results {
+            match result {
+                Ok(()) => writeln!(f, "  ok   {}", combo)?,
+                Err(e) => writeln!(f, "  FAIL {}: {}", combo, e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Message of a caught panic
+#[doc(hidden)]
+pub fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+/// Run `$body` with `$config` bound to each type-level config in
+/// [`COMBINATIONS`](crate::matrix::COMBINATIONS) and `$combo` to its
+/// [`Combination`](crate::matrix::Combination), returning a
+/// [`MatrixReport`](crate::matrix::MatrixReport).
+#[macro_export]
+macro_rules! config_matrix {
+    (|$config:ident, $combo:ident| $body:expr) => {{
+        let mut report = $crate::matrix::MatrixReport::default();
+        $crate::config_matrix!(@ints report, $config, $combo, $body, Little, with_little_endian);
+        $crate::config_matrix!(@ints report, $config, $combo, $body, Big, with_big_endian);
+        report
+    }};
+    (@ints $report:ident, $config:ident, $combo:ident, $body:expr, $endian:ident, $with_endian:ident) => {
+        $crate::config_matrix!(@limits $report, $config, $combo, $body, $endian, $with_endian, Variable, with_variable_int_encoding);
+        $crate::config_matrix!(@limits $report, $config, $combo, $body, $endian, $with_endian, Fixed, with_fixed_int_encoding);
+    };
+    (@limits $report:ident, $config:ident, $combo:ident, $body:expr, $endian:ident, $with_endian:ident, $ints:ident, $with_ints:ident) => {
+        $crate::config_matrix!(@run $report, $config, $combo, $body, $endian, $with_endian, $ints, $with_ints, None, with_no_limit());
+        $crate::config_matrix!(@run $report, $config, $combo, $body, $endian, $with_endian, $ints, $with_ints,
+            Some($crate::matrix::NIM_DEFAULT_LIMIT), with_limit::<{ $crate::matrix::NIM_DEFAULT_LIMIT }>());
+        $crate::config_matrix!(@run $report, $config, $combo, $body, $endian, $with_endian, $ints, $with_ints,
+            Some($crate::config::CROSS_SIZE_LIMIT), with_limit::<{ $crate::config::CROSS_SIZE_LIMIT }>());
+    };
+    (@run $report:ident, $config:ident, $combo:ident, $body:expr, $endian:ident, $with_endian:ident, $ints:ident, $with_ints:ident,
+        $limit:expr, $($with_limit:tt)+) => {{
+        let $combo = $crate::matrix::Combination {
+            endian: $crate::matrix::Endian::$endian,
+            int_encoding: $crate::matrix::IntEncoding::$ints,
+            limit: $limit,
+        };
+        #[allow(unused_variables)]
+        let $config = $crate::matrix::__bincode::config::standard().$with_endian().$with_ints().$($with_limit)+;
+        let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| -> ::std::result::Result<(), ::std::string::String> {
+            $body
+        }));
+        $report.record($combo, result.unwrap_or_else(|payload| ::std::result::Result::Err($crate::matrix::panic_message(payload))));
+    }};
+}
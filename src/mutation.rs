@@ -0,0 +1,221 @@
+//! Mutants of the golden vectors, labelled with Rust's decode outcome.
+//!
+//! For every file in the golden corpus, [`mutations`] lists:
+//!
+//! - every single-bit flip in the length prefix (the first value's encoding)
+//! - truncation at every offset (sampled for files over [`FULL_TRUNCATION_MAX`] bytes)
+//! - one inserted byte from [`INSERTED_BYTES`] at each marker boundary: before
+//!   the prefix, after its first byte, and after the whole prefix
+//!
+//! Each mutant is decoded as the case's type with a
+//! [`DECODE_LIMIT`](crate::golden::DECODE_LIMIT)-byte limit.
+//! Accepted mutants are labelled with the FNV-1a hash of the value re-encoded
+//! with the same config, rejected ones with the `DecodeError` variant (or
+//! `TrailingBytes`). `test_mutations.nim` replays the corpus against Nim's
+//! `deserialize` and `deserializeString` and checks that both sides accept
+//! and reject the same inputs.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bincode::error::DecodeError;
+
+use crate::config::KnownConfig;
+use crate::golden::{golden_cases, GoldenCase};
+use crate::vectors::fnv1a64;
+
+/// File name used in the shared test-data directory
+pub const CORPUS_FILE: &str = "mutations.tsv";
+
+/// Files up to this size are truncated at every offset
+pub const FULL_TRUNCATION_MAX: usize = 1024;
+
+/// Larger files are truncated within this many bytes of either end...
+const TRUNCATION_WINDOW: usize = 64;
+
+/// ...and at every multiple of this stride
+const TRUNCATION_STRIDE: usize = 1024;
+
+/// Bytes inserted at marker boundaries: zero, the u16 marker and 0xff
+pub const INSERTED_BYTES: [u8; 3] = [0x00, 0xfb, 0xff];
+
+/// One way of corrupting a valid encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// Flip bit `bit` (0 = least significant) of the byte at `offset`
+    FlipBit { offset: usize, bit: u8 },
+    /// Keep only the first `len` bytes
+    Truncate { len: usize },
+    /// Insert `byte` before the byte at `offset`
+    Insert { offset: usize, byte: u8 },
+}
+
+impl Mutation {
+    /// The mutated copy of `bytes`
+    pub fn apply(&self, bytes: &[u8]) -> Vec<u8> {
+        match *self {
+            Mutation::FlipBit { offset, bit } => {
+                let mut out = bytes.to_vec();
+                out[offset] ^= 1 << bit;
+                out
+            }
+            Mutation::Truncate { len } => bytes[..len].to_vec(),
+            Mutation::Insert { offset, byte } => {
+                let mut out = Vec::with_capacity(bytes.len() + 1);
+                out.extend_from_slice(&bytes[..offset]);
+                out.push(byte);
+                out.extend_from_slice(&bytes[offset..]);
+                out
+            }
+        }
+    }
+
+    /// Parse a label written by `Display`, e.g. `flip:0:7`
+    pub fn from_label(label: &str) -> Option<Mutation> {
+        let parts: Vec<&str> = label.split(':').collect();
+        match parts.as_slice() {
+            ["flip", offset, bit] => {
+                let bit = bit.parse().ok().filter(|&bit| bit < 8)?;
+                Some(Mutation::FlipBit { offset: offset.parse().ok()?, bit })
+            }
+            ["truncate", len] => Some(Mutation::Truncate { len: len.parse().ok()? }),
+            ["insert", offset, byte] => {
+                Some(Mutation::Insert { offset: offset.parse().ok()?, byte: byte.parse().ok()? })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mutation::FlipBit { offset, bit } => write!(f, "flip:{}:{}", offset, bit),
+            Mutation::Truncate { len } => write!(f, "truncate:{}", len),
+            Mutation::Insert { offset, byte } => write!(f, "insert:{}:{}", offset, byte),
+        }
+    }
+}
+
+/// Size of the length prefix (or first integer) at the start of `bytes`
+pub fn prefix_len(bytes: &[u8], config: KnownConfig) -> usize {
+    let len = match config {
+        KnownConfig::Fixed8 | KnownConfig::Fixed8BigEndian => 8,
+        KnownConfig::Variable | KnownConfig::VariableBigEndian => match bytes.first() {
+            Some(0xfb) => 3,
+            Some(0xfc) => 5,
+            Some(0xfd) => 9,
+            Some(0xfe) => 17,
+            _ => 1,
+        },
+    };
+    len.min(bytes.len())
+}
+
+/// Truncation lengths for a file of `len` bytes, in increasing order
+fn truncation_lengths(len: usize) -> Vec<usize> {
+    if len <= FULL_TRUNCATION_MAX {
+        return (0..len).collect();
+    }
+    (0..len)
+        .filter(|&n| {
+            n <= TRUNCATION_WINDOW || n >= len - TRUNCATION_WINDOW || n % TRUNCATION_STRIDE == 0
+        })
+        .collect()
+}
+
+/// Every mutant of `bytes` described in the module docs
+pub fn mutations(bytes: &[u8], config: KnownConfig) -> Vec<Mutation> {
+    let prefix = prefix_len(bytes, config);
+    let mut out = Vec::new();
+    for offset in 0..prefix {
+        out.extend((0..8).map(|bit| Mutation::FlipBit { offset, bit }));
+    }
+    out.extend(truncation_lengths(bytes.len()).into_iter().map(|len| Mutation::Truncate { len }));
+    let mut boundaries = vec![0, 1.min(bytes.len()), prefix];
+    boundaries.dedup();
+    for offset in boundaries {
+        out.extend(INSERTED_BYTES.iter().map(|&byte| Mutation::Insert { offset, byte }));
+    }
+    out
+}
+
+/// What Rust's decoder made of an input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Decoded with every byte consumed; FNV-1a of the re-encoded value
+    Accepted { hash: u64 },
+    /// `DecodeError` variant name, or `TrailingBytes`
+    Rejected { kind: String },
+}
+
+impl Outcome {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Outcome::Accepted { .. })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Accepted { hash } => write!(f, "ok:{:#018x}", hash),
+            Outcome::Rejected { kind } => write!(f, "err:{}", kind),
+        }
+    }
+}
+
+/// Variant name of a decode error, e.g. `UnexpectedEnd`
+pub fn error_kind(e: &DecodeError) -> String {
+    format!("{:?}", e).chars().take_while(char::is_ascii_alphanumeric).collect()
+}
+
+/// Decode `bytes` as `case`'s type and label the result
+pub fn decode_outcome(case: &GoldenCase, bytes: &[u8], config: KnownConfig) -> Outcome {
+    match case.decode_canonical(bytes, config) {
+        Ok((canonical, bytes_read)) if bytes_read == bytes.len() => Outcome::Accepted { hash: fnv1a64(&canonical) },
+        Ok(_) => Outcome::Rejected { kind: "TrailingBytes".to_string() },
+        Err(e) => Outcome::Rejected { kind: error_kind(&e) },
+    }
+}
+
+/// One labelled mutant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mutant {
+    /// Golden file, relative to the corpus directory
+    pub source: String,
+    pub type_name: &'static str,
+    pub config: KnownConfig,
+    pub mutation: Mutation,
+    pub outcome: Outcome,
+}
+
+/// Mutate every file of the golden corpus in `golden_dir` and label each mutant
+pub fn mutation_corpus(golden_dir: &Path) -> io::Result<Vec<Mutant>> {
+    let mut corpus = Vec::new();
+    for case in golden_cases() {
+        for config in KnownConfig::ALL {
+            let source = case.file(config);
+            let bytes = fs::read(golden_dir.join(&source))
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", source, e)))?;
+            for mutation in mutations(&bytes, config) {
+                let outcome = decode_outcome(&case, &mutation.apply(&bytes), config);
+                corpus.push(Mutant { source: source.clone(), type_name: case.type_name, config, mutation, outcome });
+            }
+        }
+    }
+    Ok(corpus)
+}
+
+/// The corpus as TSV: source, type, config, mutation, outcome
+pub fn render_tsv(corpus: &[Mutant]) -> String {
+    let mut out = String::from("# source\ttype\tconfig\tmutation\toutcome\n");
+    for mutant in corpus {
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            mutant.source, mutant.type_name, mutant.config, mutant.mutation, mutant.outcome
+        ));
+    }
+    out
+}
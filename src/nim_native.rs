@@ -0,0 +1,183 @@
+//! Rust stand-ins for Nim-native types with no Rust counterpart.
+//!
+//! Each type here fixes the bincode layout a Nim codec must use:
+//!
+//! | Nim type                 | Stand-in               | Wire layout                                   |
+//! |--------------------------|------------------------|-----------------------------------------------|
+//! | `set[0..63]`             | [`NimSet64`]           | `u64` bitmask, bit `i` set for element `i`    |
+//! | `set[char]`, `set[byte]` | [`NimCharSet`]         | `[u8; 32]`, bit `i % 8` of byte `i / 8`       |
+//! | `range[a..b]` of `int`   | [`NimRange`]           | `i64`; decoding rejects values outside `a..b` |
+//! | `distinct T`             | e.g. [`UserId`]        | exactly `T`                                   |
+//! | enum with holes          | e.g. [`Color`]         | `u32` holding `ord(value)`, not the index     |
+//! | `OrderedTable[K, V]`     | [`NimOrderedTable`]    | `Vec<(K, V)>` in insertion order              |
+//! | `char`                   | [`NimChar`]            | `u8`, one raw byte                            |
+//!
+//! The `u64`, `i64` and `u32` layouts follow the config's int encoding, so
+//! they are varints under variable encoding. A Nim `char` is one byte: it is
+//! not a Rust `char`, which is written as 1 to 4 UTF-8 bytes. An
+//! `OrderedTable` has the same layout as a Rust map but keeps insertion order,
+//! and decoding rejects duplicate keys. The `nim_*` golden cases record one
+//! value of each stand-in.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use bincode::de::Decoder;
+use bincode::error::{AllowedEnumVariants, DecodeError};
+use bincode::{Decode, Encode};
+
+use crate::schema::{BincodeSchema, Schema, Variant};
+
+/// `set[0..63]`: bit `i` of the mask is set when `i` is in the set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode, BincodeSchema)]
+pub struct NimSet64(pub u64);
+
+impl NimSet64 {
+    /// The set of `elements`, or `None` if one is above 63
+    pub fn from_elements(elements: &[u8]) -> Option<NimSet64> {
+        elements.iter().try_fold(NimSet64(0), |set, &e| (e < 64).then(|| NimSet64(set.0 | (1 << e))))
+    }
+
+    pub fn contains(&self, element: u8) -> bool {
+        element < 64 && self.0 & (1 << element) != 0
+    }
+
+    /// Elements in increasing order
+    pub fn elements(&self) -> Vec<u8> {
+        (0..64).filter(|&e| self.contains(e)).collect()
+    }
+}
+
+/// `set[char]` or `set[byte]`: 256 bits, bit `i % 8` of byte `i / 8`
+/// (Nim's in-memory layout)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode, BincodeSchema)]
+pub struct NimCharSet(pub [u8; 32]);
+
+impl NimCharSet {
+    pub fn from_elements(elements: &[u8]) -> NimCharSet {
+        let mut bits = [0u8; 32];
+        for &e in elements {
+            bits[usize::from(e / 8)] |= 1 << (e % 8);
+        }
+        NimCharSet(bits)
+    }
+
+    pub fn contains(&self, element: u8) -> bool {
+        self.0[usize::from(element / 8)] & (1 << (element % 8)) != 0
+    }
+}
+
+/// `range[MIN..MAX]` over `int`, written as an `i64`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, BincodeSchema)]
+pub struct NimRange<const MIN: i64, const MAX: i64>(pub i64);
+
+impl<const MIN: i64, const MAX: i64> NimRange<MIN, MAX> {
+    /// The value, if it is within `MIN..=MAX`
+    pub fn new(value: i64) -> Option<Self> {
+        (MIN..=MAX).contains(&value).then_some(NimRange(value))
+    }
+}
+
+impl<const MIN: i64, const MAX: i64, Context> Decode<Context> for NimRange<MIN, MAX> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let value = i64::decode(decoder)?;
+        NimRange::new(value)
+            .ok_or_else(|| DecodeError::OtherString(format!("{} is outside range[{}..{}]", value, MIN, MAX)))
+    }
+}
+
+/// Example `distinct uint64`: written exactly like the base type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, BincodeSchema)]
+pub struct UserId(pub u64);
+
+/// Example enum with holes: `Color = enum red = 1, green = 5, blue = 10`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red = 1,
+    Green = 5,
+    Blue = 10,
+}
+
+impl Color {
+    /// Nim ordinals, which are also the wire values
+    pub const ORDINALS: &'static [u32] = &[1, 5, 10];
+
+    pub fn from_ordinal(ordinal: u32) -> Option<Color> {
+        match ordinal {
+            1 => Some(Color::Red),
+            5 => Some(Color::Green),
+            10 => Some(Color::Blue),
+            _ => None,
+        }
+    }
+}
+
+impl BincodeSchema for Color {
+    /// Each variant's index is its ordinal
+    fn schema() -> Schema {
+        let variant = |name: &str, index| Variant { name: name.to_string(), index, fields: Vec::new() };
+        Schema::Enum {
+            name: "Color".to_string(),
+            variants: vec![variant("Red", 1), variant("Green", 5), variant("Blue", 10)],
+        }
+    }
+}
+
+impl Encode for Color {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        (*self as u32).encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for Color {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let found = u32::decode(decoder)?;
+        Color::from_ordinal(found).ok_or(DecodeError::UnexpectedVariant {
+            type_name: "Color",
+            allowed: &AllowedEnumVariants::Allowed(Color::ORDINALS),
+            found,
+        })
+    }
+}
+
+/// `OrderedTable[K, V]`: entries in insertion order, keys unique
+#[derive(Debug, Clone, PartialEq, Eq, Default, Encode)]
+pub struct NimOrderedTable<K, V>(pub Vec<(K, V)>);
+
+impl<K: PartialEq, V> NimOrderedTable<K, V> {
+    /// Insert or replace, keeping the position of an existing key like Nim's `[]=`
+    pub fn insert(&mut self, key: K, value: V) {
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.0.push((key, value)),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+}
+
+impl<K: Decode<Context> + Hash + Eq, V: Decode<Context>, Context> Decode<Context> for NimOrderedTable<K, V> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let entries = Vec::<(K, V)>::decode(decoder)?;
+        let mut seen = HashSet::with_capacity(entries.len());
+        for (i, (key, _)) in entries.iter().enumerate() {
+            if !seen.insert(key) {
+                return Err(DecodeError::OtherString(format!("duplicate key at entry {}", i)));
+            }
+        }
+        Ok(NimOrderedTable(entries))
+    }
+}
+
+impl<K: BincodeSchema, V: BincodeSchema> BincodeSchema for NimOrderedTable<K, V> {
+    /// The map layout; the schema does not check that keys are unique
+    fn schema() -> Schema {
+        Schema::Map { key: Box::new(K::schema()), value: Box::new(V::schema()) }
+    }
+}
+
+/// Nim `char`: one raw byte, unlike a Rust `char`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode, BincodeSchema)]
+pub struct NimChar(pub u8);
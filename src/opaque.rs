@@ -0,0 +1,43 @@
+//! Values wrapped the way Nim's `serializeType`/`deserializeType` wrap them.
+//!
+//! `serializeType(value, toBytes)` in `nim_bincode.nim` converts a value to
+//! bytes with a caller-supplied function and writes those bytes as a bincode
+//! `Vec<u8>`. [`Opaque<T>`] does the same with T's own bincode encoding: the
+//! inner bytes are encoded with the outer config, then written with a length
+//! prefix. Decoding reads the byte vector and requires T to consume all of it.
+//!
+//! `serializeType` always uses `standard()` (fixed 8-byte lengths,
+//! little-endian, 64 KiB limit) for the outer vector. Exchange opaque values
+//! with [`KnownConfig::Fixed8`](crate::config::KnownConfig::Fixed8), and have
+//! the Nim `toBytes`/`fromBytes` functions use `standard()` too.
+
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+
+/// `T` encoded on its own, then wrapped in a length-prefixed byte vector
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Opaque<T>(pub T);
+
+impl<T: Encode> Encode for Opaque<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        let inner = bincode::encode_to_vec(&self.0, *encoder.config())?;
+        inner.encode(encoder)
+    }
+}
+
+impl<T: Decode<()>, Context> Decode<Context> for Opaque<T> {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let inner = Vec::<u8>::decode(decoder)?;
+        let (value, bytes_read) = bincode::decode_from_slice(&inner, *decoder.config())?;
+        if bytes_read != inner.len() {
+            return Err(DecodeError::OtherString(format!(
+                "{} trailing bytes inside a {}-byte opaque value",
+                inner.len() - bytes_read,
+                inner.len()
+            )));
+        }
+        Ok(Opaque(value))
+    }
+}
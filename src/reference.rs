@@ -0,0 +1,102 @@
+//! Reference types covered by the Rust ↔ Nim vectors.
+//!
+//! [`reference_schemas`] lists every type the harness produces vectors for,
+//! under the name tools use to look it up.
+
+use std::borrow::Cow;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::num::{NonZeroU32, NonZeroU64, Wrapping};
+use std::ops::{Bound, Range, RangeInclusive};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use bincode::{Decode, Encode};
+
+use crate::depth::{Chain, Tree};
+use crate::nim_native::{Color, NimChar, NimCharSet, NimOrderedTable, NimRange, NimSet64, UserId};
+use crate::schema::{BincodeSchema, Schema};
+
+/// Mirrors `Person` in `bincode/examples/struct_example.nim`
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, BincodeSchema)]
+pub struct Person {
+    pub name: String,
+    pub age: u32,
+    pub email: String,
+}
+
+/// 32-byte hash (Keccak-256 output, Merkle node); no length prefix
+pub type Hash32 = [u8; 32];
+
+/// 20-byte account address; no length prefix
+pub type Address = [u8; 20];
+
+/// 256-bit integer as four u64 limbs, least significant first. Each limb is
+/// an ordinary bincode u64, so it is a varint under variable encoding.
+pub type U256 = [u64; 4];
+
+/// Sample signed transaction. Mirrors `SignedTransaction` in
+/// `bincode/examples/signed_transaction_codec.nim`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, BincodeSchema)]
+pub struct SignedTransaction {
+    pub nonce: u64,
+    pub gas_price: U256,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub v: u64,
+    pub r: Hash32,
+    pub s: Hash32,
+}
+
+/// Schemas of the reference vector types, by name
+pub fn reference_schemas() -> Vec<(&'static str, Schema)> {
+    vec![
+        ("Vec<u8>", Vec::<u8>::schema()),
+        ("String", String::schema()),
+        ("u32", u32::schema()),
+        ("u64", u64::schema()),
+        ("i32", i32::schema()),
+        ("Person", Person::schema()),
+        ("[u8; 32]", Hash32::schema()),
+        ("[u8; 20]", Address::schema()),
+        ("[u64; 4]", U256::schema()),
+        ("Vec<[u8; 32]>", Vec::<Hash32>::schema()),
+        ("SignedTransaction", SignedTransaction::schema()),
+        ("NimSet64", NimSet64::schema()),
+        ("NimCharSet", NimCharSet::schema()),
+        ("NimRange<-10, 1000>", NimRange::<-10, 1000>::schema()),
+        ("UserId", UserId::schema()),
+        ("Color", Color::schema()),
+        ("NimOrderedTable<String, u32>", NimOrderedTable::<String, u32>::schema()),
+        ("NimChar", NimChar::schema()),
+        ("Tree", Tree::schema()),
+        ("Chain", Chain::schema()),
+        ("Duration", Duration::schema()),
+        ("SystemTime", SystemTime::schema()),
+        ("Ipv4Addr", Ipv4Addr::schema()),
+        ("Ipv6Addr", Ipv6Addr::schema()),
+        ("IpAddr", IpAddr::schema()),
+        ("SocketAddr", SocketAddr::schema()),
+        ("NonZeroU32", NonZeroU32::schema()),
+        ("NonZeroU64", NonZeroU64::schema()),
+        ("Range<u32>", Range::<u32>::schema()),
+        ("RangeInclusive<u32>", RangeInclusive::<u32>::schema()),
+        ("Bound<u32>", Bound::<u32>::schema()),
+        ("Wrapping<u32>", Wrapping::<u32>::schema()),
+        ("Cow<str>", Cow::<str>::schema()),
+        ("Rc<String>", Rc::<String>::schema()),
+        ("Arc<u64>", Arc::<u64>::schema()),
+        ("PathBuf", PathBuf::schema()),
+    ]
+}
+
+/// Look up a reference schema by name
+pub fn reference_schema(name: &str) -> Option<Schema> {
+    reference_schemas()
+        .into_iter()
+        .find(|(type_name, _)| *type_name == name)
+        .map(|(_, schema)| schema)
+}
@@ -0,0 +1,349 @@
+//! Runtime config mirroring Nim's `BincodeConfig`.
+//!
+//! [`KnownConfig`] only covers the four harness configs at a fixed limit. A
+//! [`RuntimeConfig`] has the same three fields as the Nim object (byte order,
+//! int size, size limit) and can be read from a file or an argument. Its
+//! [`encode`](RuntimeConfig::encode) and [`decode`](RuntimeConfig::decode)
+//! dispatch to the matching type-level bincode config without a limit. The
+//! runtime limit is then enforced on the bytes actually written or read by
+//! [`LimitedWriter`] and [`LimitedReader`]. Unlike bincode's own limit, which
+//! charges every length as 8 bytes, a varint length counts as the bytes on the
+//! wire.
+//!
+//! A limiting reader cannot stop bincode from allocating a container before
+//! reading it. Decoding therefore also runs under a type-level limit of
+//! [`CLAIM_FACTOR`] times the runtime limit, rounded up to the next power of
+//! two (at least 1 KiB), so a corrupted length prefix can allocate at most 128
+//! times the runtime limit. That limit is a memory budget, not a byte count:
+//! bincode charges a container `len * size_of::<T>()` up front and a varint up
+//! to 16 bytes. It covers elements of up to 64 bytes in memory per byte on the
+//! wire (an empty `Vec` is 24 to 1, a `None::<u128>` 32 to 1); input made of
+//! larger elements, such as structs holding several empty containers, fails
+//! with `LimitExceeded` before reaching the runtime limit.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bincode::de::read::{Reader, SliceReader};
+use bincode::enc::write::Writer;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{KnownConfig, CROSS_SIZE_LIMIT};
+use crate::matrix::{Combination, Endian, IntEncoding};
+
+/// Nim's `BINCODE_SIZE_LIMIT`, the limit of `standard()`
+pub const DEFAULT_SIZE_LIMIT: u64 = 65536;
+
+/// The same fields as Nim's `BincodeConfig`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuntimeConfig {
+    #[serde(with = "byte_order_name")]
+    pub byte_order: Endian,
+    /// 0 for variable-length integers, 8 for fixed encoding. Nim also accepts
+    /// 1, 2 and 4, which Rust bincode cannot express.
+    pub int_size: u8,
+    /// Maximum number of bytes written or read
+    pub size_limit: u64,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> RuntimeConfig {
+        RuntimeConfig::standard()
+    }
+}
+
+impl RuntimeConfig {
+    /// Nim's `standard()`: little-endian, fixed 8-byte integers, 64 KiB limit
+    pub const fn standard() -> RuntimeConfig {
+        RuntimeConfig { byte_order: Endian::Little, int_size: 8, size_limit: DEFAULT_SIZE_LIMIT }
+    }
+
+    pub const fn with_little_endian(self) -> RuntimeConfig {
+        RuntimeConfig { byte_order: Endian::Little, ..self }
+    }
+
+    pub const fn with_big_endian(self) -> RuntimeConfig {
+        RuntimeConfig { byte_order: Endian::Big, ..self }
+    }
+
+    pub const fn with_variable_int_encoding(self) -> RuntimeConfig {
+        RuntimeConfig { int_size: 0, ..self }
+    }
+
+    pub const fn with_fixed_int_encoding(self) -> RuntimeConfig {
+        RuntimeConfig { int_size: 8, ..self }
+    }
+
+    pub const fn with_limit(self, size_limit: u64) -> RuntimeConfig {
+        RuntimeConfig { size_limit, ..self }
+    }
+
+    /// Integer encoding, or an error for int sizes bincode has no config for
+    pub fn int_encoding(&self) -> Result<IntEncoding, String> {
+        match self.int_size {
+            0 => Ok(IntEncoding::Variable),
+            8 => Ok(IntEncoding::Fixed),
+            1 | 2 | 4 => Err(format!("int_size {} has no Rust bincode equivalent (only 0 and 8)", self.int_size)),
+            other => Err(format!("invalid int_size {}: must be 0, 1, 2, 4 or 8", other)),
+        }
+    }
+
+    /// The Nim `BincodeConfig` expression for this config
+    pub fn nim_config(&self) -> String {
+        let mut out = String::from("standard()");
+        if self.byte_order == Endian::Big {
+            out.push_str(".withBigEndian()");
+        }
+        match self.int_size {
+            0 => out.push_str(".withVariableIntEncoding()"),
+            8 => {}
+            size => out.push_str(&format!(".withFixedIntEncoding({})", size)),
+        }
+        if self.size_limit != DEFAULT_SIZE_LIMIT {
+            out.push_str(&format!(".withLimit({}'u64)", self.size_limit));
+        }
+        out
+    }
+
+    /// Encode `value`, failing once more than `size_limit` bytes are written
+    pub fn encode<T: Encode + ?Sized>(&self, value: &T) -> Result<Vec<u8>, EncodeError> {
+        let encoding = self.int_encoding().map_err(EncodeError::OtherString)?;
+        let mut writer = LimitedWriter::new(self.size_limit);
+        let standard = bincode::config::standard().with_no_limit();
+        match (self.byte_order, encoding) {
+            (Endian::Little, IntEncoding::Variable) => {
+                bincode::encode_into_writer(value, &mut writer, standard.with_little_endian().with_variable_int_encoding())
+            }
+            (Endian::Little, IntEncoding::Fixed) => {
+                bincode::encode_into_writer(value, &mut writer, standard.with_little_endian().with_fixed_int_encoding())
+            }
+            (Endian::Big, IntEncoding::Variable) => {
+                bincode::encode_into_writer(value, &mut writer, standard.with_big_endian().with_variable_int_encoding())
+            }
+            (Endian::Big, IntEncoding::Fixed) => {
+                bincode::encode_into_writer(value, &mut writer, standard.with_big_endian().with_fixed_int_encoding())
+            }
+        }?;
+        Ok(writer.into_inner())
+    }
+
+    /// Decode a `T` from the start of `bytes`, returning it with the number of
+    /// bytes read. Fails with `LimitExceeded` once more than `size_limit` bytes
+    /// would be read.
+    pub fn decode<T: Decode<()>>(&self, bytes: &[u8]) -> Result<(T, usize), DecodeError> {
+        let encoding = self.int_encoding().map_err(DecodeError::OtherString)?;
+        let mut reader = LimitedReader::new(SliceReader::new(bytes), self.size_limit);
+        let standard = bincode::config::standard();
+        let bucket = claim_bucket(self.size_limit);
+        let value = match (self.byte_order, encoding) {
+            (Endian::Little, IntEncoding::Variable) => {
+                let base = standard.with_little_endian().with_variable_int_encoding();
+                with_claim_limit!(bucket, base, |config| bincode::decode_from_reader(&mut reader, config))
+            }
+            (Endian::Little, IntEncoding::Fixed) => {
+                let base = standard.with_little_endian().with_fixed_int_encoding();
+                with_claim_limit!(bucket, base, |config| bincode::decode_from_reader(&mut reader, config))
+            }
+            (Endian::Big, IntEncoding::Variable) => {
+                let base = standard.with_big_endian().with_variable_int_encoding();
+                with_claim_limit!(bucket, base, |config| bincode::decode_from_reader(&mut reader, config))
+            }
+            (Endian::Big, IntEncoding::Fixed) => {
+                let base = standard.with_big_endian().with_fixed_int_encoding();
+                with_claim_limit!(bucket, base, |config| bincode::decode_from_reader(&mut reader, config))
+            }
+        }?;
+        Ok((value, reader.bytes_read() as usize))
+    }
+}
+
+/// Bytes of bincode's limit allowed per byte of runtime limit: enough for
+/// container elements up to 64 bytes in memory per byte on the wire
+pub const CLAIM_FACTOR: u64 = 64;
+
+/// Exponent of the type-level claim limit for `size_limit`: the next power of
+/// two above `CLAIM_FACTOR * size_limit`, at least 2^10; 64 means no type-level limit
+fn claim_bucket(size_limit: u64) -> u32 {
+    let claims = size_limit.checked_mul(CLAIM_FACTOR).and_then(u64::checked_next_power_of_two);
+    claims.map_or(64, |power| power.trailing_zeros()).max(10)
+}
+
+/// Run `$body` with `$config` bound to `$base` limited to `2^$bucket` bytes
+macro_rules! with_claim_limit {
+    ($bucket:expr, $base:expr, |$config:ident| $body:expr) => {
+        with_claim_limit!(@arms $bucket, $base, |$config| $body,
+            10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32 33 34 35 36
+            37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63)
+    };
+    (@arms $bucket:expr, $base:expr, |$config:ident| $body:expr, $($k:literal)*) => {
+        match $bucket {
+            $($k => {
+                let $config = $base.with_limit::<{ (1u64 << $k) as usize }>();
+                $body
+            })*
+            _ => {
+                let $config = $base.with_no_limit();
+                $body
+            }
+        }
+    };
+}
+use with_claim_limit;
+
+/// bincode `Writer` that collects into a `Vec` and fails past a byte limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitedWriter {
+    out: Vec<u8>,
+    limit: u64,
+}
+
+impl LimitedWriter {
+    pub fn new(limit: u64) -> LimitedWriter {
+        LimitedWriter { out: Vec::new(), limit }
+    }
+
+    /// The bytes written so far
+    pub fn into_inner(self) -> Vec<u8> {
+        self.out
+    }
+}
+
+impl Writer for LimitedWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        if self.out.len() as u64 + bytes.len() as u64 > self.limit {
+            return Err(EncodeError::Other("size limit exceeded"));
+        }
+        self.out.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// bincode `Reader` that fails with `LimitExceeded` past a byte limit
+#[derive(Debug)]
+pub struct LimitedReader<R> {
+    inner: R,
+    limit: u64,
+    bytes_read: u64,
+}
+
+impl<R: Reader> LimitedReader<R> {
+    pub fn new(inner: R, limit: u64) -> LimitedReader<R> {
+        LimitedReader { inner, limit, bytes_read: 0 }
+    }
+
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+}
+
+impl<R: Reader> Reader for LimitedReader<R> {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let end = self.bytes_read.saturating_add(bytes.len() as u64);
+        if end > self.limit {
+            return Err(DecodeError::LimitExceeded);
+        }
+        self.inner.read(bytes)?;
+        self.bytes_read = end;
+        Ok(())
+    }
+}
+
+impl From<KnownConfig> for RuntimeConfig {
+    /// The harness config, limited to [`CROSS_SIZE_LIMIT`]
+    fn from(config: KnownConfig) -> RuntimeConfig {
+        let base = RuntimeConfig::standard().with_limit(CROSS_SIZE_LIMIT as u64);
+        match config {
+            KnownConfig::Variable => base.with_variable_int_encoding(),
+            KnownConfig::Fixed8 => base,
+            KnownConfig::VariableBigEndian => base.with_big_endian().with_variable_int_encoding(),
+            KnownConfig::Fixed8BigEndian => base.with_big_endian(),
+        }
+    }
+}
+
+impl From<Combination> for RuntimeConfig {
+    /// A matrix combination; no limit becomes `u64::MAX`
+    fn from(combo: Combination) -> RuntimeConfig {
+        RuntimeConfig {
+            byte_order: combo.endian,
+            int_size: match combo.int_encoding {
+                IntEncoding::Variable => 0,
+                IntEncoding::Fixed => 8,
+            },
+            size_limit: combo.limit.map_or(u64::MAX, |limit| limit as u64),
+        }
+    }
+}
+
+impl fmt::Display for RuntimeConfig {
+    /// `byte_order=little,int_size=8,size_limit=65536`, as parsed by `FromStr`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "byte_order={},int_size={},size_limit={}",
+            byte_order_name::name(self.byte_order),
+            self.int_size,
+            self.size_limit
+        )
+    }
+}
+
+impl FromStr for RuntimeConfig {
+    type Err = String;
+
+    /// Parse comma-separated `key=value` overrides of [`RuntimeConfig::standard`],
+    /// e.g. `byte_order=big,int_size=0`, or a harness config name such as `fixed8_be`
+    fn from_str(spec: &str) -> Result<RuntimeConfig, String> {
+        if let Some(known) = KnownConfig::from_name(spec) {
+            return Ok(known.into());
+        }
+        let mut config = RuntimeConfig::standard();
+        for pair in spec.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').ok_or_else(|| format!("expected key=value, got '{}'", pair))?;
+            let value = value.trim();
+            match key.trim() {
+                "byte_order" => config.byte_order = byte_order_name::parse(value)?,
+                "int_size" => config.int_size = value.parse().map_err(|_| format!("invalid int_size '{}'", value))?,
+                "size_limit" => {
+                    config.size_limit = value.parse().map_err(|_| format!("invalid size_limit '{}'", value))?
+                }
+                other => return Err(format!("unknown config key '{}' (byte_order, int_size, size_limit)", other)),
+            }
+        }
+        config.int_encoding()?;
+        Ok(config)
+    }
+}
+
+/// `little`/`big` as used in files and on the command line
+mod byte_order_name {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::matrix::Endian;
+
+    pub fn name(endian: Endian) -> &'static str {
+        match endian {
+            Endian::Little => "little",
+            Endian::Big => "big",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Endian, String> {
+        match name {
+            "little" => Ok(Endian::Little),
+            "big" => Ok(Endian::Big),
+            other => Err(format!("invalid byte_order '{}': expected little or big", other)),
+        }
+    }
+
+    pub fn serialize<S: Serializer>(endian: &Endian, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(name(*endian))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Endian, D::Error> {
+        parse(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,418 @@
+//! Machine-readable description of a type's bincode wire shape.
+//!
+//! A [`Schema`] says what bincode writes for a type, not how Rust stores it:
+//! `usize` is a `u64`, `Vec<T>` and `String` carry a length prefix, `[T; N]`
+//! does not, enum variants are written as a `u32` index followed by fields.
+//! Schemas serialize to JSON so tools and the Nim port can consume them.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+use std::ops::{Bound, Range, RangeInclusive};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+pub use bincode_wrapper_derive::BincodeSchema;
+
+/// Wire shape of a bincode-encoded type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Schema {
+    Unit,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+    Char,
+    /// Length-prefixed UTF-8 bytes
+    String,
+    /// The integer `item`, which must not be zero
+    NonZero { item: Box<Schema> },
+    /// Length-prefixed sequence
+    Seq { item: Box<Schema> },
+    /// Fixed-size array, no length prefix
+    Array { len: usize, item: Box<Schema> },
+    /// Length-prefixed sequence of key/value pairs
+    Map { key: Box<Schema>, value: Box<Schema> },
+    /// `0` for `None`, `1` followed by the value for `Some`
+    Option { item: Box<Schema> },
+    /// Items back to back, no prefix
+    Tuple { items: Vec<Schema> },
+    /// Fields back to back in declaration order; tuple structs use `0`, `1`, ...
+    Struct { name: String, fields: Vec<Field> },
+    /// `u32` variant index followed by the variant's fields
+    Enum { name: String, variants: Vec<Variant> },
+    /// The enclosing struct or enum called `name`, for recursive types
+    Ref { name: String },
+}
+
+/// A named struct or variant field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    pub schema: Schema,
+}
+
+/// An enum variant and the index bincode writes for it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Variant {
+    pub name: String,
+    pub index: u32,
+    pub fields: Vec<Field>,
+}
+
+impl Schema {
+    /// Parse a schema from its JSON form
+    pub fn from_json(json: &str) -> Result<Schema, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Pretty-printed JSON form of this schema
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("schema serialization cannot fail")
+    }
+}
+
+/// Most [`Schema::Ref`]s a walk may follow at once. The schema walkers use
+/// far more stack per level than a derived `Decode` (about 20 KiB in debug
+/// builds), so this is lower than
+/// [`DEFAULT_MAX_DEPTH`](crate::depth::DEFAULT_MAX_DEPTH) and fits the 2 MiB
+/// stack of a spawned thread.
+pub const MAX_REF_DEPTH: usize = 64;
+
+/// Named schemas enclosing the one being walked, to resolve [`Schema::Ref`].
+///
+/// Each followed `Ref` is one nesting level; following more than
+/// [`MAX_REF_DEPTH`] at once fails instead of overflowing the stack.
+#[derive(Debug, Default)]
+pub struct Scope<'s> {
+    named: Vec<&'s Schema>,
+    depth: usize,
+}
+
+impl<'s> Scope<'s> {
+    /// Run `f` with `schema` in scope
+    pub fn within<T>(&mut self, schema: &'s Schema, f: impl FnOnce(&mut Scope<'s>) -> T) -> T {
+        self.named.push(schema);
+        let result = f(self);
+        self.named.pop();
+        result
+    }
+
+    /// Run `f` on the innermost struct or enum called `name`, one level deeper
+    pub fn follow<T>(&mut self, name: &str, f: impl FnOnce(&mut Scope<'s>, &'s Schema) -> T) -> Result<T, String> {
+        let target = self
+            .named
+            .iter()
+            .rev()
+            .copied()
+            .find(|schema| matches!(schema, Schema::Struct { name: n, .. } | Schema::Enum { name: n, .. } if n == name))
+            .ok_or_else(|| format!("no enclosing struct or enum named {}", name))?;
+        if self.depth >= MAX_REF_DEPTH {
+            return Err(format!("nesting depth exceeds {}", MAX_REF_DEPTH));
+        }
+        self.depth += 1;
+        let result = f(self, target);
+        self.depth -= 1;
+        Ok(result)
+    }
+}
+
+/// Types that can describe their bincode wire shape.
+///
+/// Derive it with `#[derive(BincodeSchema)]` next to `Encode`/`Decode`.
+/// Recursive types must implement it by hand, with a [`Schema::Ref`] where
+/// the type contains itself.
+pub trait BincodeSchema {
+    fn schema() -> Schema;
+}
+
+macro_rules! impl_primitive_schema {
+    ($($ty:ty => $schema:ident),* $(,)?) => {
+        $(
+            impl BincodeSchema for $ty {
+                fn schema() -> Schema {
+                    Schema::$schema
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_schema! {
+    () => Unit,
+    bool => Bool,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    usize => U64,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    isize => I64,
+    f32 => F32,
+    f64 => F64,
+    char => Char,
+    str => String,
+    String => String,
+    PathBuf => String,
+}
+
+impl<T: BincodeSchema> BincodeSchema for Vec<T> {
+    fn schema() -> Schema {
+        Schema::Seq { item: Box::new(T::schema()) }
+    }
+}
+
+impl<T: BincodeSchema> BincodeSchema for [T] {
+    fn schema() -> Schema {
+        Schema::Seq { item: Box::new(T::schema()) }
+    }
+}
+
+impl<T: BincodeSchema> BincodeSchema for VecDeque<T> {
+    fn schema() -> Schema {
+        Schema::Seq { item: Box::new(T::schema()) }
+    }
+}
+
+impl<T: BincodeSchema> BincodeSchema for BTreeSet<T> {
+    fn schema() -> Schema {
+        Schema::Seq { item: Box::new(T::schema()) }
+    }
+}
+
+impl<T: BincodeSchema, S> BincodeSchema for HashSet<T, S> {
+    fn schema() -> Schema {
+        Schema::Seq { item: Box::new(T::schema()) }
+    }
+}
+
+impl<T: BincodeSchema, const N: usize> BincodeSchema for [T; N] {
+    fn schema() -> Schema {
+        Schema::Array { len: N, item: Box::new(T::schema()) }
+    }
+}
+
+impl<K: BincodeSchema, V: BincodeSchema> BincodeSchema for BTreeMap<K, V> {
+    fn schema() -> Schema {
+        Schema::Map { key: Box::new(K::schema()), value: Box::new(V::schema()) }
+    }
+}
+
+impl<K: BincodeSchema, V: BincodeSchema, S> BincodeSchema for HashMap<K, V, S> {
+    fn schema() -> Schema {
+        Schema::Map { key: Box::new(K::schema()), value: Box::new(V::schema()) }
+    }
+}
+
+impl<T: BincodeSchema> BincodeSchema for Option<T> {
+    fn schema() -> Schema {
+        Schema::Option { item: Box::new(T::schema()) }
+    }
+}
+
+impl<T: BincodeSchema + ?Sized> BincodeSchema for Box<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: BincodeSchema + ?Sized> BincodeSchema for Rc<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: BincodeSchema + ?Sized> BincodeSchema for Arc<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: BincodeSchema + ToOwned + ?Sized> BincodeSchema for Cow<'_, T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: BincodeSchema> BincodeSchema for Wrapping<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+macro_rules! impl_non_zero_schema {
+    ($($ty:ty => $int:ty),* $(,)?) => {
+        $(
+            impl BincodeSchema for $ty {
+                fn schema() -> Schema {
+                    Schema::NonZero { item: Box::new(<$int>::schema()) }
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero_schema! {
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+}
+
+fn field(name: &str, schema: Schema) -> Field {
+    Field { name: name.to_string(), schema }
+}
+
+fn variant(name: &str, index: u32, fields: Vec<Field>) -> Variant {
+    Variant { name: name.to_string(), index, fields }
+}
+
+impl BincodeSchema for Duration {
+    fn schema() -> Schema {
+        Schema::Struct {
+            name: "Duration".to_string(),
+            fields: vec![field("secs", Schema::U64), field("nanos", Schema::U32)],
+        }
+    }
+}
+
+impl BincodeSchema for SystemTime {
+    /// The `Duration` since `UNIX_EPOCH`
+    fn schema() -> Schema {
+        Duration::schema()
+    }
+}
+
+impl BincodeSchema for Ipv4Addr {
+    fn schema() -> Schema {
+        <[u8; 4]>::schema()
+    }
+}
+
+impl BincodeSchema for Ipv6Addr {
+    fn schema() -> Schema {
+        <[u8; 16]>::schema()
+    }
+}
+
+impl BincodeSchema for IpAddr {
+    fn schema() -> Schema {
+        Schema::Enum {
+            name: "IpAddr".to_string(),
+            variants: vec![
+                variant("V4", 0, vec![field("0", Ipv4Addr::schema())]),
+                variant("V6", 1, vec![field("0", Ipv6Addr::schema())]),
+            ],
+        }
+    }
+}
+
+impl BincodeSchema for SocketAddrV4 {
+    fn schema() -> Schema {
+        Schema::Struct {
+            name: "SocketAddrV4".to_string(),
+            fields: vec![field("ip", Ipv4Addr::schema()), field("port", Schema::U16)],
+        }
+    }
+}
+
+impl BincodeSchema for SocketAddrV6 {
+    /// Only the address and port; flow info and scope id are not written
+    fn schema() -> Schema {
+        Schema::Struct {
+            name: "SocketAddrV6".to_string(),
+            fields: vec![field("ip", Ipv6Addr::schema()), field("port", Schema::U16)],
+        }
+    }
+}
+
+impl BincodeSchema for SocketAddr {
+    fn schema() -> Schema {
+        Schema::Enum {
+            name: "SocketAddr".to_string(),
+            variants: vec![
+                variant("V4", 0, vec![field("0", SocketAddrV4::schema())]),
+                variant("V6", 1, vec![field("0", SocketAddrV6::schema())]),
+            ],
+        }
+    }
+}
+
+impl<T: BincodeSchema> BincodeSchema for Range<T> {
+    fn schema() -> Schema {
+        Schema::Struct { name: "Range".to_string(), fields: vec![field("start", T::schema()), field("end", T::schema())] }
+    }
+}
+
+impl<T: BincodeSchema> BincodeSchema for RangeInclusive<T> {
+    fn schema() -> Schema {
+        Schema::Struct {
+            name: "RangeInclusive".to_string(),
+            fields: vec![field("start", T::schema()), field("end", T::schema())],
+        }
+    }
+}
+
+impl<T: BincodeSchema> BincodeSchema for Bound<T> {
+    /// bincode writes `Unbounded` as 0, ahead of the declaration order
+    fn schema() -> Schema {
+        Schema::Enum {
+            name: "Bound".to_string(),
+            variants: vec![
+                variant("Included", 1, vec![field("0", T::schema())]),
+                variant("Excluded", 2, vec![field("0", T::schema())]),
+                variant("Unbounded", 0, Vec::new()),
+            ],
+        }
+    }
+}
+
+macro_rules! impl_tuple_schema {
+    ($($name:ident),+) => {
+        impl<$($name: BincodeSchema),+> BincodeSchema for ($($name,)+) {
+            fn schema() -> Schema {
+                Schema::Tuple { items: vec![$($name::schema()),+] }
+            }
+        }
+    };
+}
+
+impl_tuple_schema!(A);
+impl_tuple_schema!(A, B);
+impl_tuple_schema!(A, B, C);
+impl_tuple_schema!(A, B, C, D);
+impl_tuple_schema!(A, B, C, D, E);
+impl_tuple_schema!(A, B, C, D, E, F);
+impl_tuple_schema!(A, B, C, D, E, F, G);
+impl_tuple_schema!(A, B, C, D, E, F, G, H);
@@ -0,0 +1,164 @@
+//! Annotated hex snapshots of expected encodings.
+//!
+//! A snapshot file holds `#` comment lines describing the case, followed by a
+//! hex dump in the usual `offset  bytes  |ascii|` layout:
+//!
+//! ```text
+//! # value: 0x1234567890ABCDEFu64
+//! # config: fixed int encoding, little-endian, limit 65536
+//! # 8 bytes
+//! 00000000  ef cd ab 90 78 56 34 12                          |....xV4.|
+//! ```
+//!
+//! Only the hex columns are compared. Run the tests with `UPDATE_SNAPSHOTS=1`
+//! to (re)write every snapshot from the current encodings.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Environment variable that makes [`assert_snapshot`] rewrite snapshot files
+pub const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Hex dump of `bytes`, one line of up to 16 bytes per offset
+pub fn render_hex(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (line, chunk) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+            .collect();
+        let _ = writeln!(out, "{:08x}  {:<47}  |{}|", line * BYTES_PER_LINE, hex.join(" "), ascii);
+    }
+    out
+}
+
+/// Full snapshot file contents: comment lines, byte count, hex dump
+pub fn render_snapshot(comments: &[&str], bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for comment in comments {
+        let _ = writeln!(out, "# {}", comment);
+    }
+    let plural = if bytes.len() == 1 { "" } else { "s" };
+    let _ = writeln!(out, "# {} byte{}", bytes.len(), plural);
+    out.push_str(&render_hex(bytes));
+    out
+}
+
+/// Bytes of a snapshot file, ignoring comments, offsets and the ASCII column
+pub fn parse_snapshot(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let hex = line.split('|').next().unwrap_or_default();
+        // First column is the offset
+        for token in hex.split_whitespace().skip(1) {
+            let byte = u8::from_str_radix(token, 16)
+                .map_err(|_| format!("line {}: invalid hex byte '{}'", number + 1, token))?;
+            bytes.push(byte);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Line-by-line diff of two hex dumps plus the first differing offset
+pub fn diff_hex(expected: &[u8], actual: &[u8]) -> String {
+    let mut out = String::new();
+    let first = expected.iter().zip(actual).position(|(e, a)| e != a);
+    match first {
+        Some(offset) => {
+            let _ = writeln!(
+                out,
+                "first difference at offset {:#x}: expected {:02x}, found {:02x}",
+                offset, expected[offset], actual[offset]
+            );
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "common prefix matches; expected {} bytes, found {}",
+                expected.len(),
+                actual.len()
+            );
+        }
+    }
+    let (expected, actual) = (render_hex(expected), render_hex(actual));
+    let (mut expected, mut actual) = (expected.lines(), actual.lines());
+    let mut same: Vec<&str> = Vec::new();
+    let mut changed = false;
+    loop {
+        match (expected.next(), actual.next()) {
+            (None, None) => break,
+            (Some(e), Some(a)) if e == a => same.push(e),
+            (e, a) => {
+                write_unchanged(&mut out, &same, changed, true);
+                same.clear();
+                changed = true;
+                if let Some(e) = e {
+                    let _ = writeln!(out, "- {}", e);
+                }
+                if let Some(a) = a {
+                    let _ = writeln!(out, "+ {}", a);
+                }
+            }
+        }
+    }
+    write_unchanged(&mut out, &same, changed, false);
+    out
+}
+
+/// Write a run of identical lines, keeping a few lines of context next to changes
+fn write_unchanged(out: &mut String, lines: &[&str], after_change: bool, before_change: bool) {
+    const CONTEXT: usize = 3;
+    let head = if after_change { CONTEXT } else { 0 };
+    let tail = if before_change { CONTEXT } else { 0 };
+    if lines.len() <= head + tail + 1 {
+        for line in lines {
+            let _ = writeln!(out, "  {}", line);
+        }
+        return;
+    }
+    for line in &lines[..head] {
+        let _ = writeln!(out, "  {}", line);
+    }
+    let _ = writeln!(out, "  ... {} identical lines", lines.len() - head - tail);
+    for line in &lines[lines.len() - tail..] {
+        let _ = writeln!(out, "  {}", line);
+    }
+}
+
+/// Compare `bytes` with the snapshot at `path`, panicking with a diff on mismatch.
+///
+/// With `UPDATE_SNAPSHOTS=1` the snapshot is written instead, using `comments`
+/// as its header.
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, comments: &[&str], bytes: &[u8]) {
+    let path = path.as_ref();
+    if std::env::var(UPDATE_ENV).is_ok_and(|value| value == "1") {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).unwrap_or_else(|e| panic!("cannot create {}: {}", dir.display(), e));
+        }
+        fs::write(path, render_snapshot(comments, bytes))
+            .unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+        return;
+    }
+
+    let text = fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!("cannot read snapshot {}: {} (run with {}=1 to create it)", path.display(), e, UPDATE_ENV)
+    });
+    let expected = parse_snapshot(&text).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    if expected != bytes {
+        panic!(
+            "snapshot {} does not match (run with {}=1 to update)\n{}",
+            path.display(),
+            UPDATE_ENV,
+            diff_hex(&expected, bytes)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Per-run directory for the files exchanged by the Rust and Nim tests.
+//!
+//! Both sides read `BINCODE_TEST_DATA_DIR`; the Makefile sets it once per
+//! `make` invocation so every step of a cross-verification run shares one
+//! directory and concurrent runs never touch each other's files. Without the
+//! variable, each test process writes to its own temp directory and removes
+//! it on exit.
+//!
+//! Every file written through [`write_file`] is recorded in `MANIFEST.tsv`
+//! (file, producer, process id, unix time), so a directory shows which run
+//! produced which vector. `test_cross_verification.nim` appends to the same
+//! manifest with producer `nim`.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable naming the shared test-data directory
+pub const DIR_ENV: &str = "BINCODE_TEST_DATA_DIR";
+
+/// Manifest file name inside the test-data directory
+pub const MANIFEST_FILE: &str = "MANIFEST.tsv";
+
+/// Producer recorded for files written by this crate
+const PRODUCER: &str = "rust";
+
+/// One manifest line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub file: String,
+    /// `rust` or `nim`
+    pub producer: String,
+    pub pid: u32,
+    pub unix_time: u64,
+}
+
+/// The directory named by `BINCODE_TEST_DATA_DIR`, if set.
+///
+/// Tests that read files produced by the other language need this: a private
+/// temp directory never contains them.
+pub fn shared_dir() -> Option<PathBuf> {
+    std::env::var_os(DIR_ENV).filter(|dir| !dir.is_empty()).map(PathBuf::from)
+}
+
+/// The shared directory, panicking if `BINCODE_TEST_DATA_DIR` is unset.
+///
+/// For `#[ignore]`d tests that read files `producer` wrote: the Makefile runs
+/// them with `--ignored` after the Nim step, and running them any other way
+/// fails instead of passing without checking anything.
+pub fn require_shared_dir(producer: &str) -> PathBuf {
+    shared_dir().unwrap_or_else(|| panic!("set {} to the directory {} wrote to", DIR_ENV, producer))
+}
+
+/// Private directory of this process, when `BINCODE_TEST_DATA_DIR` is unset
+static PRIVATE: OnceLock<PathBuf> = OnceLock::new();
+
+/// The shared directory, or a temp directory private to this process.
+///
+/// The private directory is `bincode-test-data-rust-<pid>`, named like the
+/// Nim side's `bincode-test-data-nim-<pid>`, and is removed when the process
+/// exits.
+pub fn test_data_dir() -> PathBuf {
+    shared_dir().unwrap_or_else(|| {
+        PRIVATE
+            .get_or_init(|| {
+                let dir = std::env::temp_dir().join(format!("bincode-test-data-{}-{}", PRODUCER, std::process::id()));
+                // Left behind by an earlier process with the same id
+                let _ = fs::remove_dir_all(&dir);
+                // SAFETY: `remove_private_dir` takes no arguments and does not unwind
+                unsafe { atexit(remove_private_dir) };
+                dir
+            })
+            .clone()
+    })
+}
+
+extern "C" {
+    fn atexit(f: extern "C" fn()) -> std::os::raw::c_int;
+}
+
+extern "C" fn remove_private_dir() {
+    if let Some(dir) = PRIVATE.get() {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+/// Write `bytes` to `name` in the test-data directory and record it in the manifest
+pub fn write_file(name: &str, bytes: &[u8]) -> io::Result<PathBuf> {
+    let dir = test_data_dir();
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(name);
+    fs::write(&path, bytes)?;
+    record(&dir, name, PRODUCER)?;
+    Ok(path)
+}
+
+/// Read `name` from the test-data directory
+pub fn read_file(name: &str) -> io::Result<Vec<u8>> {
+    let path = test_data_dir().join(name);
+    fs::read(&path).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+/// Append a manifest line for `name` written by `producer`
+pub fn record(dir: &Path, name: &str, producer: &str) -> io::Result<()> {
+    let unix_time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let line = format!("{}\t{}\t{}\t{}\n", name, producer, std::process::id(), unix_time);
+    // One write per line keeps appends from concurrent writers whole
+    OpenOptions::new().create(true).append(true).open(dir.join(MANIFEST_FILE))?.write_all(line.as_bytes())
+}
+
+/// Parse the manifest in `dir`; a missing manifest is empty
+pub fn read_manifest(dir: &Path) -> io::Result<Vec<ManifestEntry>> {
+    let text = match fs::read_to_string(dir.join(MANIFEST_FILE)) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    text.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("bad manifest line: {}", line));
+            let mut columns = line.split('\t');
+            let mut next = || columns.next().ok_or_else(invalid);
+            Ok(ManifestEntry {
+                file: next()?.to_string(),
+                producer: next()?.to_string(),
+                pid: next()?.parse().map_err(|_| invalid())?,
+                unix_time: next()?.parse().map_err(|_| invalid())?,
+            })
+        })
+        .collect()
+}
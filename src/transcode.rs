@@ -0,0 +1,364 @@
+//! Schema-driven conversion between bincode blobs and JSON.
+//!
+//! [`bincode_to_json`] decodes a blob with any [`KnownConfig`] following a
+//! [`Schema`]; [`json_to_bincode`] goes the other way. JSON shapes:
+//!
+//! - `unit` is `null`, integers and floats are numbers, `char` is a
+//!   one-character string
+//! - `u128`/`i128` are decimal strings, non-finite floats are `"NaN"`,
+//!   `"inf"` or `"-inf"`
+//! - sequences, arrays and tuples are arrays; maps are arrays of
+//!   `[key, value]` pairs
+//! - `None` is `null`, `Some(x)` is `x`, or `[x]` when `x` could itself be
+//!   `null` (a unit or an option), so `Some(())` and `Some(None)` stay distinct
+//!   from `None`
+//! - structs are objects with fields in declaration order
+//! - enum variants without fields are `"Name"`, others `{"Name": {fields}}`
+
+use bincode::error::{DecodeError, EncodeError};
+use serde_json::{Map, Value};
+
+use crate::config::KnownConfig;
+use crate::schema::{Field, Schema, Scope};
+use crate::value::{decode_value, encode_value, BincodeValue};
+
+/// Why a blob or JSON document could not be transcoded
+#[derive(Debug)]
+pub enum TranscodeError {
+    /// The blob is not a valid encoding of the schema
+    Decode(DecodeError),
+    /// The blob decoded but `bytes_read` is less than its length
+    TrailingBytes { bytes_read: usize, len: usize },
+    /// The JSON document does not fit the schema at `path`
+    Json { path: String, message: String },
+    /// bincode refused to encode a value
+    Encode(EncodeError),
+}
+
+impl std::fmt::Display for TranscodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TranscodeError::Decode(e) => write!(f, "decode failed: {}", e),
+            TranscodeError::TrailingBytes { bytes_read, len } => {
+                write!(f, "{} trailing bytes after decoding {} of {}", len - bytes_read, bytes_read, len)
+            }
+            TranscodeError::Json { path, message } => write!(f, "{}: {}", path, message),
+            TranscodeError::Encode(e) => write!(f, "encode failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TranscodeError {}
+
+impl From<DecodeError> for TranscodeError {
+    fn from(e: DecodeError) -> Self {
+        TranscodeError::Decode(e)
+    }
+}
+
+impl From<EncodeError> for TranscodeError {
+    fn from(e: EncodeError) -> Self {
+        TranscodeError::Encode(e)
+    }
+}
+
+/// Decode `bytes` as `schema` with `config`; every byte must be consumed
+pub fn bincode_to_json(bytes: &[u8], schema: &Schema, config: KnownConfig) -> Result<Value, TranscodeError> {
+    let (value, bytes_read) = decode_value(bytes, schema, config)?;
+    if bytes_read != bytes.len() {
+        return Err(TranscodeError::TrailingBytes { bytes_read, len: bytes.len() });
+    }
+    Ok(value_to_json(&value))
+}
+
+/// Encode a JSON document shaped like `schema` with `config`
+pub fn json_to_bincode(json: &Value, schema: &Schema, config: KnownConfig) -> Result<Vec<u8>, TranscodeError> {
+    let value = json_to_value(json, schema, &mut "$".to_string(), &mut Scope::default())?;
+    Ok(encode_value(&value, schema, config)?)
+}
+
+fn float_json(value: f64) -> Value {
+    if value.is_nan() {
+        Value::from("NaN")
+    } else if value.is_infinite() {
+        Value::from(if value > 0.0 { "inf" } else { "-inf" })
+    } else {
+        Value::from(value)
+    }
+}
+
+fn fields_json(fields: &[(String, BincodeValue)]) -> Value {
+    Value::Object(fields.iter().map(|(name, value)| (name.clone(), value_to_json(value))).collect())
+}
+
+/// JSON form of a decoded value (see the module docs for the shapes)
+pub fn value_to_json(value: &BincodeValue) -> Value {
+    match value {
+        BincodeValue::Unit => Value::Null,
+        BincodeValue::Bool(v) => Value::from(*v),
+        BincodeValue::U8(v) => Value::from(*v),
+        BincodeValue::U16(v) => Value::from(*v),
+        BincodeValue::U32(v) => Value::from(*v),
+        BincodeValue::U64(v) => Value::from(*v),
+        BincodeValue::U128(v) => Value::from(v.to_string()),
+        BincodeValue::I8(v) => Value::from(*v),
+        BincodeValue::I16(v) => Value::from(*v),
+        BincodeValue::I32(v) => Value::from(*v),
+        BincodeValue::I64(v) => Value::from(*v),
+        BincodeValue::I128(v) => Value::from(v.to_string()),
+        BincodeValue::F32(v) => float_json(*v as f64),
+        BincodeValue::F64(v) => float_json(*v),
+        BincodeValue::Char(v) => Value::from(v.to_string()),
+        BincodeValue::String(v) => Value::from(v.as_str()),
+        BincodeValue::Bytes(bytes) => Value::Array(bytes.iter().map(|&b| Value::from(b)).collect()),
+        BincodeValue::Seq(items) | BincodeValue::Tuple(items) => {
+            Value::Array(items.iter().map(value_to_json).collect())
+        }
+        BincodeValue::Map(pairs) => Value::Array(
+            pairs.iter().map(|(k, v)| Value::Array(vec![value_to_json(k), value_to_json(v)])).collect(),
+        ),
+        BincodeValue::Option(None) => Value::Null,
+        BincodeValue::Option(Some(inner)) => match **inner {
+            BincodeValue::Unit | BincodeValue::Option(_) => Value::Array(vec![value_to_json(inner)]),
+            _ => value_to_json(inner),
+        },
+        BincodeValue::Struct { fields, .. } => fields_json(fields),
+        BincodeValue::Enum { variant, fields, .. } if fields.is_empty() => Value::from(variant.as_str()),
+        BincodeValue::Enum { variant, fields, .. } => {
+            let mut object = Map::new();
+            object.insert(variant.clone(), fields_json(fields));
+            Value::Object(object)
+        }
+    }
+}
+
+fn mismatch(path: &str, expected: &str, found: &Value) -> TranscodeError {
+    TranscodeError::Json { path: path.to_string(), message: format!("expected {}, found {}", expected, found) }
+}
+
+/// Run `f` with `segment` appended to `path`
+fn nested<T>(path: &mut String, segment: &str, f: impl FnOnce(&mut String) -> T) -> T {
+    let len = path.len();
+    path.push_str(segment);
+    let result = f(path);
+    path.truncate(len);
+    result
+}
+
+fn json_uint(json: &Value, max: u64, path: &str) -> Result<u64, TranscodeError> {
+    json.as_u64()
+        .filter(|&value| value <= max)
+        .ok_or_else(|| mismatch(path, &format!("an integer in 0..={}", max), json))
+}
+
+fn json_int(json: &Value, min: i64, max: i64, path: &str) -> Result<i64, TranscodeError> {
+    json.as_i64()
+        .filter(|value| (min..=max).contains(value))
+        .ok_or_else(|| mismatch(path, &format!("an integer in {}..={}", min, max), json))
+}
+
+fn json_float(json: &Value, path: &str) -> Result<f64, TranscodeError> {
+    match json {
+        Value::Number(n) => n.as_f64().ok_or_else(|| mismatch(path, "a number", json)),
+        Value::String(s) if s == "NaN" => Ok(f64::NAN),
+        Value::String(s) if s == "inf" => Ok(f64::INFINITY),
+        Value::String(s) if s == "-inf" => Ok(f64::NEG_INFINITY),
+        _ => Err(mismatch(path, "a number", json)),
+    }
+}
+
+fn json_array<'a>(json: &'a Value, path: &str) -> Result<&'a Vec<Value>, TranscodeError> {
+    json.as_array().ok_or_else(|| mismatch(path, "an array", json))
+}
+
+fn json_items<'s>(
+    json: &Value,
+    len: Option<usize>,
+    item: &'s Schema,
+    path: &mut String,
+    scope: &mut Scope<'s>,
+) -> Result<BincodeValue, TranscodeError> {
+    let items = json_array(json, path)?;
+    if let Some(len) = len.filter(|&len| len != items.len()) {
+        return Err(mismatch(path, &format!("an array of {} items", len), json));
+    }
+    if *item == Schema::U8 {
+        let bytes = items
+            .iter()
+            .enumerate()
+            .map(|(i, value)| nested(path, &format!("[{}]", i), |path| json_uint(value, u8::MAX as u64, path)))
+            .map(|byte| byte.map(|byte| byte as u8))
+            .collect::<Result<_, _>>()?;
+        return Ok(BincodeValue::Bytes(bytes));
+    }
+    let values = items
+        .iter()
+        .enumerate()
+        .map(|(i, value)| nested(path, &format!("[{}]", i), |path| json_to_value(value, item, path, scope)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(BincodeValue::Seq(values))
+}
+
+fn json_fields<'s>(
+    json: &Value,
+    fields: &'s [Field],
+    path: &mut String,
+    scope: &mut Scope<'s>,
+) -> Result<Vec<(String, BincodeValue)>, TranscodeError> {
+    let object = json.as_object().ok_or_else(|| mismatch(path, "an object", json))?;
+    if let Some(unknown) = object.keys().find(|key| !fields.iter().any(|field| field.name == **key)) {
+        return Err(TranscodeError::Json { path: path.clone(), message: format!("unknown field '{}'", unknown) });
+    }
+    fields
+        .iter()
+        .map(|field| {
+            nested(path, &format!(".{}", field.name), |path| {
+                let value = object
+                    .get(&field.name)
+                    .ok_or_else(|| TranscodeError::Json { path: path.clone(), message: "missing field".to_string() })?;
+                Ok((field.name.clone(), json_to_value(value, &field.schema, path, scope)?))
+            })
+        })
+        .collect()
+}
+
+/// Read a JSON value of a schema without nested schemas; kept out of
+/// [`json_to_value`] so each level of a recursive walk uses less stack
+fn json_primitive(json: &Value, schema: &Schema, path: &str) -> Result<BincodeValue, TranscodeError> {
+    Ok(match schema {
+        Schema::Unit if json.is_null() => BincodeValue::Unit,
+        Schema::Unit => return Err(mismatch(path, "null", json)),
+        Schema::Bool => BincodeValue::Bool(json.as_bool().ok_or_else(|| mismatch(path, "a bool", json))?),
+        Schema::U8 => BincodeValue::U8(json_uint(json, u8::MAX as u64, path)? as u8),
+        Schema::U16 => BincodeValue::U16(json_uint(json, u16::MAX as u64, path)? as u16),
+        Schema::U32 => BincodeValue::U32(json_uint(json, u32::MAX as u64, path)? as u32),
+        Schema::U64 => BincodeValue::U64(json_uint(json, u64::MAX, path)?),
+        Schema::U128 => BincodeValue::U128(
+            json.as_str()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| mismatch(path, "a decimal u128 string", json))?,
+        ),
+        Schema::I8 => BincodeValue::I8(json_int(json, i8::MIN as i64, i8::MAX as i64, path)? as i8),
+        Schema::I16 => BincodeValue::I16(json_int(json, i16::MIN as i64, i16::MAX as i64, path)? as i16),
+        Schema::I32 => BincodeValue::I32(json_int(json, i32::MIN as i64, i32::MAX as i64, path)? as i32),
+        Schema::I64 => BincodeValue::I64(json_int(json, i64::MIN, i64::MAX, path)?),
+        Schema::I128 => BincodeValue::I128(
+            json.as_str()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| mismatch(path, "a decimal i128 string", json))?,
+        ),
+        Schema::F32 => BincodeValue::F32(json_float(json, path)? as f32),
+        Schema::F64 => BincodeValue::F64(json_float(json, path)?),
+        Schema::Char => {
+            let mut chars = json.as_str().map(str::chars).ok_or_else(|| mismatch(path, "a one-character string", json))?;
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => BincodeValue::Char(c),
+                _ => return Err(mismatch(path, "a one-character string", json)),
+            }
+        }
+        Schema::String => BincodeValue::String(json.as_str().ok_or_else(|| mismatch(path, "a string", json))?.to_string()),
+        _ => unreachable!("{:?} is not a primitive schema", schema),
+    })
+}
+
+/// Read a JSON document shaped like `schema` into a [`BincodeValue`]
+fn json_to_value<'s>(
+    json: &Value,
+    schema: &'s Schema,
+    path: &mut String,
+    scope: &mut Scope<'s>,
+) -> Result<BincodeValue, TranscodeError> {
+    Ok(match schema {
+        Schema::Unit
+        | Schema::Bool
+        | Schema::U8
+        | Schema::U16
+        | Schema::U32
+        | Schema::U64
+        | Schema::U128
+        | Schema::I8
+        | Schema::I16
+        | Schema::I32
+        | Schema::I64
+        | Schema::I128
+        | Schema::F32
+        | Schema::F64
+        | Schema::Char
+        | Schema::String => json_primitive(json, schema, path)?,
+        Schema::Seq { item } => json_items(json, None, item, path, scope)?,
+        Schema::Array { len, item } => json_items(json, Some(*len), item, path, scope)?,
+        Schema::Map { key, value } => BincodeValue::Map(
+            json_array(json, path)?
+                .iter()
+                .enumerate()
+                .map(|(i, pair)| {
+                    nested(path, &format!("[{}]", i), |path| match pair.as_array().map(Vec::as_slice) {
+                        Some([k, v]) => Ok((
+                            nested(path, "[0]", |path| json_to_value(k, key, path, scope))?,
+                            nested(path, "[1]", |path| json_to_value(v, value, path, scope))?,
+                        )),
+                        _ => Err(mismatch(path, "a [key, value] pair", pair)),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        Schema::NonZero { .. } if json.as_u64() == Some(0) => return Err(mismatch(path, "a non-zero integer", json)),
+        Schema::NonZero { item } => json_to_value(json, item, path, scope)?,
+        Schema::Option { .. } if json.is_null() => BincodeValue::Option(None),
+        Schema::Option { item } => {
+            let inner = if matches!(**item, Schema::Unit | Schema::Option { .. }) {
+                match json.as_array().map(Vec::as_slice) {
+                    Some([inner]) => inner,
+                    _ => return Err(mismatch(path, "a one-item array wrapping a nullable value", json)),
+                }
+            } else {
+                json
+            };
+            BincodeValue::Option(Some(Box::new(json_to_value(inner, item, path, scope)?)))
+        }
+        Schema::Tuple { items } => {
+            let values = json_array(json, path)?;
+            if values.len() != items.len() {
+                return Err(mismatch(path, &format!("an array of {} items", items.len()), json));
+            }
+            BincodeValue::Tuple(
+                values
+                    .iter()
+                    .zip(items)
+                    .enumerate()
+                    .map(|(i, (value, item))| {
+                        nested(path, &format!("[{}]", i), |path| json_to_value(value, item, path, scope))
+                    })
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        Schema::Struct { name, fields } => BincodeValue::Struct {
+            name: name.clone(),
+            fields: scope.within(schema, |scope| json_fields(json, fields, path, scope))?,
+        },
+        Schema::Enum { name, variants } => {
+            let (variant_name, fields) = match json {
+                Value::String(variant_name) => (variant_name, None),
+                Value::Object(object) if object.len() == 1 => {
+                    let (variant_name, fields) = object.iter().next().expect("one entry");
+                    (variant_name, Some(fields))
+                }
+                _ => return Err(mismatch(path, "a variant name or {\"Variant\": {fields}}", json)),
+            };
+            let variant = variants.iter().find(|variant| variant.name == *variant_name).ok_or_else(|| {
+                TranscodeError::Json { path: path.clone(), message: format!("unknown variant '{}'", variant_name) }
+            })?;
+            let fields = match fields {
+                None if variant.fields.is_empty() => Vec::new(),
+                None => return Err(mismatch(path, &format!("fields for variant '{}'", variant_name), json)),
+                Some(fields) => nested(path, &format!(".{}", variant_name), |path| {
+                    scope.within(schema, |scope| json_fields(fields, &variant.fields, path, scope))
+                })?,
+            };
+            BincodeValue::Enum { name: name.clone(), variant: variant.name.clone(), index: variant.index, fields }
+        }
+        Schema::Ref { name } => scope
+            .follow(name, |scope, target| json_to_value(json, target, path, scope))
+            .map_err(|message| TranscodeError::Json { path: path.clone(), message })??,
+    })
+}
@@ -0,0 +1,127 @@
+//! UTF-8 validation vectors for `String` decoding.
+//!
+//! Rust's `String` decode runs `std::str::from_utf8` on the payload and fails
+//! with `DecodeError::Utf8` on malformed input. [`INVALID_UTF8`] lists the
+//! classic malformations: overlong encodings, UTF-16 surrogates, truncated
+//! multi-byte sequences, code points above U+10FFFF and stray continuation
+//! bytes. [`VALID_UTF8`] lists the edge cases that must still decode, such as
+//! U+FFFF and U+10FFFF.
+//!
+//! Each payload is written with a `Vec<u8>` length prefix (the same prefix a
+//! `String` has) and labelled with Rust's decode outcome.
+//! `test_utf8_strings.nim` checks that Nim's `deserializeString` rejects the
+//! invalid payloads and agrees with Rust on the valid ones.
+
+use bincode::error::EncodeError;
+
+use crate::config::KnownConfig;
+use crate::mutation::{error_kind, Outcome};
+use crate::vectors::fnv1a64;
+
+/// File name used in the shared test-data directory
+pub const UTF8_FILE: &str = "utf8_strings.tsv";
+
+/// Payloads Rust rejects with `DecodeError::Utf8`
+pub const INVALID_UTF8: &[(&str, &[u8])] = &[
+    // Overlong encodings
+    ("overlong_nul_2", &[0xc0, 0x80]),
+    ("overlong_slash_2", &[0xc0, 0xaf]),
+    ("overlong_c1", &[0xc1, 0xbf]),
+    ("overlong_slash_3", &[0xe0, 0x80, 0xaf]),
+    ("overlong_u07ff_3", &[0xe0, 0x9f, 0xbf]),
+    ("overlong_slash_4", &[0xf0, 0x80, 0x80, 0xaf]),
+    ("overlong_uffff_4", &[0xf0, 0x8f, 0xbf, 0xbf]),
+    // Lone surrogates, and a pair encoded separately (CESU-8)
+    ("surrogate_high", &[0xed, 0xa0, 0x80]),
+    ("surrogate_low", &[0xed, 0xbf, 0xbf]),
+    ("surrogate_pair", &[0xed, 0xa0, 0xbd, 0xed, 0xb2, 0xa9]),
+    // Truncated multi-byte sequences
+    ("truncated_2", &[0xc3]),
+    ("truncated_3_of_1", &[0xe2]),
+    ("truncated_3_of_2", &[0xe2, 0x82]),
+    ("truncated_4_of_3", &[0xf0, 0x9f, 0x9a]),
+    ("truncated_inside_text", &[0x61, 0xe2, 0x82, 0x62]),
+    // Above U+10FFFF
+    ("above_max_u110000", &[0xf4, 0x90, 0x80, 0x80]),
+    ("above_max_f5", &[0xf5, 0x80, 0x80, 0x80]),
+    ("above_max_f7", &[0xf7, 0xbf, 0xbf, 0xbf]),
+    ("lead_f8", &[0xf8, 0x88, 0x80, 0x80, 0x80]),
+    ("byte_fe", &[0xfe]),
+    ("byte_ff", &[0xff]),
+    // Stray continuation bytes
+    ("continuation_alone", &[0x80]),
+    ("continuation_last", &[0xbf]),
+    ("continuation_after_ascii", &[0x61, 0x80]),
+    ("continuation_after_complete", &[0xc3, 0xa9, 0xa9]),
+    ("continuation_run", &[0x80, 0x81, 0x82, 0x83]),
+];
+
+/// Boundary payloads Rust accepts
+pub const VALID_UTF8: &[(&str, &[u8])] = &[
+    ("empty", &[]),
+    ("nul", &[0x00]),
+    ("u007f", &[0x7f]),
+    ("u0080", &[0xc2, 0x80]),
+    ("u07ff", &[0xdf, 0xbf]),
+    ("u0800", &[0xe0, 0xa0, 0x80]),
+    ("ud7ff", &[0xed, 0x9f, 0xbf]),
+    ("ue000", &[0xee, 0x80, 0x80]),
+    ("bom", &[0xef, 0xbb, 0xbf]),
+    ("ufffd", &[0xef, 0xbf, 0xbd]),
+    ("uffff", &[0xef, 0xbf, 0xbf]),
+    ("u10000", &[0xf0, 0x90, 0x80, 0x80]),
+    ("u10ffff", &[0xf4, 0x8f, 0xbf, 0xbf]),
+    ("mixed", "aé€🚀".as_bytes()),
+];
+
+/// One payload under one config, labelled with Rust's `String` decode outcome
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Utf8Vector {
+    pub name: &'static str,
+    pub config: KnownConfig,
+    pub payload: &'static [u8],
+    /// FNV-1a of the re-encoded string, or `err:Utf8`
+    pub outcome: Outcome,
+}
+
+impl Utf8Vector {
+    /// The payload with a length prefix, as a `String` would be written
+    pub fn encode(&self) -> Result<Vec<u8>, EncodeError> {
+        self.config.encode(&self.payload.to_vec())
+    }
+}
+
+/// Decode `bytes` as a `String` with `config` and label the result
+pub fn string_outcome(bytes: &[u8], config: KnownConfig) -> Outcome {
+    match config.decode::<String>(bytes) {
+        Ok((value, bytes_read)) if bytes_read == bytes.len() => {
+            let reencoded = config.encode(&value).expect("a decoded String re-encodes");
+            Outcome::Accepted { hash: fnv1a64(&reencoded) }
+        }
+        Ok(_) => Outcome::Rejected { kind: "TrailingBytes".to_string() },
+        Err(e) => Outcome::Rejected { kind: error_kind(&e) },
+    }
+}
+
+/// Every invalid and valid payload under every config
+pub fn utf8_vectors() -> Vec<Utf8Vector> {
+    let mut vectors = Vec::new();
+    for config in KnownConfig::ALL {
+        for &(name, payload) in INVALID_UTF8.iter().chain(VALID_UTF8) {
+            let bytes = config.encode(&payload.to_vec()).expect("short payloads encode");
+            let outcome = string_outcome(&bytes, config);
+            vectors.push(Utf8Vector { name, config, payload, outcome });
+        }
+    }
+    vectors
+}
+
+/// The vectors as TSV: name, config, payload hex, outcome
+pub fn render_tsv(vectors: &[Utf8Vector]) -> String {
+    let mut out = String::from("# name\tconfig\tpayload\toutcome\n");
+    for vector in vectors {
+        let payload: String = vector.payload.iter().map(|b| format!("{:02x}", b)).collect();
+        out.push_str(&format!("{}\t{}\t{}\t{}\n", vector.name, vector.config, payload, vector.outcome));
+    }
+    out
+}
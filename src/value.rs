@@ -0,0 +1,545 @@
+//! Dynamically typed bincode values.
+//!
+//! A [`BincodeValue`] holds a decoded message without a concrete Rust type,
+//! the way `serde_json::Value` holds a JSON document. [`decode_value`] and
+//! [`encode_value`] walk a [`Schema`] with any [`KnownConfig`], so inspectors,
+//! transcoders and fuzzers can handle arbitrary messages.
+//!
+//! Every item of a sequence or map normally reads at least one byte, so the
+//! input bounds how many items a length prefix can produce. Items that read
+//! no bytes (`()`, empty tuples and structs) are the exception: decoding more
+//! than [`MAX_ZERO_SIZE_ITEMS`] of them fails with
+//! [`DecodeError::LimitExceeded`] instead of allocating one value per
+//! claimed item.
+
+use bincode::de::read::Reader;
+use bincode::de::{Decoder, DecoderImpl};
+use bincode::enc::write::Writer;
+use bincode::enc::{Encoder, EncoderImpl};
+use bincode::error::{AllowedEnumVariants, DecodeError, EncodeError, IntegerType};
+use bincode::{Decode, Encode};
+
+use crate::config::{with_known_config, KnownConfig};
+use crate::schema::{Field, Schema, Scope};
+
+/// Most items of a zero-size schema one sequence, array or map may hold
+pub const MAX_ZERO_SIZE_ITEMS: usize = 1 << 16;
+
+/// A decoded bincode value
+#[derive(Debug, Clone, PartialEq)]
+pub enum BincodeValue {
+    Unit,
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    String(String),
+    /// A sequence or array of `u8`
+    Bytes(Vec<u8>),
+    /// A sequence or array of any other item type
+    Seq(Vec<BincodeValue>),
+    Map(Vec<(BincodeValue, BincodeValue)>),
+    Option(Option<Box<BincodeValue>>),
+    Tuple(Vec<BincodeValue>),
+    Struct { name: String, fields: Vec<(String, BincodeValue)> },
+    Enum { name: String, variant: String, index: u32, fields: Vec<(String, BincodeValue)> },
+}
+
+impl BincodeValue {
+    /// Short name of the value's kind, as used in schema JSON (`u32`, `seq`, `struct`, ...)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BincodeValue::Unit => "unit",
+            BincodeValue::Bool(_) => "bool",
+            BincodeValue::U8(_) => "u8",
+            BincodeValue::U16(_) => "u16",
+            BincodeValue::U32(_) => "u32",
+            BincodeValue::U64(_) => "u64",
+            BincodeValue::U128(_) => "u128",
+            BincodeValue::I8(_) => "i8",
+            BincodeValue::I16(_) => "i16",
+            BincodeValue::I32(_) => "i32",
+            BincodeValue::I64(_) => "i64",
+            BincodeValue::I128(_) => "i128",
+            BincodeValue::F32(_) => "f32",
+            BincodeValue::F64(_) => "f64",
+            BincodeValue::Char(_) => "char",
+            BincodeValue::String(_) => "string",
+            BincodeValue::Bytes(_) => "bytes",
+            BincodeValue::Seq(_) => "seq",
+            BincodeValue::Map(_) => "map",
+            BincodeValue::Option(_) => "option",
+            BincodeValue::Tuple(_) => "tuple",
+            BincodeValue::Struct { .. } => "struct",
+            BincodeValue::Enum { .. } => "enum",
+        }
+    }
+}
+
+/// Decode a value shaped like `schema` from the start of `bytes`,
+/// returning it with the number of bytes read
+pub fn decode_value(
+    bytes: &[u8],
+    schema: &Schema,
+    config: KnownConfig,
+) -> Result<(BincodeValue, usize), DecodeError> {
+    let mut reader = SliceCursor { bytes, pos: 0 };
+    let value = with_known_config!(config, |config| {
+        let mut decoder = DecoderImpl::new(&mut reader, config, ());
+        decode_with(&mut decoder, schema, &mut Scope::default())?
+    });
+    Ok((value, reader.pos))
+}
+
+/// Encode `value` as `schema` with `config`.
+///
+/// A value that does not fit the schema fails with [`EncodeError::OtherString`]
+/// naming the offending path, e.g. `$.header.version`.
+pub fn encode_value(
+    value: &BincodeValue,
+    schema: &Schema,
+    config: KnownConfig,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut writer = VecWriter(Vec::new());
+    with_known_config!(config, |config| {
+        let mut encoder = EncoderImpl::new(&mut writer, config);
+        encode_with(&mut encoder, value, schema, &mut "$".to_string(), &mut Scope::default())?
+    });
+    Ok(writer.0)
+}
+
+/// Reader over a slice that remembers how far it got
+struct SliceCursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader for SliceCursor<'_> {
+    fn read(&mut self, out: &mut [u8]) -> Result<(), DecodeError> {
+        let remaining = self.bytes.len() - self.pos;
+        if out.len() > remaining {
+            return Err(DecodeError::UnexpectedEnd { additional: out.len() - remaining });
+        }
+        out.copy_from_slice(&self.bytes[self.pos..self.pos + out.len()]);
+        self.pos += out.len();
+        Ok(())
+    }
+}
+
+struct VecWriter(Vec<u8>);
+
+impl Writer for VecWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.0.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Collection length prefix, decoded the way bincode decodes `Vec<T>` lengths
+fn decode_len<D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
+    let len = u64::decode(decoder)?;
+    usize::try_from(len).map_err(|_| DecodeError::OutsideUsizeRange(len))
+}
+
+/// Whether a value of `schema` is encoded as zero bytes
+fn reads_no_bytes(schema: &Schema) -> bool {
+    match schema {
+        Schema::Unit => true,
+        Schema::Array { len, item } => *len == 0 || reads_no_bytes(item),
+        Schema::Tuple { items } => items.iter().all(reads_no_bytes),
+        Schema::Struct { fields, .. } => fields.iter().all(|field| reads_no_bytes(&field.schema)),
+        _ => false,
+    }
+}
+
+/// Reject `len` items that consume no input, which the input cannot bound
+fn check_item_count(len: usize, item_reads_no_bytes: bool) -> Result<(), DecodeError> {
+    if item_reads_no_bytes && len > MAX_ZERO_SIZE_ITEMS {
+        return Err(DecodeError::LimitExceeded);
+    }
+    Ok(())
+}
+
+fn decode_items<'s, D: Decoder>(
+    decoder: &mut D,
+    len: usize,
+    item: &'s Schema,
+    scope: &mut Scope<'s>,
+) -> Result<BincodeValue, DecodeError> {
+    // No preallocation: a corrupt length runs out of input instead of memory
+    check_item_count(len, reads_no_bytes(item))?;
+    if *item == Schema::U8 {
+        return Ok(BincodeValue::Bytes(decode_bytes(decoder, len)?));
+    }
+    let mut items = Vec::new();
+    for _ in 0..len {
+        items.push(decode_with(decoder, item, scope)?);
+    }
+    Ok(BincodeValue::Seq(items))
+}
+
+/// Read `len` raw bytes one at a time. bincode's own `Vec<u8>` and `String`
+/// decoding reserves the claimed length up front, which a corrupt prefix can
+/// push to gigabytes before the input runs out.
+fn decode_bytes<D: Decoder>(decoder: &mut D, len: usize) -> Result<Vec<u8>, DecodeError> {
+    let mut bytes = Vec::new();
+    for _ in 0..len {
+        bytes.push(u8::decode(decoder)?);
+    }
+    Ok(bytes)
+}
+
+/// The integer type of `value` if it is a zero, which [`Schema::NonZero`] rejects
+fn zero_integer_type(value: &BincodeValue) -> Option<IntegerType> {
+    Some(match value {
+        BincodeValue::U8(0) => IntegerType::U8,
+        BincodeValue::U16(0) => IntegerType::U16,
+        BincodeValue::U32(0) => IntegerType::U32,
+        BincodeValue::U64(0) => IntegerType::U64,
+        BincodeValue::U128(0) => IntegerType::U128,
+        BincodeValue::I8(0) => IntegerType::I8,
+        BincodeValue::I16(0) => IntegerType::I16,
+        BincodeValue::I32(0) => IntegerType::I32,
+        BincodeValue::I64(0) => IntegerType::I64,
+        BincodeValue::I128(0) => IntegerType::I128,
+        _ => return None,
+    })
+}
+
+fn decode_fields<'s, D: Decoder>(
+    decoder: &mut D,
+    fields: &'s [Field],
+    scope: &mut Scope<'s>,
+) -> Result<Vec<(String, BincodeValue)>, DecodeError> {
+    fields
+        .iter()
+        .map(|field| Ok((field.name.clone(), decode_with(decoder, &field.schema, scope)?)))
+        .collect()
+}
+
+/// Decode a schema without nested schemas; kept out of [`decode_with`] so
+/// each level of a recursive walk uses less stack
+fn decode_primitive<D: Decoder>(decoder: &mut D, schema: &Schema) -> Result<BincodeValue, DecodeError> {
+    Ok(match schema {
+        Schema::Unit => BincodeValue::Unit,
+        Schema::Bool => BincodeValue::Bool(bool::decode(decoder)?),
+        Schema::U8 => BincodeValue::U8(u8::decode(decoder)?),
+        Schema::U16 => BincodeValue::U16(u16::decode(decoder)?),
+        Schema::U32 => BincodeValue::U32(u32::decode(decoder)?),
+        Schema::U64 => BincodeValue::U64(u64::decode(decoder)?),
+        Schema::U128 => BincodeValue::U128(u128::decode(decoder)?),
+        Schema::I8 => BincodeValue::I8(i8::decode(decoder)?),
+        Schema::I16 => BincodeValue::I16(i16::decode(decoder)?),
+        Schema::I32 => BincodeValue::I32(i32::decode(decoder)?),
+        Schema::I64 => BincodeValue::I64(i64::decode(decoder)?),
+        Schema::I128 => BincodeValue::I128(i128::decode(decoder)?),
+        Schema::F32 => BincodeValue::F32(f32::decode(decoder)?),
+        Schema::F64 => BincodeValue::F64(f64::decode(decoder)?),
+        Schema::Char => BincodeValue::Char(char::decode(decoder)?),
+        Schema::String => {
+            let len = decode_len(decoder)?;
+            let bytes = decode_bytes(decoder, len)?;
+            BincodeValue::String(
+                String::from_utf8(bytes).map_err(|e| DecodeError::Utf8 { inner: e.utf8_error() })?,
+            )
+        }
+        _ => unreachable!("{:?} is not a primitive schema", schema),
+    })
+}
+
+fn decode_with<'s, D: Decoder>(
+    decoder: &mut D,
+    schema: &'s Schema,
+    scope: &mut Scope<'s>,
+) -> Result<BincodeValue, DecodeError> {
+    Ok(match schema {
+        Schema::Unit
+        | Schema::Bool
+        | Schema::U8
+        | Schema::U16
+        | Schema::U32
+        | Schema::U64
+        | Schema::U128
+        | Schema::I8
+        | Schema::I16
+        | Schema::I32
+        | Schema::I64
+        | Schema::I128
+        | Schema::F32
+        | Schema::F64
+        | Schema::Char
+        | Schema::String => decode_primitive(decoder, schema)?,
+        Schema::Seq { item } => {
+            let len = decode_len(decoder)?;
+            decode_items(decoder, len, item, scope)?
+        }
+        Schema::Array { len, item } => decode_items(decoder, *len, item, scope)?,
+        Schema::Map { key, value } => {
+            let len = decode_len(decoder)?;
+            check_item_count(len, reads_no_bytes(key) && reads_no_bytes(value))?;
+            let mut pairs = Vec::new();
+            for _ in 0..len {
+                let k = decode_with(decoder, key, scope)?;
+                let v = decode_with(decoder, value, scope)?;
+                pairs.push((k, v));
+            }
+            BincodeValue::Map(pairs)
+        }
+        Schema::NonZero { item } => {
+            let value = decode_with(decoder, item, scope)?;
+            if let Some(non_zero_type) = zero_integer_type(&value) {
+                return Err(DecodeError::NonZeroTypeIsZero { non_zero_type });
+            }
+            value
+        }
+        Schema::Option { item } => match u8::decode(decoder)? {
+            0 => BincodeValue::Option(None),
+            1 => BincodeValue::Option(Some(Box::new(decode_with(decoder, item, scope)?))),
+            found => {
+                return Err(DecodeError::UnexpectedVariant {
+                    type_name: "Option<T>",
+                    allowed: &AllowedEnumVariants::Range { min: 0, max: 1 },
+                    found: found as u32,
+                })
+            }
+        },
+        Schema::Tuple { items } => BincodeValue::Tuple(
+            items.iter().map(|item| decode_with(decoder, item, scope)).collect::<Result<_, _>>()?,
+        ),
+        Schema::Struct { name, fields } => BincodeValue::Struct {
+            name: name.clone(),
+            fields: scope.within(schema, |scope| decode_fields(decoder, fields, scope))?,
+        },
+        Schema::Enum { name, variants } => {
+            let index = u32::decode(decoder)?;
+            let variant = variants.iter().find(|variant| variant.index == index).ok_or_else(|| {
+                DecodeError::OtherString(format!("unexpected variant index {} for enum {}", index, name))
+            })?;
+            BincodeValue::Enum {
+                name: name.clone(),
+                variant: variant.name.clone(),
+                index,
+                fields: scope.within(schema, |scope| decode_fields(decoder, &variant.fields, scope))?,
+            }
+        }
+        Schema::Ref { name } => {
+            scope.follow(name, |scope, target| decode_with(decoder, target, scope)).map_err(DecodeError::OtherString)??
+        }
+    })
+}
+
+fn mismatch(path: &str, schema: &Schema, value: &BincodeValue) -> EncodeError {
+    let expected = match schema {
+        Schema::Seq { item } => format!("seq of {}", schema_kind(item)),
+        Schema::Array { len, item } => format!("array of {} {}", len, schema_kind(item)),
+        Schema::Tuple { items } => format!("tuple of {}", items.len()),
+        Schema::Struct { name, .. } | Schema::Enum { name, .. } => {
+            format!("{} {}", schema_kind(schema), name)
+        }
+        _ => schema_kind(schema).to_string(),
+    };
+    let found = match value {
+        BincodeValue::Bytes(bytes) => format!("{} bytes", bytes.len()),
+        BincodeValue::Seq(items) => format!("seq of {} items", items.len()),
+        BincodeValue::Tuple(items) => format!("tuple of {}", items.len()),
+        _ => value.kind().to_string(),
+    };
+    EncodeError::OtherString(format!("{}: expected {}, found {}", path, expected, found))
+}
+
+fn schema_kind(schema: &Schema) -> &'static str {
+    match schema {
+        Schema::Unit => "unit",
+        Schema::Bool => "bool",
+        Schema::U8 => "u8",
+        Schema::U16 => "u16",
+        Schema::U32 => "u32",
+        Schema::U64 => "u64",
+        Schema::U128 => "u128",
+        Schema::I8 => "i8",
+        Schema::I16 => "i16",
+        Schema::I32 => "i32",
+        Schema::I64 => "i64",
+        Schema::I128 => "i128",
+        Schema::F32 => "f32",
+        Schema::F64 => "f64",
+        Schema::Char => "char",
+        Schema::String => "string",
+        Schema::NonZero { .. } => "non_zero",
+        Schema::Seq { .. } => "seq",
+        Schema::Array { .. } => "array",
+        Schema::Map { .. } => "map",
+        Schema::Option { .. } => "option",
+        Schema::Tuple { .. } => "tuple",
+        Schema::Struct { .. } => "struct",
+        Schema::Enum { .. } => "enum",
+        Schema::Ref { .. } => "ref",
+    }
+}
+
+/// Run `f` with `segment` appended to `path`
+fn nested<T>(path: &mut String, segment: &str, f: impl FnOnce(&mut String) -> T) -> T {
+    let len = path.len();
+    path.push_str(segment);
+    let result = f(path);
+    path.truncate(len);
+    result
+}
+
+fn encode_items<'a, 's, E: Encoder>(
+    encoder: &mut E,
+    items: impl Iterator<Item = (&'a BincodeValue, &'s Schema)>,
+    path: &mut String,
+    scope: &mut Scope<'s>,
+) -> Result<(), EncodeError> {
+    for (i, (value, schema)) in items.enumerate() {
+        nested(path, &format!("[{}]", i), |path| encode_with(encoder, value, schema, path, scope))?;
+    }
+    Ok(())
+}
+
+fn encode_fields<'s, E: Encoder>(
+    encoder: &mut E,
+    values: &[(String, BincodeValue)],
+    fields: &'s [Field],
+    path: &mut String,
+    scope: &mut Scope<'s>,
+) -> Result<(), EncodeError> {
+    if values.len() != fields.len() || values.iter().zip(fields).any(|((name, _), field)| *name != field.name) {
+        let expected: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+        let found: Vec<&str> = values.iter().map(|(name, _)| name.as_str()).collect();
+        return Err(EncodeError::OtherString(format!(
+            "{}: expected fields {:?}, found {:?}",
+            path, expected, found
+        )));
+    }
+    for ((name, value), field) in values.iter().zip(fields) {
+        nested(path, &format!(".{}", name), |path| encode_with(encoder, value, &field.schema, path, scope))?;
+    }
+    Ok(())
+}
+
+/// Encode a value of a schema without nested schemas; kept out of
+/// [`encode_with`] so each level of a recursive walk uses less stack
+fn encode_primitive<E: Encoder>(
+    encoder: &mut E,
+    value: &BincodeValue,
+    schema: &Schema,
+    path: &str,
+) -> Result<(), EncodeError> {
+    match (schema, value) {
+        (Schema::Unit, BincodeValue::Unit) => {}
+        (Schema::Bool, BincodeValue::Bool(v)) => v.encode(encoder)?,
+        (Schema::U8, BincodeValue::U8(v)) => v.encode(encoder)?,
+        (Schema::U16, BincodeValue::U16(v)) => v.encode(encoder)?,
+        (Schema::U32, BincodeValue::U32(v)) => v.encode(encoder)?,
+        (Schema::U64, BincodeValue::U64(v)) => v.encode(encoder)?,
+        (Schema::U128, BincodeValue::U128(v)) => v.encode(encoder)?,
+        (Schema::I8, BincodeValue::I8(v)) => v.encode(encoder)?,
+        (Schema::I16, BincodeValue::I16(v)) => v.encode(encoder)?,
+        (Schema::I32, BincodeValue::I32(v)) => v.encode(encoder)?,
+        (Schema::I64, BincodeValue::I64(v)) => v.encode(encoder)?,
+        (Schema::I128, BincodeValue::I128(v)) => v.encode(encoder)?,
+        (Schema::F32, BincodeValue::F32(v)) => v.encode(encoder)?,
+        (Schema::F64, BincodeValue::F64(v)) => v.encode(encoder)?,
+        (Schema::Char, BincodeValue::Char(v)) => v.encode(encoder)?,
+        (Schema::String, BincodeValue::String(v)) => v.encode(encoder)?,
+        _ => return Err(mismatch(path, schema, value)),
+    }
+    Ok(())
+}
+
+fn encode_with<'s, E: Encoder>(
+    encoder: &mut E,
+    value: &BincodeValue,
+    schema: &'s Schema,
+    path: &mut String,
+    scope: &mut Scope<'s>,
+) -> Result<(), EncodeError> {
+    match (schema, value) {
+        (
+            Schema::Unit
+            | Schema::Bool
+            | Schema::U8
+            | Schema::U16
+            | Schema::U32
+            | Schema::U64
+            | Schema::U128
+            | Schema::I8
+            | Schema::I16
+            | Schema::I32
+            | Schema::I64
+            | Schema::I128
+            | Schema::F32
+            | Schema::F64
+            | Schema::Char
+            | Schema::String,
+            _,
+        ) => encode_primitive(encoder, value, schema, path)?,
+        (Schema::Seq { item }, BincodeValue::Bytes(bytes)) if **item == Schema::U8 => {
+            // Same wire format as `Vec<u8>`: length prefix, then raw bytes
+            bytes.encode(encoder)?
+        }
+        (Schema::Array { len, item }, BincodeValue::Bytes(bytes))
+            if **item == Schema::U8 && bytes.len() == *len =>
+        {
+            encoder.writer().write(bytes)?
+        }
+        (Schema::Seq { item }, BincodeValue::Seq(items)) => {
+            (items.len() as u64).encode(encoder)?;
+            encode_items(encoder, items.iter().zip(std::iter::repeat(&**item)), path, scope)?;
+        }
+        (Schema::Array { len, item }, BincodeValue::Seq(items)) if items.len() == *len => {
+            encode_items(encoder, items.iter().zip(std::iter::repeat(&**item)), path, scope)?;
+        }
+        (Schema::Map { key, value }, BincodeValue::Map(pairs)) => {
+            (pairs.len() as u64).encode(encoder)?;
+            for (i, (k, v)) in pairs.iter().enumerate() {
+                nested(path, &format!("[{}]", i), |path| {
+                    encode_items(encoder, [(k, &**key), (v, &**value)].into_iter(), path, scope)
+                })?;
+            }
+        }
+        (Schema::NonZero { item }, _) => {
+            if zero_integer_type(value).is_some() {
+                return Err(EncodeError::OtherString(format!("{}: expected non-zero {}, found 0", path, schema_kind(item))));
+            }
+            encode_with(encoder, value, item, path, scope)?;
+        }
+        (Schema::Option { .. }, BincodeValue::Option(None)) => 0u8.encode(encoder)?,
+        (Schema::Option { item }, BincodeValue::Option(Some(value))) => {
+            1u8.encode(encoder)?;
+            encode_with(encoder, value, item, path, scope)?;
+        }
+        (Schema::Tuple { items }, BincodeValue::Tuple(values)) if values.len() == items.len() => {
+            encode_items(encoder, values.iter().zip(items), path, scope)?;
+        }
+        (Schema::Struct { fields, .. }, BincodeValue::Struct { fields: values, .. }) => {
+            scope.within(schema, |scope| encode_fields(encoder, values, fields, path, scope))?;
+        }
+        (Schema::Enum { variants, .. }, BincodeValue::Enum { variant, fields: values, .. }) => {
+            let found = variants.iter().find(|v| v.name == *variant).ok_or_else(|| {
+                EncodeError::OtherString(format!("{}: unknown variant '{}'", path, variant))
+            })?;
+            found.index.encode(encoder)?;
+            nested(path, &format!(".{}", variant), |path| {
+                scope.within(schema, |scope| encode_fields(encoder, values, &found.fields, path, scope))
+            })?;
+        }
+        (Schema::Ref { name }, _) => scope
+            .follow(name, |scope, target| encode_with(encoder, value, target, path, scope))
+            .map_err(|message| EncodeError::OtherString(format!("{}: {}", path, message)))??,
+        _ => return Err(mismatch(path, schema, value)),
+    }
+    Ok(())
+}
@@ -0,0 +1,310 @@
+//! `Vec<u8>` vectors exchanged by the Rust and Nim cross-verification tests.
+//!
+//! This list is the single source of truth: `tests/cross_verification.rs`
+//! uses it directly, and [`nim_vector_module`] renders it as
+//! `tests/cross_vectors.nim` (file names, payload specs, FNV-1a hashes and
+//! configs) for `test_cross_verification.nim` to import. Regenerate that file
+//! with `make nim-vectors` after changing anything here.
+
+use std::fmt::Write;
+
+use crate::config::{KnownConfig, CROSS_SIZE_LIMIT};
+
+/// How a vector's bytes are produced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Payload {
+    /// These exact bytes
+    Literal(&'static [u8]),
+    /// `len` copies of `byte`
+    Fill { byte: u8, len: usize },
+    /// `len` pseudo-random bytes from [`seeded_bytes`]
+    Seeded { seed: u64, len: usize },
+}
+
+impl Payload {
+    /// Number of bytes in the payload
+    pub fn len(&self) -> usize {
+        match self {
+            Payload::Literal(bytes) => bytes.len(),
+            Payload::Fill { len, .. } | Payload::Seeded { len, .. } => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The payload bytes
+    pub fn bytes(&self) -> Vec<u8> {
+        match self {
+            Payload::Literal(bytes) => bytes.to_vec(),
+            Payload::Fill { byte, len } => vec![*byte; *len],
+            Payload::Seeded { seed, len } => seeded_bytes(*seed, *len),
+        }
+    }
+}
+
+/// One cross-verification vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrossVector {
+    pub description: &'static str,
+    pub payload: Payload,
+}
+
+impl CrossVector {
+    /// The `Vec<u8>` value both sides serialize
+    pub fn data(&self) -> Vec<u8> {
+        self.payload.bytes()
+    }
+}
+
+/// Every vector, in file order (`*_001.bin` is the first)
+pub const CROSS_VECTORS: &[CrossVector] = &[
+    CrossVector { description: "[1, 2, 3, 4, 5]", payload: Payload::Literal(&[1, 2, 3, 4, 5]) },
+    CrossVector { description: "empty", payload: Payload::Literal(&[]) },
+    CrossVector { description: "[0, 255, 128, 64]", payload: Payload::Literal(&[0, 255, 128, 64]) },
+    CrossVector { description: "\"Hello, World!\" bytes", payload: Payload::Literal(b"Hello, World!") },
+    CrossVector { description: "[42]", payload: Payload::Literal(&[42]) },
+    CrossVector {
+        description: "\"Test with émojis 🚀\" bytes",
+        payload: Payload::Literal("Test with émojis 🚀".as_bytes()),
+    },
+    CrossVector { description: "100 x 0x01", payload: Payload::Fill { byte: 1, len: 100 } },
+    // The boundary vectors use position-dependent bytes so that offset and
+    // copy errors change the payload instead of moving zeros around.
+    CrossVector { description: "20 KiB seeded bytes", payload: Payload::Seeded { seed: 8, len: 20 * 1024 } },
+    // Just below the 251 threshold (single-byte length)
+    CrossVector { description: "250 seeded bytes", payload: Payload::Seeded { seed: 9, len: 250 } },
+    // Just at the 251 threshold (0xfb + u16 LE)
+    CrossVector { description: "251 seeded bytes", payload: Payload::Seeded { seed: 10, len: 251 } },
+    // Just below 2^16 (0xfb + u16 LE: 3 + 65535 = 65538)
+    CrossVector { description: "65535 seeded bytes", payload: Payload::Seeded { seed: 11, len: 65535 } },
+    // Just at 2^16 (0xfc + u32 LE: 5 + 65536 = 65541)
+    CrossVector { description: "65536 seeded bytes", payload: Payload::Seeded { seed: 12, len: 65536 } },
+];
+
+/// A config the vectors are exchanged in, with the file prefix each side writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrossConfig {
+    pub config: KnownConfig,
+    pub rust_prefix: &'static str,
+    pub nim_prefix: &'static str,
+}
+
+impl CrossConfig {
+    /// File the Rust tests write vector `index` (0-based) to
+    pub fn rust_file(&self, index: usize) -> String {
+        vector_file(self.rust_prefix, index)
+    }
+
+    /// File the Nim tests write vector `index` (0-based) to
+    pub fn nim_file(&self, index: usize) -> String {
+        vector_file(self.nim_prefix, index)
+    }
+}
+
+/// Variable-length integer encoding
+pub const CROSS_VARIABLE: CrossConfig =
+    CrossConfig { config: KnownConfig::Variable, rust_prefix: "rust_var", nim_prefix: "nim_var" };
+
+/// Fixed 8-byte integer encoding
+pub const CROSS_FIXED8: CrossConfig =
+    CrossConfig { config: KnownConfig::Fixed8, rust_prefix: "rust_fixed8", nim_prefix: "nim_fixed8" };
+
+/// Every config the vectors are exchanged in
+pub const CROSS_CONFIGS: [CrossConfig; 2] = [CROSS_VARIABLE, CROSS_FIXED8];
+
+/// `<prefix>_<nnn>.bin`, numbered from 1
+pub fn vector_file(prefix: &str, index: usize) -> String {
+    format!("{}_{:03}.bin", prefix, index + 1)
+}
+
+/// Next output of the SplitMix64 generator, advancing `state`
+pub fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// `len` bytes of SplitMix64 output seeded with `seed`, each word little-endian.
+///
+/// The generated Nim module implements the same function, so both sides
+/// build identical payloads from the seed alone.
+pub fn seeded_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    let mut bytes = Vec::with_capacity(len + 8);
+    while bytes.len() < len {
+        bytes.extend_from_slice(&splitmix64(&mut state).to_le_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+/// 64-bit FNV-1a hash, recorded for each vector in the Nim module
+pub fn fnv1a64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x100000001b3))
+}
+
+/// Nim enum value for a cross config, e.g. `ccFixed8`
+fn nim_config_name(config: &CrossConfig) -> String {
+    let mut chars = config.config.name().chars();
+    let first = chars.next().map(|c| c.to_ascii_uppercase()).into_iter();
+    format!("cc{}", first.chain(chars).collect::<String>())
+}
+
+/// Render [`CROSS_VECTORS`] and [`CROSS_CONFIGS`] as the Nim module `tests/cross_vectors.nim`
+pub fn nim_vector_module() -> String {
+    let mut out = String::new();
+    let count = CROSS_VECTORS.len();
+    let _ = writeln!(out, "# Generated by `bincode_tool nim-vectors` from src/vectors.rs. Do not edit.");
+    out.push_str(
+        "
+{.push raises: [], gcsafe.}
+
+import bincode_config
+
+type
+  PayloadKind* = enum
+    pkLiteral
+    pkFill
+    pkSeeded
+
+  CrossVector* = object
+    description*: string
+    length*: int
+    hash*: uint64 ## FNV-1a (64-bit) of the payload
+    case kind*: PayloadKind
+    of pkLiteral:
+      bytes*: seq[byte]
+    of pkFill:
+      fill*: byte
+    of pkSeeded:
+      seed*: uint64
+
+  CrossConfig* = enum
+",
+    );
+    for config in &CROSS_CONFIGS {
+        let _ = writeln!(out, "    {} = \"{}\"", nim_config_name(config), config.config.name());
+    }
+
+    let _ = write!(
+        out,
+        "
+const CrossSizeLimit* = {}'u64
+
+const CrossVectors*: array[{}, CrossVector] = [
+",
+        CROSS_SIZE_LIMIT, count
+    );
+    for vector in CROSS_VECTORS {
+        let data = vector.data();
+        let _ = write!(
+            out,
+            "  CrossVector(\n    description: \"{}\",\n    length: {},\n    hash: {:#018x}'u64,\n",
+            vector.description.replace('\\', "\\\\").replace('"', "\\\""),
+            data.len(),
+            fnv1a64(&data)
+        );
+        match vector.payload {
+            Payload::Literal([]) => {
+                out.push_str("    kind: pkLiteral,\n    bytes: @[],\n");
+            }
+            Payload::Literal(bytes) => {
+                out.push_str("    kind: pkLiteral,\n    bytes: @[\n");
+                for chunk in bytes.chunks(12) {
+                    let items: Vec<String> = chunk.iter().map(|b| format!("{}'u8", b)).collect();
+                    let _ = writeln!(out, "      {},", items.join(", "));
+                }
+                out.push_str("    ],\n");
+            }
+            Payload::Fill { byte, .. } => {
+                let _ = writeln!(out, "    kind: pkFill,\n    fill: {}'u8,", byte);
+            }
+            Payload::Seeded { seed, .. } => {
+                let _ = writeln!(out, "    kind: pkSeeded,\n    seed: {}'u64,", seed);
+            }
+        }
+        out.push_str("  ),\n");
+    }
+    out.push_str("]\n");
+
+    for (name, side) in [("RustFiles", "Rust"), ("NimFiles", "Nim")] {
+        let _ = write!(
+            out,
+            "\n# Files the {} tests write, per config\nconst {}*: array[CrossConfig, array[{}, string]] = [\n",
+            side, name, count
+        );
+        for config in &CROSS_CONFIGS {
+            let _ = writeln!(out, "  {}: [", nim_config_name(config));
+            for index in 0..count {
+                let file = if side == "Rust" { config.rust_file(index) } else { config.nim_file(index) };
+                let _ = writeln!(out, "    \"{}\",", file);
+            }
+            out.push_str("  ],\n");
+        }
+        out.push_str("]\n");
+    }
+
+    out.push_str(
+        "
+func bincodeConfig*(config: CrossConfig): BincodeConfig =
+  ## The `BincodeConfig` the Rust tests use for `config`
+  case config
+",
+    );
+    for config in &CROSS_CONFIGS {
+        let _ = writeln!(
+            out,
+            "  of {}:\n    {}.withLimit(CrossSizeLimit)",
+            nim_config_name(config),
+            config.config.nim_config()
+        );
+    }
+
+    out.push_str(
+        "
+func fnv1a64*(data: openArray[byte]): uint64 =
+  ## FNV-1a (64-bit) hash, as computed by the Rust generator
+  result = 0xcbf29ce484222325'u64
+  for b in data:
+    result = (result xor uint64(b)) * 0x100000001b3'u64
+
+func splitmix64*(state: var uint64): uint64 =
+  ## Next output of the SplitMix64 generator, advancing `state`
+  state += 0x9e3779b97f4a7c15'u64
+  var z = state
+  z = (z xor (z shr 30)) * 0xbf58476d1ce4e5b9'u64
+  z = (z xor (z shr 27)) * 0x94d049bb133111eb'u64
+  z xor (z shr 31)
+
+func seededBytes*(seed: uint64, length: int): seq[byte] =
+  ## `length` bytes of SplitMix64 output seeded with `seed`, each word little-endian
+  result = newSeq[byte](length)
+  var state = seed
+  var word = 0'u64
+  for i in 0 ..< length:
+    if i mod 8 == 0:
+      word = splitmix64(state)
+    result[i] = byte((word shr (8 * (i mod 8))) and 0xff)
+
+func payload*(vector: CrossVector): seq[byte] =
+  ## The bytes described by `vector`
+  case vector.kind
+  of pkLiteral:
+    vector.bytes
+  of pkFill:
+    var data = newSeq[byte](vector.length)
+    for i in 0 ..< data.len:
+      data[i] = vector.fill
+    data
+  of pkSeeded:
+    seededBytes(vector.seed, vector.length)
+
+{.pop.}
+",
+    );
+    out
+}
@@ -1,198 +1,82 @@
-use bincode;
-
-/// Create bincode config matching the format used by the Nim implementation
-/// and the Rust ↔ Nim cross‑verification tests.
-fn bincode_config() -> impl bincode::config::Config {
-    bincode::config::standard()
-        .with_little_endian()
-        .with_fixed_int_encoding()
-        .with_limit::<65536>()
+use std::borrow::Cow;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+use std::num::{NonZeroU32, NonZeroU64, Wrapping};
+use std::ops::Bound;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use bincode::error::{DecodeError, EncodeError};
+use bincode_wrapper::config::{fixed8_config, variable_config};
+use bincode_wrapper::snapshot::assert_snapshot;
+
+/// Expected encodings live here as annotated hex dumps; run with
+/// `UPDATE_SNAPSHOTS=1` to regenerate them.
+const SNAPSHOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+
+const WRAPPER_TEXT: &str = "Hello, bincode!                                                                                                                                       !";
+
+/// Compare `encoded` with `tests/snapshots/<name>.hex`
+#[track_caller]
+fn check_snapshot(name: &str, value: &str, config: &str, encoded: &[u8]) {
+    let config = match config {
+        "fixed8_config" => "fixed int encoding, little-endian",
+        "variable_config" => "variable int encoding, little-endian",
+        other => other,
+    };
+    let path = format!("{}/{}.hex", SNAPSHOT_DIR, name);
+    assert_snapshot(path, &[&format!("value: {}", value), &format!("config: {}", config)], encoded);
+}
+
+/// One snapshot test per line: `test_name: config => value;`
+macro_rules! format_snapshots {
+    ($($name:ident: $config:ident => $value:expr;)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let encoded = bincode::encode_to_vec($value, $config()).unwrap();
+                let name = stringify!($name).trim_start_matches("test_");
+                check_snapshot(name, stringify!($value), stringify!($config), &encoded);
+            }
+        )*
+    };
 }
 
 // ============================================================================
 // Category 1: Vec<u8> Format Tests
 // ============================================================================
 
-#[test]
-fn test_empty_vec_u8_format() {
-    let config = bincode_config();
-    let empty: Vec<u8> = vec![];
-    let encoded = bincode::encode_to_vec(&empty, config).unwrap();
-    
-    // Empty vec should be 8 zero bytes (u64 length = 0)
-    assert_eq!(encoded.len(), 8);
-    assert_eq!(encoded, [0, 0, 0, 0, 0, 0, 0, 0]);
-}
-
-#[test]
-fn test_small_vec_u8_format() {
-    let config = bincode_config();
-    let small = vec![1u8, 2, 3, 4, 5];
-    let encoded = bincode::encode_to_vec(&small, config).unwrap();
-    
-    // Should be 8 bytes (u64 length = 5) + 5 data bytes = 13 bytes total
-    assert_eq!(encoded.len(), 13);
-    assert_eq!(&encoded[..8], &[5, 0, 0, 0, 0, 0, 0, 0]); // length prefix
-    assert_eq!(&encoded[8..], &[1, 2, 3, 4, 5]); // data
-}
-
-#[test]
-fn test_single_byte_vec_u8_format() {
-    let config = bincode_config();
-    let single = vec![42u8];
-    let encoded = bincode::encode_to_vec(&single, config).unwrap();
-    
-    // Should be 8 bytes (u64 length = 1) + 1 data byte = 9 bytes total
-    assert_eq!(encoded.len(), 9);
-    assert_eq!(&encoded[..8], &[1, 0, 0, 0, 0, 0, 0, 0]); // length prefix
-    assert_eq!(encoded[8], 42); // data
-}
-
-#[test]
-fn test_large_vec_u8_format() {
-    let config = bincode_config();
-    let large: Vec<u8> = (0..256).map(|i| i as u8).collect();
-    let encoded = bincode::encode_to_vec(&large, config).unwrap();
-    
-    // Should be 8 bytes (u64 length = 256) + 256 data bytes = 264 bytes total
-    assert_eq!(encoded.len(), 264);
-    
-    // Verify length prefix (256 = 0x00000100 in little-endian)
-    assert_eq!(&encoded[..8], &[0, 1, 0, 0, 0, 0, 0, 0]);
-    
-    // Verify data
-    for i in 0..256 {
-        assert_eq!(encoded[8 + i], i as u8);
-    }
-}
-
-#[test]
-fn test_wrapper_vec_u8_format() {
-    let config = bincode_config();
-    let data = vec![1u8, 2, 3, 4, 5];
-    let encoded = bincode::encode_to_vec(&data, config).unwrap();
-    
-    // Verify format matches wrapper: 8-byte u64 length prefix + data
-    assert_eq!(encoded.len(), 13);
-    assert_eq!(&encoded[..8], &[5, 0, 0, 0, 0, 0, 0, 0]); // length prefix (u64 = 5)
-    assert_eq!(&encoded[8..], &[1, 2, 3, 4, 5]); // data
+format_snapshots! {
+    test_empty_vec_u8_format: fixed8_config => &Vec::<u8>::new();
+    test_small_vec_u8_format: fixed8_config => &vec![1u8, 2, 3, 4, 5];
+    test_single_byte_vec_u8_format: fixed8_config => &vec![42u8];
+    test_large_vec_u8_format: fixed8_config => &(0..=255u8).collect::<Vec<u8>>();
+    test_wrapper_vec_u8_format: fixed8_config => &vec![1u8, 2, 3, 4, 5];
 }
 
 // ============================================================================
 // Category 2: String Format Tests
 // ============================================================================
 
-#[test]
-fn test_string_format() {
-    let config = bincode_config();
-    let hello = "Hello".to_string();
-    let encoded = bincode::encode_to_vec(&hello, config).unwrap();
-    
-    // Should be 8 bytes (u64 length = 5) + 5 UTF-8 bytes = 13 bytes total
-    assert_eq!(encoded.len(), 13);
-    assert_eq!(&encoded[..8], &[5, 0, 0, 0, 0, 0, 0, 0]); // length prefix
-    assert_eq!(&encoded[8..], b"Hello"); // UTF-8 bytes
-}
-
-#[test]
-fn test_empty_string_format() {
-    let config = bincode_config();
-    let empty_str = "".to_string();
-    let encoded = bincode::encode_to_vec(&empty_str, config).unwrap();
-    
-    // Empty string should be 8 zero bytes (u64 length = 0)
-    assert_eq!(encoded.len(), 8);
-    assert_eq!(encoded, [0, 0, 0, 0, 0, 0, 0, 0]);
-}
-
-#[test]
-fn test_wrapper_string_format() {
-    let config = bincode_config();
-    let text = "Hello, bincode!                                                                                                                                       !";
-    let text_bytes = text.as_bytes().to_vec();
-    let encoded = bincode::encode_to_vec(&text_bytes, config).unwrap();
-    
-    // Verify format matches wrapper: 8-byte u64 length prefix + UTF-8 bytes
-    assert_eq!(encoded.len(), 159); // 8 bytes length + 151 bytes data
-    assert_eq!(&encoded[..8], &[151, 0, 0, 0, 0, 0, 0, 0]); // length prefix (u64 = 151)
-    
-    // Verify length decoding
-    let length = u64::from_le_bytes([
-        encoded[0], encoded[1], encoded[2], encoded[3],
-        encoded[4], encoded[5], encoded[6], encoded[7],
-    ]);
-    assert_eq!(length, 151);
-    assert_eq!(text_bytes.len(), 151);
+format_snapshots! {
+    test_string_format: fixed8_config => &"Hello".to_string();
+    test_empty_string_format: fixed8_config => &String::new();
+    test_wrapper_string_format: fixed8_config => &WRAPPER_TEXT.as_bytes().to_vec();
 }
 
 // ============================================================================
 // Category 3: Integer Format Tests
 // ============================================================================
 
-#[test]
-fn test_u32_format() {
-    let config = bincode_config();
-    let u32_val: u32 = 42;
-    let encoded = bincode::encode_to_vec(&u32_val, config).unwrap();
-    
-    // u32 should be 4 bytes, little-endian
-    assert_eq!(encoded.len(), 4);
-    assert_eq!(encoded, [0x2A, 0x00, 0x00, 0x00]);
-}
-
-#[test]
-fn test_u32_encoding() {
-    let config = bincode_config();
-    
-    let u32_test: u32 = 0x12345678;
-    let encoded = bincode::encode_to_vec(&u32_test, config).unwrap();
-    assert_eq!(encoded, [0x78, 0x56, 0x34, 0x12], "u32 encoding mismatch");
-}
-
-#[test]
-fn test_u64_format() {
-    let config = bincode_config();
-    let u64_val: u64 = 0x1234567890ABCDEF;
-    let encoded = bincode::encode_to_vec(&u64_val, config).unwrap();
-    
-    // u64 should be 8 bytes, little-endian
-    assert_eq!(encoded.len(), 8);
-    assert_eq!(encoded, [0xEF, 0xCD, 0xAB, 0x90, 0x78, 0x56, 0x34, 0x12]);
-}
-
-#[test]
-fn test_u64_encoding() {
-    let config = bincode_config();
-    
-    let u64_test: u64 = 0x0123456789ABCDEF;
-    let encoded = bincode::encode_to_vec(&u64_test, config).unwrap();
-    assert_eq!(encoded, [0xEF, 0xCD, 0xAB, 0x89, 0x67, 0x45, 0x23, 0x01], "u64 encoding mismatch");
-}
-
-#[test]
-fn test_i32_format() {
-    let config = bincode_config();
-    let i32_val: i32 = -42;
-    let encoded = bincode::encode_to_vec(&i32_val, config).unwrap();
-    
-    // i32 should be 4 bytes, little-endian, two's complement
-    assert_eq!(encoded.len(), 4);
-    assert_eq!(encoded, [0xD6, 0xFF, 0xFF, 0xFF]);
-}
-
-#[test]
-fn test_i32_encoding() {
-    let config = bincode_config();
-    
-    // i32: -1 (should be 0xFFFFFFFF)
-    let i32_test: i32 = -1;
-    let encoded = bincode::encode_to_vec(&i32_test, config).unwrap();
-    assert_eq!(encoded, [0xFF, 0xFF, 0xFF, 0xFF], "i32 -1 encoding mismatch");
-    
-    // i32: 0x7FFFFFFF (max positive)
-    let i32_max: i32 = 0x7FFFFFFF;
-    let encoded_max = bincode::encode_to_vec(&i32_max, config).unwrap();
-    assert_eq!(encoded_max, [0xFF, 0xFF, 0xFF, 0x7F], "i32 max encoding mismatch");
+format_snapshots! {
+    test_u32_format: fixed8_config => 42u32;
+    test_u32_encoding: fixed8_config => 0x12345678u32;
+    test_u64_format: fixed8_config => 0x1234567890ABCDEFu64;
+    test_u64_encoding: fixed8_config => 0x0123456789ABCDEFu64;
+    test_i32_format: fixed8_config => -42i32;
+    test_i32_encoding: fixed8_config => -1i32;
+    test_i32_max_encoding: fixed8_config => i32::MAX;
 }
 
 // ============================================================================
@@ -201,7 +85,7 @@ fn test_i32_encoding() {
 
 #[test]
 fn test_vec_u8_length_encoding() {
-    let config = bincode_config();
+    let config = fixed8_config();
     
     // Test various lengths
     for len in [0u64, 1, 5, 255, 256, 65535, 65536].iter() {
@@ -224,72 +108,114 @@ fn test_vec_u8_length_encoding() {
 // Category 5: Standard vs Fixed Encoding Comparison Tests
 // ============================================================================
 
-#[test]
-fn test_standard_vs_fixed_encoding_vec_u8() {
-    let data = vec![1u8, 2, 3, 4, 5];
-    
-    // Standard config (variable-length encoding)
-    let standard_config = bincode::config::standard();
-    let encoded_standard = bincode::encode_to_vec(&data, standard_config).unwrap();
-    
-    // Fixed encoding config (matches our wrapper)
-    let fixed_config = bincode::config::standard()
-        .with_little_endian()
-        .with_fixed_int_encoding()
-        .with_limit::<65536>();
-    let encoded_fixed = bincode::encode_to_vec(&data, fixed_config).unwrap();
-    
-    // Standard uses variable-length: first byte is length (5)
-    assert_eq!(encoded_standard, [5, 1, 2, 3, 4, 5]);
-    assert_eq!(encoded_standard.len(), 6);
-    
-    // Fixed uses 8-byte u64 length prefix
-    assert_eq!(&encoded_fixed[..8], &[5, 0, 0, 0, 0, 0, 0, 0]);
-    assert_eq!(&encoded_fixed[8..], &[1, 2, 3, 4, 5]);
-    assert_eq!(encoded_fixed.len(), 13);
+format_snapshots! {
+    test_standard_vs_fixed_encoding_vec_u8_standard: variable_config => &vec![1u8, 2, 3, 4, 5];
+    test_standard_vs_fixed_encoding_vec_u8_fixed: fixed8_config => &vec![1u8, 2, 3, 4, 5];
+    test_standard_vs_fixed_encoding_string_standard: variable_config => &WRAPPER_TEXT.as_bytes().to_vec();
+    test_standard_vs_fixed_encoding_string_fixed: fixed8_config => &WRAPPER_TEXT.as_bytes().to_vec();
+    test_standard_vs_fixed_encoding_empty_standard: variable_config => &Vec::<u8>::new();
+    test_standard_vs_fixed_encoding_empty_fixed: fixed8_config => &Vec::<u8>::new();
 }
 
-#[test]
-fn test_standard_vs_fixed_encoding_string() {
-    let text = "Hello, bincode!                                                                                                                                       !";
-    let text_bytes = text.as_bytes().to_vec();
-    
-    let standard_config = bincode::config::standard();
-    let fixed_config = bincode::config::standard()
-        .with_little_endian()
-        .with_fixed_int_encoding()
-        .with_limit::<65536>();
-    
-    let encoded_standard = bincode::encode_to_vec(&text_bytes, standard_config).unwrap();
-    let encoded_fixed = bincode::encode_to_vec(&text_bytes, fixed_config).unwrap();
-    
-    // Standard: first byte is length (151 = 0x97)
-    assert_eq!(encoded_standard[0], 151);
-    assert_eq!(encoded_standard.len(), 152); // 1 byte length + 151 bytes data
-    
-    // Fixed: first 8 bytes are length (151 in little-endian u64)
-    assert_eq!(&encoded_fixed[..8], &[151, 0, 0, 0, 0, 0, 0, 0]);
-    assert_eq!(encoded_fixed.len(), 159); // 8 bytes length + 151 bytes data
-}
+// ============================================================================
+// Category 6: Standard-Library Type Format Tests
+// ============================================================================
 
-#[test]
-fn test_standard_vs_fixed_encoding_empty() {
-    let empty: Vec<u8> = vec![];
-    
-    let standard_config = bincode::config::standard();
-    let fixed_config = bincode::config::standard()
-        .with_little_endian()
-        .with_fixed_int_encoding()
-        .with_limit::<65536>();
-    
-    let encoded_standard = bincode::encode_to_vec(&empty, standard_config).unwrap();
-    let encoded_fixed = bincode::encode_to_vec(&empty, fixed_config).unwrap();
-    
-    // Standard: single zero byte
-    assert_eq!(encoded_standard, [0]);
-    assert_eq!(encoded_standard.len(), 1);
-    
-    // Fixed: 8 zero bytes
-    assert_eq!(encoded_fixed, [0, 0, 0, 0, 0, 0, 0, 0]);
-    assert_eq!(encoded_fixed.len(), 8);
+format_snapshots! {
+    test_duration_fixed: fixed8_config => Duration::new(90, 500_000_000);
+    test_duration_standard: variable_config => Duration::new(90, 500_000_000);
+    test_system_time_fixed: fixed8_config => SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123);
+    test_system_time_standard: variable_config => SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123);
+    test_ipv4_addr_fixed: fixed8_config => Ipv4Addr::new(192, 168, 1, 1);
+    test_ipv4_addr_standard: variable_config => Ipv4Addr::new(192, 168, 1, 1);
+    test_ipv6_addr_fixed: fixed8_config => Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    test_ipv6_addr_standard: variable_config => Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    test_ip_addr_v4_fixed: fixed8_config => IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    test_ip_addr_v4_standard: variable_config => IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+    test_socket_addr_v4_fixed: fixed8_config => SocketAddr::from(([127, 0, 0, 1], 8080));
+    test_socket_addr_v4_standard: variable_config => SocketAddr::from(([127, 0, 0, 1], 8080));
+    test_socket_addr_v6_fixed: fixed8_config => SocketAddr::from((Ipv6Addr::LOCALHOST, 443));
+    test_socket_addr_v6_standard: variable_config => SocketAddr::from((Ipv6Addr::LOCALHOST, 443));
+    test_non_zero_u32_fixed: fixed8_config => NonZeroU32::new(42).unwrap();
+    test_non_zero_u32_standard: variable_config => NonZeroU32::new(42).unwrap();
+    test_non_zero_u64_fixed: fixed8_config => NonZeroU64::MAX;
+    test_non_zero_u64_standard: variable_config => NonZeroU64::MAX;
+    test_range_fixed: fixed8_config => 3u32..300;
+    test_range_standard: variable_config => 3u32..300;
+    test_range_inclusive_fixed: fixed8_config => 1u32..=255;
+    test_range_inclusive_standard: variable_config => 1u32..=255;
+    test_bound_excluded_fixed: fixed8_config => Bound::Excluded(5u32);
+    test_bound_excluded_standard: variable_config => Bound::Excluded(5u32);
+    test_wrapping_fixed: fixed8_config => Wrapping(u32::MAX);
+    test_wrapping_standard: variable_config => Wrapping(u32::MAX);
+    test_cow_str_fixed: fixed8_config => Cow::Borrowed("cow");
+    test_cow_str_standard: variable_config => Cow::Borrowed("cow");
+    test_path_buf_fixed: fixed8_config => PathBuf::from("/var/lib/data.bin");
+    test_path_buf_standard: variable_config => PathBuf::from("/var/lib/data.bin");
+}
+
+#[test]
+fn test_non_zero_rejects_zero() {
+    let err = bincode::decode_from_slice::<NonZeroU32, _>(&[0], variable_config()).unwrap_err();
+    assert!(matches!(err, DecodeError::NonZeroTypeIsZero { .. }), "{:?}", err);
+    let err = bincode::decode_from_slice::<NonZeroU64, _>(&[0; 8], fixed8_config()).unwrap_err();
+    assert!(matches!(err, DecodeError::NonZeroTypeIsZero { .. }), "{:?}", err);
+}
+
+#[test]
+fn test_bound_tags_differ_from_declaration_order() {
+    // std declares Included, Excluded, Unbounded; bincode writes Unbounded = 0
+    let tags: Vec<Vec<u8>> = [Bound::Unbounded, Bound::Included(5u32), Bound::Excluded(5u32)]
+        .iter()
+        .map(|bound| bincode::encode_to_vec(bound, variable_config()).unwrap())
+        .collect();
+    assert_eq!(tags, [vec![0], vec![1, 5], vec![2, 5]]);
+    assert!(bincode::decode_from_slice::<Bound<u32>, _>(&[3, 5], variable_config()).is_err());
+}
+
+#[test]
+fn test_socket_addr_v6_drops_flowinfo_and_scope_id() {
+    let addr = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 7, 9);
+    let encoded = bincode::encode_to_vec(addr, fixed8_config()).unwrap();
+    assert_eq!(encoded.len(), 16 + 2);
+    let (decoded, _): (SocketAddrV6, _) = bincode::decode_from_slice(&encoded, fixed8_config()).unwrap();
+    assert_eq!((decoded.flowinfo(), decoded.scope_id()), (0, 0));
+}
+
+#[test]
+fn test_duration_decode_normalizes_nanos() {
+    // 1 s + 1.5e9 ns is accepted and read as 2.5 s; it re-encodes differently
+    let mut bytes = bincode::encode_to_vec(1u64, fixed8_config()).unwrap();
+    bytes.extend_from_slice(&1_500_000_000u32.to_le_bytes());
+    let (duration, _): (Duration, _) = bincode::decode_from_slice(&bytes, fixed8_config()).unwrap();
+    assert_eq!(duration, Duration::new(2, 500_000_000));
+    let mut overflow = u64::MAX.to_le_bytes().to_vec();
+    overflow.extend_from_slice(&1_000_000_000u32.to_le_bytes());
+    let err = bincode::decode_from_slice::<Duration, _>(&overflow, fixed8_config()).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidDuration { .. }), "{:?}", err);
+}
+
+#[test]
+fn test_system_time_before_epoch_fails_to_encode() {
+    let before = SystemTime::UNIX_EPOCH - Duration::from_secs(1);
+    let err = bincode::encode_to_vec(before, fixed8_config()).unwrap_err();
+    assert!(matches!(err, EncodeError::InvalidSystemTime { .. }), "{:?}", err);
+}
+
+#[test]
+fn test_wrappers_encode_as_their_contents() {
+    let config = variable_config();
+    let text = bincode::encode_to_vec("shared", config).unwrap();
+    assert_eq!(bincode::encode_to_vec(Rc::new("shared".to_string()), config).unwrap(), text);
+    assert_eq!(bincode::encode_to_vec(Arc::new("shared".to_string()), config).unwrap(), text);
+    assert_eq!(bincode::encode_to_vec(Cow::<str>::Owned("shared".to_string()), config).unwrap(), text);
+    assert_eq!(bincode::encode_to_vec(PathBuf::from("shared"), config).unwrap(), text);
+    assert_eq!(
+        bincode::encode_to_vec(Wrapping(300u32), config).unwrap(),
+        bincode::encode_to_vec(300u32, config).unwrap()
+    );
+    assert_eq!(
+        bincode::encode_to_vec(Duration::new(7, 9), config).unwrap(),
+        bincode::encode_to_vec((7u64, 9u32), config).unwrap()
+    );
 }
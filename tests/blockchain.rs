@@ -0,0 +1,110 @@
+use bincode_wrapper::blockchain::{
+    chain_cases, chain_file, sample_address, sample_hash, sample_proof, sample_transaction, CHAIN_CONFIGS, SAMPLE_U256,
+};
+use bincode_wrapper::codegen::nim::generate_nim_module;
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::reference::{Hash32, SignedTransaction};
+use bincode_wrapper::schema::BincodeSchema;
+use bincode_wrapper::test_data;
+
+// ============================================================================
+// Layout Tests
+// ============================================================================
+
+#[test]
+fn test_fixed_arrays_have_no_length_prefix() {
+    for config in KnownConfig::ALL {
+        assert_eq!(config.encode(&sample_hash(0)).unwrap(), sample_hash(0), "{}", config);
+        assert_eq!(config.encode(&sample_address()).unwrap(), sample_address(), "{}", config);
+    }
+}
+
+#[test]
+fn test_vec_of_hashes_has_length_prefix() {
+    let proof = sample_proof(3);
+    let flat: Vec<u8> = proof.concat();
+    let variable = KnownConfig::Variable.encode(&proof).unwrap();
+    assert_eq!((variable[0], &variable[1..]), (3, &flat[..]));
+    let fixed = KnownConfig::Fixed8.encode(&proof).unwrap();
+    assert_eq!((&fixed[..8], &fixed[8..]), (&3u64.to_le_bytes()[..], &flat[..]));
+    // The same 32 bytes as a Vec<u8> gain a prefix that the array does not have
+    assert_eq!(KnownConfig::Variable.encode(&sample_hash(0).to_vec()).unwrap().len(), 33);
+    assert_eq!(KnownConfig::Variable.encode(&Vec::<Hash32>::new()).unwrap(), [0]);
+}
+
+#[test]
+fn test_u256_limbs_follow_int_encoding() {
+    let fixed: Vec<u8> = SAMPLE_U256.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+    assert_eq!(KnownConfig::Fixed8.encode(&SAMPLE_U256).unwrap(), fixed);
+    let big: Vec<u8> = SAMPLE_U256.iter().flat_map(|limb| limb.to_be_bytes()).collect();
+    assert_eq!(KnownConfig::Fixed8BigEndian.encode(&SAMPLE_U256).unwrap(), big);
+    // Under varint each limb is its own varint: 9 + 1 + 3 + 9 bytes, not 32
+    assert_eq!(KnownConfig::Variable.encode(&SAMPLE_U256).unwrap().len(), 22);
+    assert_eq!(KnownConfig::Variable.encode(&[0u64; 4]).unwrap(), [0, 0, 0, 0]);
+}
+
+#[test]
+fn test_transaction_is_fields_in_order() {
+    let tx = sample_transaction();
+    let config = KnownConfig::Fixed8;
+    let mut expected = config.encode(&tx.nonce).unwrap();
+    for part in [
+        config.encode(&tx.gas_price).unwrap(),
+        config.encode(&tx.gas_limit).unwrap(),
+        tx.to.to_vec(),
+        config.encode(&tx.value).unwrap(),
+        config.encode(&tx.data).unwrap(),
+        config.encode(&tx.v).unwrap(),
+        tx.r.to_vec(),
+        tx.s.to_vec(),
+    ] {
+        expected.extend(part);
+    }
+    assert_eq!(config.encode(&tx).unwrap(), expected);
+    assert_eq!(expected.len(), 8 + 32 + 8 + 20 + 32 + (8 + 4) + 8 + 32 + 32);
+}
+
+#[test]
+fn test_committed_signed_transaction_codec_is_up_to_date() {
+    let committed = std::fs::read_to_string("bincode/examples/signed_transaction_codec.nim").unwrap();
+    let generated = generate_nim_module(&SignedTransaction::schema()).unwrap();
+    assert_eq!(committed, generated, "signed_transaction_codec.nim is stale, run `make nim-codegen`");
+}
+
+// ============================================================================
+// Cross-Verification Tests
+// ============================================================================
+
+#[test]
+fn test_chain_values_roundtrip() {
+    for (name, value) in chain_cases() {
+        for config in KnownConfig::ALL {
+            let bytes = value.encode(config).unwrap();
+            assert_eq!(value.decode_same(&bytes, config).unwrap(), value, "{} {}", name, config);
+        }
+    }
+}
+
+/// Step 1 of `make test-blockchain`: the Nim side decodes these files
+#[test]
+fn test_write_chain_vectors() {
+    for (name, value) in chain_cases() {
+        for config in CHAIN_CONFIGS {
+            test_data::write_file(&chain_file("rust", name, config), &value.encode(config).unwrap()).unwrap();
+        }
+    }
+}
+
+/// Step 3 of `make test-blockchain`: decode what Nim wrote
+#[test]
+#[ignore = "reads files written by test_blockchain.nim; run by `make test-blockchain`"]
+fn test_nim_chain_vectors_decode() {
+    test_data::require_shared_dir("test_blockchain.nim");
+    for (name, value) in chain_cases() {
+        for config in CHAIN_CONFIGS {
+            let bytes = test_data::read_file(&chain_file("nim", name, config)).unwrap();
+            assert_eq!(bytes, value.encode(config).unwrap(), "{} {}", name, config);
+            assert_eq!(value.decode_same(&bytes, config).unwrap(), value, "{} {}", name, config);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::fs;
+
+use bincode_wrapper::cases::{load_cases, parse_hex, Expect, Input, Output};
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::test_data;
+
+const CASES_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cases.toml");
+
+// ============================================================================
+// Case File Tests
+// ============================================================================
+
+/// Runs every case in `tests/cases.toml` and writes the encoded vectors to the
+/// test-data directory as `case_<name>_<config>.bin`
+#[test]
+fn test_cases_file() {
+    let cases = load_cases(&fs::read_to_string(CASES_FILE).unwrap()).unwrap_or_else(|e| panic!("{}", e));
+    let mut problems = Vec::new();
+    for case in &cases {
+        for checked in case.run() {
+            if let Some(problem) = &checked.problem {
+                problems.push(format!("{} ({}): {}", case.name, checked.config, problem));
+            }
+            if let Ok(Output::Bytes(bytes)) = &checked.output {
+                test_data::write_file(&format!("case_{}_{}.bin", case.name, checked.config), bytes).unwrap();
+            }
+        }
+    }
+    assert!(problems.is_empty(), "{} of the cases in tests/cases.toml failed:\n{}", problems.len(), problems.join("\n"));
+    println!("Ran {} cases", cases.len());
+}
+
+// ============================================================================
+// Loader Tests
+// ============================================================================
+
+#[test]
+fn test_load_encode_and_decode_cases() {
+    let cases = load_cases(
+        r#"
+        [[case]]
+        name = "small"
+        type = "Vec<u8>"
+        configs = ["variable", "fixed8"]
+        value = [7]
+        expect.variable = "01 07"
+        expect.fixed8 = "0100000000000000 07"
+
+        [[case]]
+        name = "short"
+        type = "String"
+        configs = ["fixed8_be"]
+        bytes = "00"
+        expect.error = "UnexpectedEnd"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(cases[0].input, Input::Value(serde_json::json!([7])));
+    assert_eq!(
+        cases[0].expect,
+        Expect::Bytes(vec![(KnownConfig::Variable, vec![1, 7]), (KnownConfig::Fixed8, vec![1, 0, 0, 0, 0, 0, 0, 0, 7])])
+    );
+    assert_eq!(cases[1].input, Input::Bytes(vec![0]));
+    assert!(cases.iter().all(|case| case.run().iter().all(|checked| checked.problem.is_none())));
+}
+
+#[test]
+fn test_mismatch_is_reported() {
+    let cases = load_cases(
+        r#"
+        [[case]]
+        name = "wrong"
+        type = "u32"
+        configs = ["variable"]
+        value = 300
+        expect.variable = "fb 2c 02"
+        "#,
+    )
+    .unwrap();
+    let checked = cases[0].run();
+    assert_eq!(checked[0].problem.as_deref(), Some("expected bytes [fb 2c 02], got [fb 2c 01]"));
+}
+
+#[test]
+fn test_invalid_cases_are_rejected() {
+    let load = |case: &str| load_cases(&format!("[[case]]\nname = \"bad\"\n{}", case)).unwrap_err();
+    assert!(load("type = \"u7\"\nconfigs = [\"variable\"]\nvalue = 1\nexpect.variable = \"01\"").contains("unknown type 'u7'"));
+    assert!(load("type = \"u32\"\nconfigs = [\"variable\", \"fixed8\"]\nvalue = 1\nexpect.variable = \"01\"")
+        .contains("no expected bytes for config 'fixed8'"));
+    assert!(load("type = \"u32\"\nconfigs = [\"variable\"]\nbytes = \"01\"\nexpect.variable = \"01\"")
+        .contains("expects `expect.value` or `expect.error`"));
+    assert!(load("type = \"u32\"\nconfigs = [\"variable\"]\nvalue = 1\nexpect.error = \"X\"\ncomment = 1")
+        .contains("unknown field `comment`"));
+    assert_eq!(parse_hex("0").unwrap_err(), "odd number of hex digits in '0'");
+}
@@ -0,0 +1,71 @@
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::detect::{detect_config, ExpectedType, Outcome};
+
+// ============================================================================
+// Config Detection Tests
+// ============================================================================
+
+#[test]
+fn test_detect_fixed8_vec_u8() {
+    let encoded = KnownConfig::Fixed8.encode(&vec![1u8, 2, 3, 4, 5]).unwrap();
+    let candidates = detect_config::<Vec<u8>>(&encoded);
+
+    assert_eq!(candidates.len(), KnownConfig::ALL.len());
+    assert_eq!(candidates[0].config, KnownConfig::Fixed8);
+    assert!(candidates[0].is_clean());
+    assert_eq!(candidates.iter().filter(|c| c.is_clean()).count(), 1);
+}
+
+#[test]
+fn test_detect_fixed8_big_endian_string() {
+    let encoded = KnownConfig::Fixed8BigEndian.encode(&"Hello, World!".to_string()).unwrap();
+    let candidates = detect_config::<String>(&encoded);
+
+    assert_eq!(candidates[0].config, KnownConfig::Fixed8BigEndian);
+    assert!(candidates[0].is_clean());
+    // Little-endian fixed8 reads a length of 13 << 56 and fails
+    let fixed8 = candidates.iter().find(|c| c.config == KnownConfig::Fixed8).unwrap();
+    assert!(matches!(fixed8.outcome, Outcome::Failed(_)));
+}
+
+#[test]
+fn test_detect_variable_short_payload_is_ambiguous() {
+    // Below the 251 threshold varint lengths are a single byte, so the
+    // byte order cannot be told apart
+    let encoded = KnownConfig::Variable.encode(&vec![42u8]).unwrap();
+    let candidates = detect_config::<Vec<u8>>(&encoded);
+
+    let clean: Vec<KnownConfig> = candidates.iter().filter(|c| c.is_clean()).map(|c| c.config).collect();
+    assert_eq!(clean, [KnownConfig::Variable, KnownConfig::VariableBigEndian]);
+}
+
+#[test]
+fn test_detect_variable_marker_payload() {
+    let encoded = KnownConfig::Variable.encode(&vec![7u8; 300]).unwrap();
+    let candidates = ExpectedType::Bytes.detect(&encoded);
+
+    assert_eq!(candidates[0].config, KnownConfig::Variable);
+    assert!(candidates[0].is_clean());
+    assert!(!candidates[1].is_clean());
+}
+
+#[test]
+fn test_detect_ranks_trailing_bytes_after_clean() {
+    let mut encoded = KnownConfig::Variable.encode(&vec![1u8, 2, 3]).unwrap();
+    encoded.push(0xAA);
+    let candidates = detect_config::<Vec<u8>>(&encoded);
+
+    assert!(candidates.iter().all(|c| !c.is_clean()));
+    assert_eq!(candidates[0].config, KnownConfig::Variable);
+    assert!(matches!(candidates[0].outcome, Outcome::Trailing { bytes_read: 4 }));
+}
+
+#[test]
+fn test_expected_type_names() {
+    assert_eq!(ExpectedType::from_name("Vec<u8>"), Some(ExpectedType::Bytes));
+    assert_eq!(ExpectedType::from_name("String"), Some(ExpectedType::String));
+    assert_eq!(ExpectedType::from_name("u32"), None);
+    for config in KnownConfig::ALL {
+        assert_eq!(KnownConfig::from_name(config.name()), Some(config));
+    }
+}
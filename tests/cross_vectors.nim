@@ -0,0 +1,239 @@
+# Generated by `bincode_tool nim-vectors` from src/vectors.rs. Do not edit.
+
+{.push raises: [], gcsafe.}
+
+import bincode_config
+
+type
+  PayloadKind* = enum
+    pkLiteral
+    pkFill
+    pkSeeded
+
+  CrossVector* = object
+    description*: string
+    length*: int
+    hash*: uint64 ## FNV-1a (64-bit) of the payload
+    case kind*: PayloadKind
+    of pkLiteral:
+      bytes*: seq[byte]
+    of pkFill:
+      fill*: byte
+    of pkSeeded:
+      seed*: uint64
+
+  CrossConfig* = enum
+    ccVariable = "variable"
+    ccFixed8 = "fixed8"
+
+const CrossSizeLimit* = 4294967305'u64
+
+const CrossVectors*: array[12, CrossVector] = [
+  CrossVector(
+    description: "[1, 2, 3, 4, 5]",
+    length: 5,
+    hash: 0x0f66dcbf4f6b7d88'u64,
+    kind: pkLiteral,
+    bytes: @[
+      1'u8, 2'u8, 3'u8, 4'u8, 5'u8,
+    ],
+  ),
+  CrossVector(
+    description: "empty",
+    length: 0,
+    hash: 0xcbf29ce484222325'u64,
+    kind: pkLiteral,
+    bytes: @[],
+  ),
+  CrossVector(
+    description: "[0, 255, 128, 64]",
+    length: 4,
+    hash: 0xd865f17bf62913a0'u64,
+    kind: pkLiteral,
+    bytes: @[
+      0'u8, 255'u8, 128'u8, 64'u8,
+    ],
+  ),
+  CrossVector(
+    description: "\"Hello, World!\" bytes",
+    length: 13,
+    hash: 0x6ef05bd7cc857c54'u64,
+    kind: pkLiteral,
+    bytes: @[
+      72'u8, 101'u8, 108'u8, 108'u8, 111'u8, 44'u8, 32'u8, 87'u8, 111'u8, 114'u8, 108'u8, 100'u8,
+      33'u8,
+    ],
+  ),
+  CrossVector(
+    description: "[42]",
+    length: 1,
+    hash: 0xaf63a74c8601927d'u64,
+    kind: pkLiteral,
+    bytes: @[
+      42'u8,
+    ],
+  ),
+  CrossVector(
+    description: "\"Test with émojis 🚀\" bytes",
+    length: 22,
+    hash: 0xa3ba129b1e3c5e78'u64,
+    kind: pkLiteral,
+    bytes: @[
+      84'u8, 101'u8, 115'u8, 116'u8, 32'u8, 119'u8, 105'u8, 116'u8, 104'u8, 32'u8, 195'u8, 169'u8,
+      109'u8, 111'u8, 106'u8, 105'u8, 115'u8, 32'u8, 240'u8, 159'u8, 154'u8, 128'u8,
+    ],
+  ),
+  CrossVector(
+    description: "100 x 0x01",
+    length: 100,
+    hash: 0x55d53d2a6c2c3e79'u64,
+    kind: pkFill,
+    fill: 1'u8,
+  ),
+  CrossVector(
+    description: "20 KiB seeded bytes",
+    length: 20480,
+    hash: 0x31556a4800242bb0'u64,
+    kind: pkSeeded,
+    seed: 8'u64,
+  ),
+  CrossVector(
+    description: "250 seeded bytes",
+    length: 250,
+    hash: 0x7b7fdae78a72ae4c'u64,
+    kind: pkSeeded,
+    seed: 9'u64,
+  ),
+  CrossVector(
+    description: "251 seeded bytes",
+    length: 251,
+    hash: 0x2c9ec8cb26fcdcd7'u64,
+    kind: pkSeeded,
+    seed: 10'u64,
+  ),
+  CrossVector(
+    description: "65535 seeded bytes",
+    length: 65535,
+    hash: 0xa18e383903797aeb'u64,
+    kind: pkSeeded,
+    seed: 11'u64,
+  ),
+  CrossVector(
+    description: "65536 seeded bytes",
+    length: 65536,
+    hash: 0x0ff6dc911b85f4df'u64,
+    kind: pkSeeded,
+    seed: 12'u64,
+  ),
+]
+
+# Files the Rust tests write, per config
+const RustFiles*: array[CrossConfig, array[12, string]] = [
+  ccVariable: [
+    "rust_var_001.bin",
+    "rust_var_002.bin",
+    "rust_var_003.bin",
+    "rust_var_004.bin",
+    "rust_var_005.bin",
+    "rust_var_006.bin",
+    "rust_var_007.bin",
+    "rust_var_008.bin",
+    "rust_var_009.bin",
+    "rust_var_010.bin",
+    "rust_var_011.bin",
+    "rust_var_012.bin",
+  ],
+  ccFixed8: [
+    "rust_fixed8_001.bin",
+    "rust_fixed8_002.bin",
+    "rust_fixed8_003.bin",
+    "rust_fixed8_004.bin",
+    "rust_fixed8_005.bin",
+    "rust_fixed8_006.bin",
+    "rust_fixed8_007.bin",
+    "rust_fixed8_008.bin",
+    "rust_fixed8_009.bin",
+    "rust_fixed8_010.bin",
+    "rust_fixed8_011.bin",
+    "rust_fixed8_012.bin",
+  ],
+]
+
+# Files the Nim tests write, per config
+const NimFiles*: array[CrossConfig, array[12, string]] = [
+  ccVariable: [
+    "nim_var_001.bin",
+    "nim_var_002.bin",
+    "nim_var_003.bin",
+    "nim_var_004.bin",
+    "nim_var_005.bin",
+    "nim_var_006.bin",
+    "nim_var_007.bin",
+    "nim_var_008.bin",
+    "nim_var_009.bin",
+    "nim_var_010.bin",
+    "nim_var_011.bin",
+    "nim_var_012.bin",
+  ],
+  ccFixed8: [
+    "nim_fixed8_001.bin",
+    "nim_fixed8_002.bin",
+    "nim_fixed8_003.bin",
+    "nim_fixed8_004.bin",
+    "nim_fixed8_005.bin",
+    "nim_fixed8_006.bin",
+    "nim_fixed8_007.bin",
+    "nim_fixed8_008.bin",
+    "nim_fixed8_009.bin",
+    "nim_fixed8_010.bin",
+    "nim_fixed8_011.bin",
+    "nim_fixed8_012.bin",
+  ],
+]
+
+func bincodeConfig*(config: CrossConfig): BincodeConfig =
+  ## The `BincodeConfig` the Rust tests use for `config`
+  case config
+  of ccVariable:
+    standard().withVariableIntEncoding().withLimit(CrossSizeLimit)
+  of ccFixed8:
+    standard().withFixedIntEncoding(8).withLimit(CrossSizeLimit)
+
+func fnv1a64*(data: openArray[byte]): uint64 =
+  ## FNV-1a (64-bit) hash, as computed by the Rust generator
+  result = 0xcbf29ce484222325'u64
+  for b in data:
+    result = (result xor uint64(b)) * 0x100000001b3'u64
+
+func splitmix64*(state: var uint64): uint64 =
+  ## Next output of the SplitMix64 generator, advancing `state`
+  state += 0x9e3779b97f4a7c15'u64
+  var z = state
+  z = (z xor (z shr 30)) * 0xbf58476d1ce4e5b9'u64
+  z = (z xor (z shr 27)) * 0x94d049bb133111eb'u64
+  z xor (z shr 31)
+
+func seededBytes*(seed: uint64, length: int): seq[byte] =
+  ## `length` bytes of SplitMix64 output seeded with `seed`, each word little-endian
+  result = newSeq[byte](length)
+  var state = seed
+  var word = 0'u64
+  for i in 0 ..< length:
+    if i mod 8 == 0:
+      word = splitmix64(state)
+    result[i] = byte((word shr (8 * (i mod 8))) and 0xff)
+
+func payload*(vector: CrossVector): seq[byte] =
+  ## The bytes described by `vector`
+  case vector.kind
+  of pkLiteral:
+    vector.bytes
+  of pkFill:
+    var data = newSeq[byte](vector.length)
+    for i in 0 ..< data.len:
+      data[i] = vector.fill
+    data
+  of pkSeeded:
+    seededBytes(vector.seed, vector.length)
+
+{.pop.}
@@ -0,0 +1,156 @@
+use bincode::error::DecodeError;
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::depth::{
+    decode_with_max_depth, depth_vectors, render_tsv, Chain, DepthError, DepthType, DepthVector, Tree,
+    DEFAULT_MAX_DEPTH, DEPTH_FILE,
+};
+use bincode_wrapper::schema::{BincodeSchema, MAX_REF_DEPTH};
+use bincode_wrapper::test_data;
+use bincode_wrapper::transcode::{bincode_to_json, json_to_bincode};
+use bincode_wrapper::value::{decode_value, encode_value};
+
+/// `depth` nodes, each with the deeper node on the left and a leaf on the
+/// right. Encoding and dropping it recurse once per level, so keep `depth`
+/// to a few hundred.
+fn nested_tree(depth: usize) -> Tree {
+    (0..depth).fold(Tree::Leaf, |tree, _| Tree::Node(Box::new(tree), Box::new(Tree::Leaf)))
+}
+
+/// `depth` nested `Some`s around a `None`; the same depth bound applies
+fn nested_chain(depth: usize) -> Chain {
+    (0..depth).fold(Chain(None), |chain, _| Chain(Some(Box::new(chain))))
+}
+
+// ============================================================================
+// Vector Format Tests
+// ============================================================================
+
+#[test]
+fn test_vector_bytes_match_derived_encoding() {
+    for config in [KnownConfig::Variable, KnownConfig::Fixed8] {
+        for depth in [0, 1, 2, 100] {
+            let tree = DepthVector { depth_type: DepthType::Tree, config, depth };
+            assert_eq!(tree.bytes().unwrap(), config.encode(&nested_tree(depth)).unwrap(), "{}", tree.file());
+            let chain = DepthVector { depth_type: DepthType::Chain, config, depth };
+            assert_eq!(chain.bytes().unwrap(), config.encode(&nested_chain(depth)).unwrap(), "{}", chain.file());
+        }
+    }
+    // Node(Leaf, Leaf) under varint, and the same tags as 4-byte u32s under fixed8
+    let tree = DepthVector { depth_type: DepthType::Tree, config: KnownConfig::Variable, depth: 1 };
+    assert_eq!(tree.bytes().unwrap(), [1, 0, 0]);
+    let tree = DepthVector { config: KnownConfig::Fixed8, ..tree };
+    assert_eq!(tree.bytes().unwrap(), [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_deep_vectors_stay_small() {
+    let vectors = depth_vectors();
+    let deepest = vectors.iter().map(|vector| vector.bytes().unwrap().len()).max().unwrap();
+    // 100k nodes and 100k + 1 leaves, 4 bytes each under fixed8
+    assert_eq!(deepest, 4 * 200_001);
+}
+
+// ============================================================================
+// Depth Limit Tests
+// ============================================================================
+
+#[test]
+fn test_depth_at_limit_decodes() {
+    for config in [KnownConfig::Variable, KnownConfig::Fixed8] {
+        let bytes = config.encode(&nested_tree(100)).unwrap();
+        let (tree, bytes_read) = decode_with_max_depth::<Tree>(&bytes, config, 100).unwrap();
+        assert_eq!((tree, bytes_read), (nested_tree(100), bytes.len()));
+        let bytes = config.encode(&nested_chain(100)).unwrap();
+        let (chain, _) = decode_with_max_depth::<Chain>(&bytes, config, 100).unwrap();
+        assert_eq!(chain, nested_chain(100));
+    }
+    // A leaf needs no depth at all
+    assert_eq!(decode_with_max_depth::<Tree>(&[0], KnownConfig::Variable, 0).unwrap().0, Tree::Leaf);
+}
+
+#[test]
+fn test_one_level_past_limit_fails() {
+    let bytes = KnownConfig::Variable.encode(&nested_chain(101)).unwrap();
+    match decode_with_max_depth::<Chain>(&bytes, KnownConfig::Variable, 100) {
+        Err(DepthError::DepthLimitExceeded { max_depth: 100 }) => {}
+        other => panic!("expected DepthLimitExceeded, got {:?}", other),
+    }
+    let err = decode_with_max_depth::<Tree>(&[1, 0, 0], KnownConfig::Variable, 0).unwrap_err();
+    assert_eq!((err.kind(), err.to_string()), ("DepthLimitExceeded".to_string(), "nesting depth exceeds 0".to_string()));
+}
+
+#[test]
+fn test_depth_bombs_rejected_without_stack_overflow() {
+    for vector in depth_vectors().into_iter().filter(|vector| vector.depth == 100_000) {
+        assert_eq!(vector.outcome(DEFAULT_MAX_DEPTH).to_string(), "err:DepthLimitExceeded", "{}", vector.file());
+    }
+}
+
+#[test]
+fn test_malformed_input_is_not_a_depth_error() {
+    // A bad tag and a truncated node keep their own error kinds
+    let err = decode_with_max_depth::<Tree>(&[1, 0, 2], KnownConfig::Variable, 10).unwrap_err();
+    assert_eq!(err.kind(), "UnexpectedVariant");
+    let err = decode_with_max_depth::<Chain>(&[1, 1], KnownConfig::Variable, 10).unwrap_err();
+    assert_eq!(err.kind(), "UnexpectedEnd");
+}
+
+// ============================================================================
+// Schema Tests
+// ============================================================================
+
+#[test]
+fn test_schema_walks_recursive_types() {
+    for config in [KnownConfig::Variable, KnownConfig::Fixed8] {
+        for (depth_type, schema) in [(DepthType::Tree, Tree::schema()), (DepthType::Chain, Chain::schema())] {
+            let bytes = DepthVector { depth_type, config, depth: MAX_REF_DEPTH }.bytes().unwrap();
+            let (value, bytes_read) = decode_value(&bytes, &schema, config).unwrap();
+            assert_eq!(bytes_read, bytes.len(), "{:?} {}", depth_type, config);
+            assert_eq!(encode_value(&value, &schema, config).unwrap(), bytes, "{:?} {}", depth_type, config);
+            let json = bincode_to_json(&bytes, &schema, config).unwrap();
+            assert_eq!(json_to_bincode(&json, &schema, config).unwrap(), bytes, "{:?} {}", depth_type, config);
+        }
+    }
+    let bytes = KnownConfig::Variable.encode(&nested_tree(1)).unwrap();
+    let json = bincode_to_json(&bytes, &Tree::schema(), KnownConfig::Variable).unwrap();
+    assert_eq!(json, serde_json::json!({"Node": {"0": "Leaf", "1": "Leaf"}}));
+    assert_eq!(json_to_bincode(&json, &Tree::schema(), KnownConfig::Variable).unwrap(), bytes);
+}
+
+#[test]
+fn test_schema_depth_bombs_rejected_without_stack_overflow() {
+    for vector in depth_vectors().into_iter().filter(|vector| vector.depth == 100_000) {
+        let schema = match vector.depth_type {
+            DepthType::Tree => Tree::schema(),
+            DepthType::Chain => Chain::schema(),
+        };
+        match decode_value(&vector.bytes().unwrap(), &schema, vector.config) {
+            Err(DecodeError::OtherString(message)) => assert_eq!(message, "nesting depth exceeds 64", "{}", vector.file()),
+            other => panic!("{}: expected a depth error, got {:?}", vector.file(), other.map(|(_, n)| n)),
+        }
+    }
+}
+
+// ============================================================================
+// Export Tests
+// ============================================================================
+
+#[test]
+fn test_manifest_records_limit_semantics() {
+    let tsv = render_tsv(&depth_vectors());
+    assert!(tsv.contains("Tree\tvariable\t1\t1\tdepth_tree_variable_1.bin\tok:0x"), "{}", tsv);
+    assert!(tsv.contains("Chain\tfixed8\t100\t1\tdepth_chain_fixed8_100.bin\terr:DepthLimitExceeded\n"));
+    assert!(tsv.contains("Chain\tfixed8\t100\t100\tdepth_chain_fixed8_100.bin\tok:0x"));
+    assert!(tsv.contains("Tree\tfixed8\t100000\t1024\tdepth_tree_fixed8_100000.bin\terr:DepthLimitExceeded\n"));
+}
+
+/// Exports the vectors and their outcomes for a Nim decoder of recursive types
+#[test]
+fn test_write_depth_vectors() {
+    let vectors = depth_vectors();
+    for vector in &vectors {
+        test_data::write_file(&vector.file(), &vector.bytes().unwrap()).unwrap();
+    }
+    let path = test_data::write_file(DEPTH_FILE, render_tsv(&vectors).as_bytes()).unwrap();
+    println!("Wrote {} depth vectors to {}", vectors.len(), path.display());
+}
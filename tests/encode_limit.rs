@@ -0,0 +1,74 @@
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::encode_limit::{
+    encode_checked, limit_outcome, limit_vectors, render_tsv, LimitedEncodeError, PayloadType, EDGE_LIMITS, LIMIT_FILE,
+};
+use bincode_wrapper::mutation::Outcome;
+use bincode_wrapper::runtime_config::RuntimeConfig;
+use bincode_wrapper::test_data;
+
+// ============================================================================
+// Encode Limit Tests
+// ============================================================================
+
+#[test]
+fn test_payload_at_limit_is_accepted() {
+    // The prefix is not counted: 10 bytes under a 10-byte limit write 18 bytes
+    let config = RuntimeConfig::standard().with_limit(10);
+    assert_eq!(encode_checked(&vec![1u8; 10], &config).unwrap().len(), 18);
+    assert_eq!(encode_checked("0123456789", &config).unwrap().len(), 18);
+    // RuntimeConfig::encode limits the bytes written instead
+    assert!(config.encode(&vec![1u8; 10]).is_err());
+}
+
+#[test]
+fn test_payload_over_limit_is_refused() {
+    let config = RuntimeConfig::standard().with_variable_int_encoding().with_limit(10);
+    match encode_checked(&vec![1u8; 11], &config) {
+        Err(LimitedEncodeError::SizeLimitExceeded { len: 11, limit: 10 }) => {}
+        other => panic!("expected SizeLimitExceeded, got {:?}", other),
+    }
+    // Multi-byte characters count as their UTF-8 bytes
+    assert!(matches!(encode_checked("ééééé€", &config), Err(LimitedEncodeError::SizeLimitExceeded { len: 13, .. })));
+    assert_eq!(
+        encode_checked(&[0u8; 1][..], &config.with_limit(0)).unwrap_err().to_string(),
+        "payload of 1 bytes exceeds the size limit of 0 bytes"
+    );
+}
+
+#[test]
+fn test_accepted_encodings_match_plain_bincode() {
+    let payload = vec![b'a'; 251];
+    for config in [KnownConfig::Variable, KnownConfig::Fixed8BigEndian] {
+        let runtime = RuntimeConfig::from(config).with_limit(251);
+        assert_eq!(encode_checked(&payload, &runtime).unwrap(), config.encode(&payload).unwrap());
+    }
+    assert_eq!(
+        limit_outcome(KnownConfig::Fixed8, PayloadType::String, 250, 251),
+        Outcome::Rejected { kind: "SizeLimitExceeded".to_string() }
+    );
+}
+
+// ============================================================================
+// Vector Tests
+// ============================================================================
+
+#[test]
+fn test_limit_vectors_cover_both_sides_of_each_limit() {
+    let vectors = limit_vectors();
+    for limit in EDGE_LIMITS {
+        let at_limit = vectors.iter().filter(|vector| vector.limit == limit);
+        for vector in at_limit {
+            assert_eq!(vector.outcome.is_accepted(), vector.len <= limit, "{:?}", vector);
+        }
+    }
+    assert!(render_tsv(&vectors).contains("fixed8\tString\t1\t2\terr:SizeLimitExceeded\n"));
+}
+
+/// Step 1 of `make test-encode-limits`: the Nim side checks this file
+#[test]
+fn test_write_encode_limit_vectors() {
+    let vectors = limit_vectors();
+    let path = test_data::write_file(LIMIT_FILE, render_tsv(&vectors).as_bytes()).unwrap();
+    let accepted = vectors.iter().filter(|vector| vector.outcome.is_accepted()).count();
+    println!("Wrote {} encode-limit vectors ({} accepted) to {}", vectors.len(), accepted, path.display());
+}
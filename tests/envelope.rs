@@ -0,0 +1,128 @@
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::config_matrix;
+use bincode_wrapper::envelope::{
+    envelope_vectors, leb128_decode, leb128_encode, render_tsv, IntEnvelope, ENVELOPE_FILE, I32_VALUES, I64_VALUES,
+    U32_VALUES,
+};
+use bincode_wrapper::test_data;
+
+// ============================================================================
+// Envelope Format Tests
+// ============================================================================
+
+#[test]
+fn test_envelope_bytes() {
+    // Variable: length prefix + zigzag LEB128
+    assert_eq!(KnownConfig::Variable.encode(&IntEnvelope(-1i32)).unwrap(), [0x01, 0x01]);
+    assert_eq!(KnownConfig::Variable.encode(&IntEnvelope(300u32)).unwrap(), [0x02, 0xac, 0x02]);
+    assert_eq!(KnownConfig::Variable.encode(&IntEnvelope(i64::MIN)).unwrap()[0], 10);
+    // Fixed: 8-byte length + the value sign-extended to 64 bits
+    let mut expected = vec![8, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend([0xff; 8]);
+    assert_eq!(KnownConfig::Fixed8.encode(&IntEnvelope(-1i32)).unwrap(), expected);
+    assert_eq!(
+        KnownConfig::Fixed8BigEndian.encode(&IntEnvelope(1u32)).unwrap(),
+        [0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 1]
+    );
+}
+
+#[test]
+fn test_envelope_roundtrip_in_every_combination() {
+    let report = config_matrix!(|config, combo| {
+        for &value in I32_VALUES {
+            let encoded = bincode::encode_to_vec(IntEnvelope(value), config).map_err(|e| e.to_string())?;
+            let (decoded, _): (IntEnvelope<i32>, _) =
+                bincode::decode_from_slice(&encoded, config).map_err(|e| format!("{}: {}", value, e))?;
+            assert_eq!(decoded.0, value, "{}", combo);
+        }
+        for &value in U32_VALUES {
+            let encoded = bincode::encode_to_vec(IntEnvelope(value), config).map_err(|e| e.to_string())?;
+            let (decoded, _): (IntEnvelope<u32>, _) =
+                bincode::decode_from_slice(&encoded, config).map_err(|e| format!("{}: {}", value, e))?;
+            assert_eq!(decoded.0, value, "{}", combo);
+        }
+        for &value in I64_VALUES {
+            let encoded = bincode::encode_to_vec(IntEnvelope(value), config).map_err(|e| e.to_string())?;
+            let (decoded, _): (IntEnvelope<i64>, _) =
+                bincode::decode_from_slice(&encoded, config).map_err(|e| format!("{}: {}", value, e))?;
+            assert_eq!(decoded.0, value, "{}", combo);
+        }
+        Ok(())
+    });
+    report.assert_passed();
+}
+
+#[test]
+fn test_envelope_differs_from_bare_integer() {
+    for config in KnownConfig::ALL {
+        for &value in I32_VALUES {
+            assert_ne!(config.encode(&IntEnvelope(value)).unwrap(), config.encode(&value).unwrap(), "{} {}", config, value);
+        }
+        for &value in U32_VALUES {
+            assert_ne!(config.encode(&IntEnvelope(value)).unwrap(), config.encode(&value).unwrap(), "{} {}", config, value);
+        }
+        for &value in I64_VALUES {
+            assert_ne!(config.encode(&IntEnvelope(value)).unwrap(), config.encode(&value).unwrap(), "{} {}", config, value);
+        }
+    }
+    // Bare varint 300 uses bincode's 0xfb marker, the envelope LEB128
+    assert_eq!(KnownConfig::Variable.encode(&300u32).unwrap(), [0xfb, 0x2c, 0x01]);
+    assert_eq!(KnownConfig::Variable.encode(&IntEnvelope(300u32)).unwrap(), [0x02, 0xac, 0x02]);
+    // Bare fixed i32 is 4 bytes; the envelope is 8 + 8
+    assert_eq!(KnownConfig::Fixed8.encode(&-1i32).unwrap(), [0xff; 4]);
+    // Read as a bare integer, an envelope decodes to its length prefix
+    let envelope = KnownConfig::Variable.encode(&IntEnvelope(5u32)).unwrap();
+    assert_eq!(KnownConfig::Variable.decode::<u32>(&envelope).unwrap(), (1, 1));
+}
+
+#[test]
+fn test_strict_decode() {
+    // Payload of 7 bytes under fixed encoding
+    let short = KnownConfig::Fixed8.encode(&vec![0u8; 7]).unwrap();
+    assert!(KnownConfig::Fixed8.decode::<IntEnvelope<u32>>(&short).is_err());
+    // 2^32 does not fit a u32, i64::MIN does not fit an i32
+    let wide = KnownConfig::Fixed8.encode(&IntEnvelope(1i64 << 32)).unwrap();
+    assert!(KnownConfig::Fixed8.decode::<IntEnvelope<u32>>(&wide).is_err());
+    let wide = KnownConfig::Variable.encode(&IntEnvelope(i64::MIN)).unwrap();
+    assert!(KnownConfig::Variable.decode::<IntEnvelope<i32>>(&wide).is_err());
+}
+
+#[test]
+fn test_leb128() {
+    for value in [0, 1, 127, 128, 300, u64::from(u32::MAX), u64::MAX] {
+        assert_eq!(leb128_decode(&leb128_encode(value)), Some(value));
+    }
+    assert_eq!(leb128_encode(u64::MAX).len(), 10);
+    assert_eq!(leb128_decode(&[0x80, 0x00]), None, "overlong");
+    assert_eq!(leb128_decode(&[0x01, 0x01]), None, "trailing byte");
+    assert_eq!(leb128_decode(&[0x80]), None, "unterminated");
+    assert_eq!(leb128_decode(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]), None, "past 64 bits");
+}
+
+// ============================================================================
+// Cross-Verification Tests
+// ============================================================================
+
+/// Step 1 of `make test-int-envelopes`: the Nim side decodes these files
+#[test]
+fn test_write_envelope_vectors() {
+    let vectors = envelope_vectors();
+    for vector in &vectors {
+        test_data::write_file(&vector.rust_file(), &vector.encode().unwrap()).unwrap();
+    }
+    let path = test_data::write_file(ENVELOPE_FILE, render_tsv(&vectors).as_bytes()).unwrap();
+    println!("Wrote {} envelopes, listed in {}", vectors.len(), path.display());
+}
+
+/// Step 3 of `make test-int-envelopes`: decode what the Nim helpers wrote
+#[test]
+#[ignore = "reads files written by test_int_envelopes.nim; run by `make test-int-envelopes`"]
+fn test_nim_envelopes_decode() {
+    test_data::require_shared_dir("test_int_envelopes.nim");
+    for vector in envelope_vectors() {
+        let file = vector.nim_file();
+        let bytes = test_data::read_file(&file).unwrap_or_else(|e| panic!("{}: {}", file, e));
+        assert_eq!(vector.decode(&bytes).unwrap(), (vector.value, bytes.len()), "{}", file);
+        assert_eq!(bytes, vector.encode().unwrap(), "{} differs from the Rust envelope", file);
+    }
+}
@@ -0,0 +1,160 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::exhaustion::{
+    exhaustion_vectors, render_tsv, ExhaustionVector, Measured, CLAIMED_LENGTHS, EXHAUSTION_FILE, EXHAUSTION_TYPES,
+    TAIL_LEN,
+};
+use bincode_wrapper::golden::DECODE_LIMIT;
+use bincode_wrapper::test_data;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Peak allocation allowed while rejecting a vector
+const MAX_PEAK_BYTES: usize = 64 * 1024;
+
+/// Time allowed for rejecting a vector (generous for debug builds)
+const MAX_DECODE_TIME: Duration = Duration::from_millis(100);
+
+thread_local! {
+    static LIVE: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+fn track_alloc(size: usize) {
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + size);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+fn track_dealloc(size: usize) {
+    let _ = LIVE.try_with(|live| live.set(live.get().saturating_sub(size)));
+}
+
+/// System allocator that counts the bytes live on each thread
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            track_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        track_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            track_dealloc(layout.size());
+            track_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Run `f` and return its result with the peak bytes it had allocated at
+/// once on this thread, on top of what was already live
+fn measure_peak<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let start = LIVE.with(Cell::get);
+    PEAK.with(|peak| peak.set(start));
+    let result = f();
+    (result, PEAK.with(Cell::get) - start)
+}
+
+/// Decode `vector` while measuring time and peak allocation
+fn measure(vector: &ExhaustionVector) -> Measured {
+    let start = Instant::now();
+    let (outcome, peak_bytes) = measure_peak(|| vector.decode());
+    Measured { vector: vector.clone(), outcome, peak_bytes, elapsed: start.elapsed() }
+}
+
+// ============================================================================
+// Allocator Tests
+// ============================================================================
+
+#[test]
+fn test_counting_allocator_measures_peak() {
+    let (len, peak) = measure_peak(|| {
+        let big = vec![0u8; 1 << 20];
+        drop(vec![0u8; 1024]);
+        big.len()
+    });
+    assert_eq!(len, 1 << 20);
+    assert!((1 << 20..(1 << 20) + 4096).contains(&peak), "peak {}", peak);
+    // Within the decode limit, bincode allocates what the prefix claims
+    let bytes = KnownConfig::Fixed8.encode(&vec![7u8; 4096]).unwrap();
+    let (decoded, peak) = measure_peak(|| KnownConfig::Fixed8.decode_with_limit::<Vec<u8>, DECODE_LIMIT>(&bytes));
+    assert_eq!(decoded.unwrap().0.len(), 4096);
+    assert!(peak >= 4096, "peak {}", peak);
+}
+
+// ============================================================================
+// Exhaustion Vector Tests
+// ============================================================================
+
+#[test]
+fn test_vectors_are_tiny() {
+    let vectors = exhaustion_vectors();
+    assert_eq!(vectors.len(), EXHAUSTION_TYPES.len() * 2 * CLAIMED_LENGTHS.len());
+    for vector in &vectors {
+        assert!(vector.bytes.len() <= 9 + TAIL_LEN, "{:?}", vector);
+    }
+    // 2^40 under varint is the 0xfd marker and 8 little-endian bytes
+    let vec_u64 = vectors
+        .iter()
+        .find(|vector| vector.type_name == "Vec<u64>" && vector.config == KnownConfig::Variable && vector.claimed == 1 << 40)
+        .unwrap();
+    assert_eq!(vec_u64.bytes[..9], [0xfd, 0, 0, 0, 0, 0, 1, 0, 0]);
+}
+
+#[test]
+fn test_claimed_lengths_fail_fast_with_bounded_allocation() {
+    for vector in exhaustion_vectors() {
+        let Measured { outcome, peak_bytes, elapsed, .. } = measure(&vector);
+        let label = format!("{} {} claiming {}", vector.type_name, vector.config, vector.claimed);
+        assert_eq!(outcome.to_string(), "err:LimitExceeded", "{}", label);
+        assert!(peak_bytes <= MAX_PEAK_BYTES, "{}: peak {} bytes", label, peak_bytes);
+        assert!(elapsed <= MAX_DECODE_TIME, "{}: took {:?}", label, elapsed);
+    }
+}
+
+#[test]
+fn test_decode_value_runs_out_of_input_with_bounded_allocation() {
+    // No limit here: String and Vec<u8> are read byte by byte, not preallocated
+    for vector in exhaustion_vectors().iter().filter(|vector| ["String", "Vec<u8>"].contains(&vector.type_name)) {
+        let start = Instant::now();
+        let (outcome, peak_bytes) = measure_peak(|| vector.decode_value());
+        let label = format!("{} {} claiming {}", vector.type_name, vector.config, vector.claimed);
+        assert_eq!(outcome.to_string(), "err:UnexpectedEnd", "{}", label);
+        assert!(peak_bytes <= MAX_PEAK_BYTES, "{}: peak {} bytes", label, peak_bytes);
+        assert!(start.elapsed() <= MAX_DECODE_TIME, "{}: took {:?}", label, start.elapsed());
+    }
+}
+
+#[test]
+fn test_claim_within_limit_still_needs_the_data() {
+    // A claim the limit allows fails on the missing bytes instead
+    let mut bytes = KnownConfig::Variable.encode(&1000u64).unwrap();
+    bytes.extend_from_slice(&[0x01; TAIL_LEN]);
+    let result = KnownConfig::Variable.decode_with_limit::<Vec<u64>, DECODE_LIMIT>(&bytes);
+    assert!(result.unwrap_err().to_string().contains("UnexpectedEnd"));
+}
+
+/// Writes the measured vectors for a future Nim collection decoder
+#[test]
+fn test_write_exhaustion_vectors() {
+    let measured: Vec<Measured> = exhaustion_vectors().iter().map(measure).collect();
+    let path = test_data::write_file(EXHAUSTION_FILE, render_tsv(&measured).as_bytes()).unwrap();
+    let peak = measured.iter().map(|row| row.peak_bytes).max().unwrap_or(0);
+    println!("Wrote {} exhaustion vectors (peak {} bytes) to {}", measured.len(), peak, path.display());
+}
@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::Path;
+
+use bincode_wrapper::golden::{
+    check_corpus, locked_bincode_version, read_manifest, write_corpus, UPDATE_ENV,
+};
+
+const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden");
+
+fn current_bincode_version() -> String {
+    let lock = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.lock")).unwrap();
+    locked_bincode_version(&lock).expect("bincode missing from Cargo.lock")
+}
+
+// ============================================================================
+// Golden Corpus Tests
+// ============================================================================
+
+#[test]
+fn test_golden_corpus_matches_current_bincode() {
+    let dir = Path::new(GOLDEN_DIR);
+    let current = current_bincode_version();
+    if std::env::var(UPDATE_ENV).is_ok_and(|value| value == "1") {
+        write_corpus(dir, &current).unwrap();
+        return;
+    }
+
+    let recorded = read_manifest(dir)
+        .unwrap_or_else(|e| panic!("cannot read golden manifest: {} (run with {}=1 to record it)", e, UPDATE_ENV))
+        .bincode_version;
+    let problems = check_corpus(dir).unwrap();
+    assert!(
+        problems.is_empty(),
+        "golden corpus recorded with bincode {} differs from bincode {}; \
+         if the change is intended, re-record with {}=1\n\n{}",
+        recorded,
+        current,
+        UPDATE_ENV,
+        problems.join("\n")
+    );
+    if recorded != current {
+        eprintln!(
+            "note: golden corpus was recorded with bincode {}, bytes unchanged with {}",
+            recorded, current
+        );
+    }
+}
+
+#[test]
+fn test_locked_bincode_version() {
+    let lock = "[[package]]\nname = \"bincode\"\nversion = \"2.0.1\"\n\n[[package]]\nname = \"serde\"\n";
+    assert_eq!(locked_bincode_version(lock).as_deref(), Some("2.0.1"));
+    assert_eq!(locked_bincode_version("[[package]]\nname = \"serde\"\n"), None);
+}
+
+#[test]
+fn test_check_corpus_reports_drift() {
+    let dir = std::env::temp_dir().join(format!("golden_drift_{}", std::process::id()));
+    write_corpus(&dir, "0.0.0").unwrap();
+    assert!(check_corpus(&dir).unwrap().is_empty());
+
+    // Flip the length prefix of one file and drop another
+    let path = dir.join("fixed8/vec_u8_001.bin");
+    let mut bytes = fs::read(&path).unwrap();
+    bytes[0] ^= 0xff;
+    fs::write(&path, bytes).unwrap();
+    fs::remove_file(dir.join("variable/person.bin")).unwrap();
+
+    let problems = check_corpus(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(problems.len(), 2, "{:#?}", problems);
+    assert!(problems[0].starts_with("fixed8/vec_u8_001.bin (Vec<u8> [1, 2, 3, 4, 5]) changed:"));
+    assert!(problems[0].contains("first difference at offset 0x0: expected fa, found 05"));
+    assert!(problems[1].starts_with("variable/person.bin: "));
+}
@@ -0,0 +1,69 @@
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::length_prefix::{
+    build_table, encode_prefix, parse_table, sampled_lengths, write_table, EXHAUSTIVE_MAX, TABLE_CONFIGS, TABLE_FILE,
+};
+use bincode_wrapper::test_data;
+
+// ============================================================================
+// Table Contents Tests
+// ============================================================================
+
+#[test]
+fn test_varint_prefixes_at_marker_thresholds() {
+    let varint = |length| encode_prefix(length, KnownConfig::Variable).unwrap();
+    assert_eq!(varint(250), [250]);
+    assert_eq!(varint(251), [0xfb, 251, 0]);
+    assert_eq!(varint(65535), [0xfb, 0xff, 0xff]);
+    assert_eq!(varint(65536), [0xfc, 0, 0, 1, 0]);
+    assert_eq!(varint(1 << 32), [0xfd, 0, 0, 0, 0, 1, 0, 0, 0]);
+    assert_eq!(varint(u64::MAX), [0xfd, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+}
+
+#[test]
+fn test_fixed8_prefixes_follow_byte_order() {
+    assert_eq!(encode_prefix(0x0102, KnownConfig::Fixed8).unwrap(), [2, 1, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(encode_prefix(0x0102, KnownConfig::Fixed8BigEndian).unwrap(), [0, 0, 0, 0, 0, 0, 1, 2]);
+}
+
+#[test]
+fn test_sampled_lengths_reach_u64_max() {
+    let sampled = sampled_lengths();
+    assert!(sampled.iter().all(|&length| length > EXHAUSTIVE_MAX));
+    assert!(sampled.windows(2).all(|pair| pair[0] < pair[1]));
+    for length in [(1u64 << 32) - 1, 1 << 32, (1 << 32) + 1, u64::MAX] {
+        assert!(sampled.contains(&length), "{} not sampled", length);
+    }
+}
+
+#[test]
+fn test_every_prefix_decodes_back_to_its_length() {
+    let rows = build_table().unwrap();
+    assert_eq!(rows.len() as u64, EXHAUSTIVE_MAX + 1 + sampled_lengths().len() as u64);
+    for row in &rows {
+        for (config, prefix) in TABLE_CONFIGS.iter().zip(&row.prefixes) {
+            let (decoded, read): (u64, usize) = config.decode(prefix).unwrap();
+            assert_eq!((decoded, read), (row.length, prefix.len()), "{} with {}", row.length, config);
+        }
+    }
+}
+
+// ============================================================================
+// Table File Tests
+// ============================================================================
+
+#[test]
+fn test_table_roundtrips_and_rejects_truncation() {
+    let rows = build_table().unwrap();
+    let bytes = write_table(&rows);
+    assert_eq!(parse_table(&bytes).unwrap(), rows);
+    let e = parse_table(&bytes[..bytes.len() - 1]).unwrap_err();
+    assert!(e.contains("truncated"), "{}", e);
+}
+
+/// Step 1 of `make test-length-prefixes`: the Nim side reads this file
+#[test]
+fn test_write_length_prefix_table() {
+    let bytes = write_table(&build_table().unwrap());
+    let path = test_data::write_file(TABLE_FILE, &bytes).unwrap();
+    println!("Wrote {} bytes to {}", bytes.len(), path.display());
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+
+use bincode_wrapper::config_matrix;
+use bincode_wrapper::matrix::{Combination, Endian, IntEncoding, COMBINATIONS, NIM_DEFAULT_LIMIT};
+
+// ============================================================================
+// Driver Tests
+// ============================================================================
+
+#[test]
+fn test_matrix_runs_every_combination_in_order() {
+    let report = config_matrix!(|config, combo| {
+        let _ = config;
+        Ok(())
+    });
+    let combos: Vec<Combination> = report.results.iter().map(|(combo, _)| *combo).collect();
+    assert_eq!(combos, COMBINATIONS);
+    let names: HashSet<String> = COMBINATIONS.iter().map(|combo| combo.to_string()).collect();
+    assert_eq!(names.len(), COMBINATIONS.len(), "combination names must be unique");
+    report.assert_passed();
+}
+
+#[test]
+fn test_config_matches_combination() {
+    // 300 needs a 0xfb marker under varint and 4 bytes under fixed encoding
+    let report = config_matrix!(|config, combo| {
+        let encoded = bincode::encode_to_vec(300u32, config).map_err(|e| e.to_string())?;
+        let expected = match (combo.int_encoding, combo.endian) {
+            (IntEncoding::Variable, Endian::Little) => vec![0xfb, 0x2c, 0x01],
+            (IntEncoding::Variable, Endian::Big) => vec![0xfb, 0x01, 0x2c],
+            (IntEncoding::Fixed, Endian::Little) => vec![0x2c, 0x01, 0, 0],
+            (IntEncoding::Fixed, Endian::Big) => vec![0, 0, 0x01, 0x2c],
+        };
+        if encoded != expected {
+            return Err(format!("encoded {:02x?}, expected {:02x?}", encoded, expected));
+        }
+        Ok(())
+    });
+    report.assert_passed();
+}
+
+#[test]
+fn test_limit_is_applied() {
+    // Fits every limit, then one that needs more than Nim's default limit
+    for len in [NIM_DEFAULT_LIMIT - 16, NIM_DEFAULT_LIMIT] {
+        let data = vec![0u8; len];
+        let report = config_matrix!(|config, combo| {
+            let encoded = bincode::encode_to_vec(&data, config).map_err(|e| e.to_string())?;
+            let decoded: Result<(Vec<u8>, usize), _> = bincode::decode_from_slice(&encoded, config);
+            match (combo.allows(encoded.len()), decoded) {
+                (true, Ok(_)) | (false, Err(_)) => Ok(()),
+                (_, Ok(_)) => Err(format!("decoded {} bytes past the limit", encoded.len())),
+                (_, Err(e)) => Err(e.to_string()),
+            }
+        });
+        report.assert_passed();
+    }
+}
+
+#[test]
+fn test_failures_are_reported_per_combination() {
+    let report = config_matrix!(|config, combo| {
+        let _ = config;
+        assert_ne!(combo.endian, Endian::Big, "big-endian");
+        if combo.limit.is_none() {
+            return Err("no limit".to_string());
+        }
+        Ok(())
+    });
+    let failures = report.failures();
+    // 6 big-endian panics plus the 2 unlimited little-endian combinations
+    assert_eq!(failures.len(), 8);
+    assert!(failures.iter().any(|(combo, e)| combo.to_string() == "be/fixed/limit=65536" && e.contains("big-endian")));
+    assert!(failures.contains(&(COMBINATIONS[0], "no limit")));
+    assert!(report.to_string().contains("  ok   le/varint/limit=65536\n"));
+}
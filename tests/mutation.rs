@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::golden::golden_cases;
+use bincode_wrapper::mutation::{decode_outcome, mutation_corpus, mutations, render_tsv, Mutation, Outcome, CORPUS_FILE};
+use bincode_wrapper::test_data;
+
+const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden");
+
+// ============================================================================
+// Mutation Tests
+// ============================================================================
+
+#[test]
+fn test_mutation_labels_roundtrip() {
+    for mutation in [
+        Mutation::FlipBit { offset: 3, bit: 7 },
+        Mutation::Truncate { len: 0 },
+        Mutation::Insert { offset: 1, byte: 0xfb },
+    ] {
+        assert_eq!(Mutation::from_label(&mutation.to_string()), Some(mutation));
+    }
+    assert_eq!(Mutation::from_label("flip:0:8"), None);
+    assert_eq!(Mutation::from_label("swap:0"), None);
+}
+
+#[test]
+fn test_mutations_of_small_varint_vector() {
+    let bytes = [5, 1, 2, 3, 4, 5];
+    let all = mutations(&bytes, KnownConfig::Variable);
+    // 8 prefix bit flips, 6 truncations, 3 bytes at offsets 0 and 1
+    assert_eq!(all.len(), 8 + 6 + 6);
+    assert_eq!(Mutation::FlipBit { offset: 0, bit: 0 }.apply(&bytes), [4, 1, 2, 3, 4, 5]);
+    assert_eq!(Mutation::Insert { offset: 1, byte: 0xff }.apply(&bytes), [5, 0xff, 1, 2, 3, 4, 5]);
+    assert_eq!(Mutation::Truncate { len: 2 }.apply(&bytes), [5, 1]);
+}
+
+#[test]
+fn test_fixed8_flips_cover_the_whole_prefix() {
+    let bytes = KnownConfig::Fixed8.encode(&vec![7u8; 3]).unwrap();
+    let flips = mutations(&bytes, KnownConfig::Fixed8)
+        .into_iter()
+        .filter(|mutation| matches!(mutation, Mutation::FlipBit { .. }))
+        .count();
+    assert_eq!(flips, 64);
+}
+
+#[test]
+fn test_decode_outcome_labels() {
+    let cases = golden_cases();
+    let case = cases.iter().find(|case| case.name == "vec_u8_001").unwrap();
+    let bytes = case.encode(KnownConfig::Variable).unwrap();
+    assert!(decode_outcome(case, &bytes, KnownConfig::Variable).is_accepted());
+
+    let shorter = Mutation::FlipBit { offset: 0, bit: 0 }.apply(&bytes);
+    assert_eq!(
+        decode_outcome(case, &shorter, KnownConfig::Variable),
+        Outcome::Rejected { kind: "TrailingBytes".to_string() }
+    );
+    let truncated = Mutation::Truncate { len: 3 }.apply(&bytes);
+    assert_eq!(decode_outcome(case, &truncated, KnownConfig::Variable).to_string(), "err:UnexpectedEnd");
+}
+
+// ============================================================================
+// Corpus Tests
+// ============================================================================
+
+#[test]
+fn test_corpus_labels_thousands_of_mutants() {
+    let corpus = mutation_corpus(Path::new(GOLDEN_DIR)).unwrap();
+    assert!(corpus.len() > 5000, "only {} mutants", corpus.len());
+    // Dropping bytes from a valid encoding never leaves a valid one
+    assert!(corpus
+        .iter()
+        .filter(|mutant| matches!(mutant.mutation, Mutation::Truncate { .. }))
+        .all(|mutant| !mutant.outcome.is_accepted()));
+    // Most bit flips in a bare integer give another valid integer
+    assert!(corpus.iter().any(|mutant| mutant.outcome.is_accepted()));
+}
+
+/// Step 1 of `make test-mutations`: the Nim side replays this file
+#[test]
+fn test_write_mutation_corpus() {
+    let corpus = mutation_corpus(Path::new(GOLDEN_DIR)).unwrap();
+    let path = test_data::write_file(CORPUS_FILE, render_tsv(&corpus).as_bytes()).unwrap();
+    let accepted = corpus.iter().filter(|mutant| mutant.outcome.is_accepted()).count();
+    println!("Wrote {} mutants ({} accepted) to {}", corpus.len(), accepted, path.display());
+}
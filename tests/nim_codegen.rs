@@ -0,0 +1,111 @@
+use std::fs;
+use std::num::NonZeroU32;
+use std::ops::Range;
+
+use bincode::{Decode, Encode};
+use bincode_wrapper::codegen::nim::generate_nim_module;
+use bincode_wrapper::reference::Person;
+use bincode_wrapper::schema::{BincodeSchema, Schema};
+
+#[derive(Encode, Decode, BincodeSchema)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { width: u16, height: u16 },
+}
+
+#[derive(Encode, Decode, BincodeSchema)]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(Encode, Decode, BincodeSchema)]
+struct Scene {
+    r#type: Color,
+    shapes: Vec<Shape>,
+    label: Option<String>,
+    payload: Vec<u8>,
+    origin: [i32; 2],
+}
+
+// ============================================================================
+// Generated Module Tests
+// ============================================================================
+
+#[test]
+fn test_person_module_uses_nim_bincode_primitives() {
+    let module = generate_nim_module(&Person::schema()).unwrap();
+
+    assert!(module.contains("  Person* = object\n    name*: string\n    age*: uint32\n    email*: string\n"));
+    assert!(module.contains("  serializeString(stream, value.name, config)\n  encodeUint(stream, value.age, config)\n"));
+    assert!(module.contains("  result.age = decodeUint[uint32](data, pos, config)\n"));
+    assert!(module.contains("proc serializePerson*("));
+    assert!(module.contains("func deserializePerson*("));
+    assert!(module.contains("checkNoTrailingBytes(data.len, pos, 0)"));
+}
+
+#[test]
+fn test_nested_types_are_declared_before_use() {
+    let module = generate_nim_module(&Scene::schema()).unwrap();
+
+    let color = module.find("  Color* {.pure.} = enum").unwrap();
+    let shape = module.find("  Shape* = object").unwrap();
+    let scene = module.find("  Scene* = object").unwrap();
+    assert!(color < scene && shape < scene);
+    assert!(module.contains("import std/[options]"));
+    // Rust keywords come through unraw, Nim keywords get backticks
+    assert!(module.contains("    `type`*: Color\n"));
+    assert!(module.contains("    origin*: array[2, int32]\n"));
+    assert!(module.contains("  serialize(stream, value.payload, config)\n"));
+}
+
+#[test]
+fn test_enum_with_data_becomes_object_variant() {
+    let module = generate_nim_module(&Shape::schema()).unwrap();
+
+    assert!(module.contains("  ShapeKind* {.pure.} = enum\n    Empty = 0\n    Circle = 1\n    Rect = 2\n"));
+    assert!(module.contains("    case kind*: ShapeKind\n"));
+    assert!(module.contains("      circle0*: float64\n"));
+    assert!(module.contains("      rectWidth*: uint16\n"));
+    assert!(module.contains("  encodeUint(stream, value.kind.ord.uint32, config)\n"));
+    assert!(module.contains("  of 2'u32:\n    result = Shape(kind: ShapeKind.Rect)\n"));
+}
+
+#[test]
+fn test_non_zero_fields_check_for_zero() {
+    let module = generate_nim_module(&Range::<NonZeroU32>::schema()).unwrap();
+    assert!(module.contains("    start*: uint32\n"));
+    assert!(module.contains("  if value.start == 0:\n    raise newException(BincodeError, \"NonZero value is zero\")\n  encodeUint(stream, value.start, config)\n"));
+    assert!(module.contains("  result.start = decodeUint[uint32](data, pos, config)\n  if result.start == 0:\n"));
+}
+
+#[test]
+fn test_unsupported_schemas_are_rejected() {
+    assert!(generate_nim_module(&Vec::<u8>::schema()).is_err());
+    assert!(generate_nim_module(&<(u128,)>::schema()).is_err());
+
+    let conflicting = Schema::Tuple {
+        items: vec![
+            Schema::Struct { name: "A".to_string(), fields: vec![] },
+            Schema::Struct {
+                name: "A".to_string(),
+                fields: vec![bincode_wrapper::schema::Field { name: "x".to_string(), schema: Schema::U8 }],
+            },
+        ],
+    };
+    let Err(e) = generate_nim_module(&Schema::Struct {
+        name: "Outer".to_string(),
+        fields: vec![bincode_wrapper::schema::Field { name: "pair".to_string(), schema: conflicting }],
+    }) else {
+        panic!("conflicting type names should be rejected");
+    };
+    assert!(e.to_string().contains("'A'"));
+}
+
+#[test]
+fn test_committed_person_codec_is_up_to_date() {
+    let committed = fs::read_to_string("bincode/examples/person_codec.nim").unwrap();
+    let generated = generate_nim_module(&Person::schema()).unwrap();
+    assert_eq!(committed, generated, "person_codec.nim is stale, run `make nim-codegen`");
+}
@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+
+use bincode::error::DecodeError;
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::mutation::error_kind;
+use bincode_wrapper::nim_native::{Color, NimChar, NimCharSet, NimOrderedTable, NimRange, NimSet64, UserId};
+
+type Percent = NimRange<-10, 1000>;
+
+// ============================================================================
+// Layout Tests
+// ============================================================================
+
+#[test]
+fn test_set64_is_a_u64_bitmask() {
+    let set = NimSet64::from_elements(&[0, 1, 7, 63]).unwrap();
+    let mask = 1 | 2 | 0x80 | 1 << 63;
+    assert_eq!(set, NimSet64(mask));
+    assert_eq!(set.elements(), [0, 1, 7, 63]);
+    for config in KnownConfig::ALL {
+        assert_eq!(config.encode(&set).unwrap(), config.encode(&mask).unwrap(), "{}", config);
+    }
+    assert_eq!(KnownConfig::Fixed8.encode(&set).unwrap(), mask.to_le_bytes());
+    // The empty set is a single zero byte under varint
+    assert_eq!(KnownConfig::Variable.encode(&NimSet64::default()).unwrap(), [0]);
+    assert_eq!(NimSet64::from_elements(&[64]), None);
+}
+
+#[test]
+fn test_char_set_is_32_bitmask_bytes() {
+    let set = NimCharSet::from_elements(&[0, b'A', b'z', 0xff]);
+    let mut expected = [0u8; 32];
+    expected[0] = 0x01; // '\0'
+    expected[8] = 0x02; // 'A' = 65
+    expected[15] = 0x04; // 'z' = 122
+    expected[31] = 0x80; // '\xff'
+    for config in KnownConfig::ALL {
+        assert_eq!(config.encode(&set).unwrap(), expected, "{}", config);
+    }
+    assert!(set.contains(b'A') && !set.contains(b'B'));
+}
+
+#[test]
+fn test_range_and_distinct_use_base_layout() {
+    for config in KnownConfig::ALL {
+        assert_eq!(config.encode(&Percent::new(-10).unwrap()).unwrap(), config.encode(&-10i64).unwrap(), "{}", config);
+        assert_eq!(config.encode(&UserId(300)).unwrap(), config.encode(&300u64).unwrap(), "{}", config);
+    }
+    // Zigzag 19 under varint, 8 bytes under fixed8
+    assert_eq!(KnownConfig::Variable.encode(&Percent::new(-10).unwrap()).unwrap(), [19]);
+    assert_eq!(KnownConfig::Fixed8.encode(&UserId(300)).unwrap(), 300u64.to_le_bytes());
+    assert_eq!(Percent::new(1001), None);
+}
+
+#[test]
+fn test_enum_with_holes_writes_ordinal() {
+    // Blue is the third variant but ordinal 10; a derived enum would write 2
+    assert_eq!(KnownConfig::Variable.encode(&Color::Blue).unwrap(), [10]);
+    assert_eq!(KnownConfig::Fixed8.encode(&Color::Green).unwrap(), 5u32.to_le_bytes());
+    for ordinal in Color::ORDINALS {
+        let color = Color::from_ordinal(*ordinal).unwrap();
+        assert_eq!(KnownConfig::Variable.decode::<Color>(&[*ordinal as u8]).unwrap().0, color);
+    }
+}
+
+#[test]
+fn test_ordered_table_keeps_insertion_order() {
+    let mut table = NimOrderedTable::default();
+    table.insert("zeta".to_string(), 1u32);
+    table.insert("alpha".to_string(), 2);
+    table.insert("zeta".to_string(), 3);
+    assert_eq!(table.0, [("zeta".to_string(), 3), ("alpha".to_string(), 2)]);
+    assert_eq!(table.get(&"alpha".to_string()), Some(&2));
+    for config in KnownConfig::ALL {
+        let bytes = config.encode(&table).unwrap();
+        assert_eq!(bytes, config.encode(&table.0).unwrap(), "{}", config);
+        // Same length-and-pairs layout as a map, but a BTreeMap sorts the keys
+        let sorted: BTreeMap<String, u32> = table.0.iter().cloned().collect();
+        let mut reordered = table.0.clone();
+        reordered.reverse();
+        assert_eq!(config.encode(&sorted).unwrap(), config.encode(&reordered).unwrap(), "{}", config);
+        assert_eq!(config.decode::<NimOrderedTable<String, u32>>(&bytes).unwrap().0, table);
+    }
+}
+
+#[test]
+fn test_nim_char_is_one_raw_byte() {
+    for config in KnownConfig::ALL {
+        assert_eq!(config.encode(&NimChar(0xe9)).unwrap(), [0xe9], "{}", config);
+    }
+    // A Rust char is UTF-8: 'é' (U+00E9) takes two bytes
+    assert_eq!(KnownConfig::Variable.encode(&'é').unwrap(), [0xc3, 0xa9]);
+}
+
+// ============================================================================
+// Rejection Tests
+// ============================================================================
+
+#[test]
+fn test_range_rejects_out_of_bounds() {
+    for config in KnownConfig::ALL {
+        for value in [-11i64, 1001, i64::MIN, i64::MAX] {
+            let bytes = config.encode(&value).unwrap();
+            match config.decode::<Percent>(&bytes) {
+                Err(DecodeError::OtherString(message)) => {
+                    assert_eq!(message, format!("{} is outside range[-10..1000]", value), "{}", config)
+                }
+                other => panic!("expected an out-of-range error, got {:?}", other),
+            }
+        }
+        let bytes = config.encode(&1000i64).unwrap();
+        assert_eq!(config.decode::<Percent>(&bytes).unwrap().0, Percent::new(1000).unwrap());
+    }
+}
+
+#[test]
+fn test_enum_hole_is_rejected() {
+    for config in KnownConfig::ALL {
+        for hole in [0u32, 2, 3, 4, 6, 9, 11, u32::MAX] {
+            let bytes = config.encode(&hole).unwrap();
+            let err = config.decode::<Color>(&bytes).unwrap_err();
+            assert_eq!(error_kind(&err), "UnexpectedVariant", "{} {}", hole, config);
+        }
+    }
+}
+
+#[test]
+fn test_ordered_table_rejects_duplicate_keys() {
+    let entries = vec![(1u32, 10u32), (2, 20), (1, 30)];
+    for config in KnownConfig::ALL {
+        let bytes = config.encode(&entries).unwrap();
+        match config.decode::<NimOrderedTable<u32, u32>>(&bytes) {
+            Err(DecodeError::OtherString(message)) => assert_eq!(message, "duplicate key at entry 2", "{}", config),
+            other => panic!("expected a duplicate-key error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_ordered_table_decodes_many_keys() {
+    // The duplicate check is linear, so a large table stays fast
+    let entries: Vec<(u32, u32)> = (0..100_000).map(|k| (k, k)).collect();
+    let bytes = KnownConfig::Variable.encode(&entries).unwrap();
+    let (table, _) = KnownConfig::Variable.decode::<NimOrderedTable<u32, u32>>(&bytes).unwrap();
+    assert_eq!(table.0, entries);
+}
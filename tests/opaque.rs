@@ -0,0 +1,96 @@
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::config_matrix;
+use bincode_wrapper::opaque::Opaque;
+use bincode_wrapper::reference::Person;
+use bincode_wrapper::test_data;
+
+/// Values exchanged with `test_opaque.nim`, which has the same list
+fn person_cases() -> Vec<(&'static str, Person)> {
+    vec![
+        ("person_alice", Person { name: "Alice".to_string(), age: 30, email: "alice@example.com".to_string() }),
+        ("person_empty", Person { name: String::new(), age: 0, email: String::new() }),
+        ("person_unicode", Person { name: "Zoë 🚀".to_string(), age: u32::MAX, email: "zoë@例え.jp".to_string() }),
+    ]
+}
+
+fn string_cases() -> Vec<(&'static str, String)> {
+    vec![("string_hello", "Hello, World!".to_string()), ("string_long", "x".repeat(300))]
+}
+
+/// `<side>_opaque_<name>.bin`
+fn opaque_file(side: &str, name: &str) -> String {
+    format!("{}_opaque_{}.bin", side, name)
+}
+
+// ============================================================================
+// Format Tests
+// ============================================================================
+
+#[test]
+fn test_opaque_wraps_inner_encoding() {
+    let (_, person) = person_cases().remove(0);
+    let inner = KnownConfig::Fixed8.encode(&person).unwrap();
+    let wrapped = KnownConfig::Fixed8.encode(&Opaque(person.clone())).unwrap();
+    assert_eq!(wrapped[..8], (inner.len() as u64).to_le_bytes());
+    assert_eq!(wrapped[8..], inner);
+    assert_eq!(wrapped, KnownConfig::Fixed8.encode(&inner).unwrap());
+
+    // The inner bytes use the outer config: 300 as a varint inside a 3-byte vector
+    assert_eq!(KnownConfig::Variable.encode(&Opaque(300u32)).unwrap(), [0x03, 0xfb, 0x2c, 0x01]);
+}
+
+#[test]
+fn test_opaque_roundtrip_in_every_combination() {
+    let report = config_matrix!(|config, combo| {
+        for (name, person) in person_cases() {
+            let encoded = bincode::encode_to_vec(Opaque(person.clone()), config).map_err(|e| e.to_string())?;
+            let (decoded, bytes_read): (Opaque<Person>, _) =
+                bincode::decode_from_slice(&encoded, config).map_err(|e| format!("{}: {}", name, e))?;
+            assert_eq!(decoded.0, person, "{} {}", name, combo);
+            assert_eq!(bytes_read, encoded.len());
+        }
+        Ok(())
+    });
+    report.assert_passed();
+}
+
+#[test]
+fn test_opaque_rejects_trailing_inner_bytes() {
+    // A 2-byte vector holding the 1-byte varint 5 and a stray zero
+    let err = KnownConfig::Variable.decode::<Opaque<u32>>(&[0x02, 0x05, 0x00]).unwrap_err();
+    assert!(err.to_string().contains("1 trailing bytes inside a 2-byte opaque value"), "{}", err);
+    // Too few inner bytes for the value
+    assert!(KnownConfig::Variable.decode::<Opaque<u32>>(&[0x01, 0xfb]).is_err());
+}
+
+// ============================================================================
+// Cross-Verification Tests (fixed8, the config of Nim's serializeType)
+// ============================================================================
+
+/// Step 1 of `make test-opaque`: the Nim side unwraps these with `deserializeType`
+#[test]
+fn test_write_opaque_values() {
+    for (name, person) in person_cases() {
+        test_data::write_file(&opaque_file("rust", name), &KnownConfig::Fixed8.encode(&Opaque(person)).unwrap()).unwrap();
+    }
+    for (name, text) in string_cases() {
+        test_data::write_file(&opaque_file("rust", name), &KnownConfig::Fixed8.encode(&Opaque(text)).unwrap()).unwrap();
+    }
+}
+
+/// Step 3 of `make test-opaque`: unwrap what Nim's `serializeType` wrote
+#[test]
+#[ignore = "reads files written by test_opaque.nim; run by `make test-opaque`"]
+fn test_nim_opaque_values_decode() {
+    test_data::require_shared_dir("test_opaque.nim");
+    for (name, person) in person_cases() {
+        let bytes = test_data::read_file(&opaque_file("nim", name)).unwrap();
+        let (decoded, bytes_read) = KnownConfig::Fixed8.decode::<Opaque<Person>>(&bytes).unwrap();
+        assert_eq!((decoded.0, bytes_read), (person, bytes.len()), "{}", name);
+    }
+    for (name, text) in string_cases() {
+        let bytes = test_data::read_file(&opaque_file("nim", name)).unwrap();
+        let (decoded, bytes_read) = KnownConfig::Fixed8.decode::<Opaque<String>>(&bytes).unwrap();
+        assert_eq!((decoded.0, bytes_read), (text, bytes.len()), "{}", name);
+    }
+}
@@ -0,0 +1,149 @@
+use bincode::error::{DecodeError, EncodeError};
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::config_matrix;
+use bincode_wrapper::matrix::Endian;
+use bincode_wrapper::reference::Person;
+use bincode_wrapper::runtime_config::{RuntimeConfig, DEFAULT_SIZE_LIMIT};
+
+fn person() -> Person {
+    Person { name: "Alice".to_string(), age: 30, email: "alice@example.com".to_string() }
+}
+
+// ============================================================================
+// Dispatch Tests
+// ============================================================================
+
+#[test]
+fn test_matches_type_level_configs() {
+    let data: Vec<u8> = (0..300).map(|i| i as u8).collect();
+    let report = config_matrix!(|config, combo| {
+        let runtime = RuntimeConfig::from(combo);
+        let expected = bincode::encode_to_vec(&data, config).unwrap();
+        assert_eq!(runtime.encode(&data).map_err(|e| e.to_string())?, expected, "{}", runtime);
+        assert_eq!(runtime.decode::<Vec<u8>>(&expected).map_err(|e| e.to_string())?, (data.clone(), expected.len()));
+
+        let expected = bincode::encode_to_vec(person(), config).unwrap();
+        assert_eq!(runtime.encode(&person()).map_err(|e| e.to_string())?, expected, "{}", runtime);
+        assert_eq!(runtime.decode::<Person>(&expected).map_err(|e| e.to_string())?, (person(), expected.len()));
+        Ok(())
+    });
+    report.assert_passed();
+}
+
+#[test]
+fn test_known_configs() {
+    for config in KnownConfig::ALL {
+        let runtime = RuntimeConfig::from(config);
+        assert_eq!(runtime.encode(&person()).unwrap(), config.encode(&person()).unwrap(), "{}", config);
+        assert_eq!(runtime.to_string().parse::<RuntimeConfig>(), Ok(runtime));
+        assert_eq!(config.name().parse::<RuntimeConfig>(), Ok(runtime));
+    }
+}
+
+// ============================================================================
+// Limit Tests
+// ============================================================================
+
+#[test]
+fn test_decode_limit_matches_bincode() {
+    // 8-byte prefix + 100 bytes: fits a 108-byte limit, not a 107-byte one
+    let encoded = KnownConfig::Fixed8.encode(&vec![1u8; 100]).unwrap();
+    let runtime = RuntimeConfig::from(KnownConfig::Fixed8);
+
+    assert!(runtime.with_limit(108).decode::<Vec<u8>>(&encoded).is_ok());
+    assert!(KnownConfig::Fixed8.decode_with_limit::<Vec<u8>, 108>(&encoded).is_ok());
+    assert!(matches!(runtime.with_limit(107).decode::<Vec<u8>>(&encoded), Err(DecodeError::LimitExceeded)));
+    assert!(matches!(
+        KnownConfig::Fixed8.decode_with_limit::<Vec<u8>, 107>(&encoded),
+        Err(DecodeError::LimitExceeded)
+    ));
+}
+
+#[test]
+fn test_decode_limit_counts_wire_bytes() {
+    // bincode's own limit charges a varint length as 8 bytes; the runtime
+    // limit counts the 1 byte actually read
+    let encoded = KnownConfig::Variable.encode(&vec![1u8; 100]).unwrap();
+    let runtime = RuntimeConfig::from(KnownConfig::Variable);
+
+    assert!(runtime.with_limit(101).decode::<Vec<u8>>(&encoded).is_ok());
+    assert!(matches!(runtime.with_limit(100).decode::<Vec<u8>>(&encoded), Err(DecodeError::LimitExceeded)));
+    assert!(KnownConfig::Variable.decode_with_limit::<Vec<u8>, 101>(&encoded).is_err());
+}
+
+#[test]
+fn test_varint_claims_do_not_exceed_runtime_limit() {
+    // 1-byte varints that bincode's limit charges 8 and 16 bytes each
+    let runtime = RuntimeConfig::standard().with_variable_int_encoding().with_limit(3000);
+    let wide = vec![1u64; 2000];
+    let encoded = runtime.encode(&wide).unwrap();
+    assert_eq!(runtime.decode::<Vec<u64>>(&encoded).unwrap(), (wide, encoded.len()));
+    let widest = vec![1u128; 2000];
+    let encoded = runtime.encode(&widest).unwrap();
+    assert_eq!(runtime.decode::<Vec<u128>>(&encoded).unwrap(), (widest, encoded.len()));
+}
+
+#[test]
+fn test_nested_empty_containers_at_limit() {
+    // 1 byte on the wire each, but bincode claims size_of::<T>() per element
+    let runtime = RuntimeConfig::standard().with_variable_int_encoding().with_limit(1000);
+    let vecs = vec![Vec::<u8>::new(); 997];
+    let encoded = runtime.encode(&vecs).unwrap();
+    assert_eq!(encoded.len(), 1000);
+    assert_eq!(runtime.decode::<Vec<Vec<u8>>>(&encoded).unwrap(), (vecs, 1000));
+    let strings = vec![String::new(); 997];
+    let encoded = runtime.encode(&strings).unwrap();
+    assert_eq!(runtime.decode::<Vec<String>>(&encoded).unwrap(), (strings, 1000));
+    let nested = vec![vec![Vec::<u8>::new()]; 498];
+    let encoded = runtime.encode(&nested).unwrap();
+    assert_eq!(encoded.len(), 999);
+    assert_eq!(runtime.decode::<Vec<Vec<Vec<u8>>>>(&encoded).unwrap(), (nested, 999));
+    let options = vec![None::<u128>; 997];
+    let encoded = runtime.encode(&options).unwrap();
+    assert_eq!(runtime.decode::<Vec<Option<u128>>>(&encoded).unwrap(), (options, 1000));
+}
+
+#[test]
+fn test_huge_length_prefix_is_rejected_before_allocating() {
+    // 0xfd + u64::MAX: bincode would try to allocate the whole length
+    let encoded = [0xfd, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+    let runtime = RuntimeConfig::standard().with_variable_int_encoding().with_limit(1000);
+    assert!(matches!(runtime.decode::<Vec<u8>>(&encoded), Err(DecodeError::LimitExceeded)));
+}
+
+#[test]
+fn test_encode_limit() {
+    let runtime = RuntimeConfig::standard().with_limit(108);
+    assert_eq!(runtime.encode(&vec![0u8; 100]).unwrap().len(), 108);
+    assert!(matches!(runtime.encode(&vec![0u8; 101]), Err(EncodeError::Other("size limit exceeded"))));
+}
+
+// ============================================================================
+// Parsing Tests
+// ============================================================================
+
+#[test]
+fn test_parse_and_render() {
+    let config: RuntimeConfig = "byte_order=big, int_size=0,size_limit=1024".parse().unwrap();
+    assert_eq!(config, RuntimeConfig::standard().with_big_endian().with_variable_int_encoding().with_limit(1024));
+    assert_eq!(config.to_string(), "byte_order=big,int_size=0,size_limit=1024");
+    assert_eq!(config.nim_config(), "standard().withBigEndian().withVariableIntEncoding().withLimit(1024'u64)");
+    assert_eq!("".parse::<RuntimeConfig>(), Ok(RuntimeConfig::standard()));
+    assert_eq!(RuntimeConfig::standard().nim_config(), "standard()");
+
+    let from_json: RuntimeConfig = serde_json::from_str(r#"{"byte_order": "big", "size_limit": 10}"#).unwrap();
+    assert_eq!(from_json, RuntimeConfig { byte_order: Endian::Big, int_size: 8, size_limit: 10 });
+    assert_eq!(RuntimeConfig::default().size_limit, DEFAULT_SIZE_LIMIT);
+}
+
+#[test]
+fn test_invalid_configs() {
+    assert!("int_size=4".parse::<RuntimeConfig>().unwrap_err().contains("no Rust bincode equivalent"));
+    assert!("int_size=3".parse::<RuntimeConfig>().unwrap_err().contains("must be 0, 1, 2, 4 or 8"));
+    assert!("byte_order=middle".parse::<RuntimeConfig>().unwrap_err().contains("expected little or big"));
+    assert!("limit=5".parse::<RuntimeConfig>().unwrap_err().contains("unknown config key 'limit'"));
+
+    let nim_only = RuntimeConfig { int_size: 4, ..RuntimeConfig::standard() };
+    assert!(matches!(nim_only.encode(&1u32), Err(EncodeError::OtherString(_))));
+    assert!(matches!(nim_only.decode::<u32>(&[1, 0, 0, 0]), Err(DecodeError::OtherString(_))));
+}
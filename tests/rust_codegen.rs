@@ -0,0 +1,119 @@
+use std::fs;
+
+use bincode::{Decode, Encode};
+use bincode_wrapper::codegen::rust::{generate_rust_module, parse_nim_objects};
+use bincode_wrapper::reference::Person;
+use bincode_wrapper::schema::BincodeSchema;
+
+const MESSAGES_NIM: &str = r#"
+import std/options
+
+type
+  Status* {.pure.} = enum
+    Active = 0
+    Closed = 1
+
+  Header* = object
+    version*: uint8
+    sequenceNo*: uint64 # per sender
+
+  Message* = object
+    header*: Header
+    senderId*, receiverId*: array[0..3, byte]
+    body*: string
+    tags*: seq[string]
+    replyTo*: Option[Header]
+    `type`*: int32
+    weight*: float
+
+proc helper() = discard
+"#;
+
+/// What the generated Rust for `MESSAGES_NIM` should look like on the wire
+#[derive(Encode, Decode, BincodeSchema)]
+struct Header {
+    version: u8,
+    sequence_no: u64,
+}
+
+#[derive(Encode, Decode, BincodeSchema)]
+struct Message {
+    header: Header,
+    sender_id: [u8; 4],
+    receiver_id: [u8; 4],
+    body: String,
+    tags: Vec<String>,
+    reply_to: Option<Header>,
+    r#type: i32,
+    weight: f64,
+}
+
+// ============================================================================
+// Nim Parsing Tests
+// ============================================================================
+
+#[test]
+fn test_nim_objects_match_rust_schemas() {
+    let objects = parse_nim_objects(MESSAGES_NIM).unwrap();
+    assert_eq!(objects, vec![Header::schema(), Message::schema()]);
+}
+
+#[test]
+fn test_generated_person_codec_parses_back_to_person() {
+    let source = fs::read_to_string("bincode/examples/person_codec.nim").unwrap();
+    assert_eq!(parse_nim_objects(&source).unwrap(), vec![Person::schema()]);
+}
+
+#[test]
+fn test_single_line_type_declaration() {
+    let objects = parse_nim_objects("type Point* = object\n  x*, y*: int\n").unwrap();
+    let module = generate_rust_module(&objects).unwrap();
+    assert!(module.contains("pub struct Point {\n    pub x: i64,\n    pub y: i64,\n}\n"));
+}
+
+#[test]
+fn test_unsupported_declarations_are_rejected() {
+    let unknown = parse_nim_objects("type A* = object\n  b*: B\n").unwrap_err();
+    assert!(unknown.to_string().contains("unknown type 'B'"));
+
+    let variant = "type A* = object\n  case kind*: bool\n  of true: x*: int\n  of false: discard\n";
+    assert!(parse_nim_objects(variant).unwrap_err().to_string().contains("object variants"));
+
+    let table = parse_nim_objects("type A* = object\n  t*: Table[string, int]\n").unwrap_err();
+    assert!(table.to_string().contains("Table"));
+
+    let cyclic = parse_nim_objects("type A* = object\n  a*: Option[A]\n").unwrap_err();
+    assert!(cyclic.to_string().contains("contains itself"));
+
+    let generic = parse_nim_objects("type Foo*[T] = object\n  x*: T\n").unwrap_err();
+    assert_eq!(generic.to_string(), "generic objects are not supported (in 'Foo')");
+}
+
+#[test]
+fn test_field_names_become_valid_rust_identifiers() {
+    let source = "type A* = object\n  senderID*, HTTPServer*, parseURL2*, `self`*, `super`*, `crate`*, `try`*: int\n";
+    let module = generate_rust_module(&parse_nim_objects(source).unwrap()).unwrap();
+    assert!(module.contains(
+        "    pub sender_id: i64,\n    pub http_server: i64,\n    pub parse_url2: i64,\n    \
+         pub self_: i64,\n    pub super_: i64,\n    pub crate_: i64,\n    pub r#try: i64,\n"
+    ), "{}", module);
+}
+
+// ============================================================================
+// Generated Module Tests
+// ============================================================================
+
+#[test]
+fn test_generated_structs_derive_encode_decode_in_field_order() {
+    let module = generate_rust_module(&parse_nim_objects(MESSAGES_NIM).unwrap()).unwrap();
+
+    assert!(module.contains("use bincode::{Decode, Encode};\n"));
+    assert!(module.contains(
+        "#[derive(Debug, Clone, PartialEq, Encode, Decode, BincodeSchema)]\npub struct Message {\n"
+    ));
+    assert!(module.contains(
+        "    pub header: Header,\n    pub sender_id: [u8; 4],\n    pub receiver_id: [u8; 4],\n    pub body: String,\n"
+    ));
+    assert!(module.contains("    pub reply_to: Option<Header>,\n    pub r#type: i32,\n    pub weight: f64,\n"));
+    assert!(!module.contains("Status"));
+}
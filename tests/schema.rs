@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::num::{NonZeroU32, NonZeroU64};
+use std::ops::Bound;
+
+use bincode::error::{DecodeError, EncodeError, IntegerType};
+use bincode::{Decode, Encode};
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::golden::golden_cases;
+use bincode_wrapper::reference::{reference_schema, reference_schemas, Person};
+use bincode_wrapper::schema::{BincodeSchema, Field, Schema, Variant};
+use bincode_wrapper::transcode::json_to_bincode;
+use bincode_wrapper::value::{decode_value, encode_value, BincodeValue};
+
+#[derive(Encode, Decode, BincodeSchema)]
+struct Wrapper(u16, Vec<u8>);
+
+#[derive(Encode, Decode, BincodeSchema)]
+enum Message {
+    Ping,
+    Data(Vec<u8>),
+    Move { x: i32, y: i32 },
+}
+
+#[derive(Encode, Decode, BincodeSchema)]
+struct Generic<T> {
+    items: Option<Vec<T>>,
+}
+
+// ============================================================================
+// Primitive and Container Schema Tests
+// ============================================================================
+
+#[test]
+fn test_primitive_schemas() {
+    assert_eq!(u8::schema(), Schema::U8);
+    assert_eq!(i64::schema(), Schema::I64);
+    // usize is always written as u64
+    assert_eq!(usize::schema(), Schema::U64);
+    assert_eq!(String::schema(), Schema::String);
+    assert_eq!(<()>::schema(), Schema::Unit);
+}
+
+#[test]
+fn test_container_schemas() {
+    assert_eq!(Vec::<u8>::schema(), Schema::Seq { item: Box::new(Schema::U8) });
+    assert_eq!(<[u8; 4]>::schema(), Schema::Array { len: 4, item: Box::new(Schema::U8) });
+    assert_eq!(Option::<u32>::schema(), Schema::Option { item: Box::new(Schema::U32) });
+    assert_eq!(
+        BTreeMap::<String, u64>::schema(),
+        Schema::Map { key: Box::new(Schema::String), value: Box::new(Schema::U64) }
+    );
+    assert_eq!(
+        <(u8, bool)>::schema(),
+        Schema::Tuple { items: vec![Schema::U8, Schema::Bool] }
+    );
+}
+
+// ============================================================================
+// Derive Tests
+// ============================================================================
+
+#[test]
+fn test_derive_struct_fields_in_order() {
+    let expected = Schema::Struct {
+        name: "Person".to_string(),
+        fields: vec![
+            Field { name: "name".to_string(), schema: Schema::String },
+            Field { name: "age".to_string(), schema: Schema::U32 },
+            Field { name: "email".to_string(), schema: Schema::String },
+        ],
+    };
+    assert_eq!(Person::schema(), expected);
+}
+
+#[test]
+fn test_derive_tuple_struct() {
+    let Schema::Struct { name, fields } = Wrapper::schema() else {
+        panic!("expected a struct schema");
+    };
+    assert_eq!(name, "Wrapper");
+    let names: Vec<&str> = fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["0", "1"]);
+}
+
+#[test]
+fn test_derive_enum_variant_indices() {
+    let Schema::Enum { name, variants } = Message::schema() else {
+        panic!("expected an enum schema");
+    };
+    assert_eq!(name, "Message");
+    assert_eq!(
+        variants[0],
+        Variant { name: "Ping".to_string(), index: 0, fields: vec![] }
+    );
+    assert_eq!(variants[1].index, 1);
+    assert_eq!(variants[2].index, 2);
+    assert_eq!(variants[2].fields[1].name, "y");
+
+    // The index in the schema is the one bincode writes
+    let encoded = bincode::encode_to_vec(Message::Move { x: 1, y: 2 }, bincode::config::standard()).unwrap();
+    assert_eq!(encoded[0], 2);
+}
+
+#[test]
+fn test_derive_generic_struct() {
+    let Schema::Struct { fields, .. } = Generic::<i16>::schema() else {
+        panic!("expected a struct schema");
+    };
+    assert_eq!(
+        fields[0].schema,
+        Schema::Option { item: Box::new(Schema::Seq { item: Box::new(Schema::I16) }) }
+    );
+}
+
+#[test]
+fn test_std_schemas() {
+    assert_eq!(NonZeroU32::schema(), Schema::NonZero { item: Box::new(Schema::U32) });
+    assert_eq!(Cow::<str>::schema(), Schema::String);
+    let Schema::Enum { variants, .. } = Bound::<u32>::schema() else {
+        panic!("expected an enum schema");
+    };
+    let tags: Vec<(&str, u32)> = variants.iter().map(|v| (v.name.as_str(), v.index)).collect();
+    assert_eq!(tags, [("Included", 1), ("Excluded", 2), ("Unbounded", 0)]);
+}
+
+#[test]
+fn test_non_zero_schema_rejects_zero() {
+    let schema = NonZeroU64::schema();
+    assert!(matches!(
+        decode_value(&[0], &schema, KnownConfig::Variable),
+        Err(DecodeError::NonZeroTypeIsZero { non_zero_type: IntegerType::U64 })
+    ));
+    let Err(EncodeError::OtherString(message)) = encode_value(&BincodeValue::U64(0), &schema, KnownConfig::Variable)
+    else {
+        panic!("zero should be rejected");
+    };
+    assert_eq!(message, "$: expected non-zero u64, found 0");
+    assert!(json_to_bincode(&serde_json::json!(0), &schema, KnownConfig::Variable).is_err());
+    assert_eq!(json_to_bincode(&serde_json::json!(7), &schema, KnownConfig::Variable).unwrap(), vec![7]);
+}
+
+// ============================================================================
+// JSON Export Tests
+// ============================================================================
+
+#[test]
+fn test_schema_json_format() {
+    let json = serde_json::to_value(Vec::<u8>::schema()).unwrap();
+    assert_eq!(json, serde_json::json!({ "kind": "seq", "item": { "kind": "u8" } }));
+}
+
+#[test]
+fn test_schema_json_roundtrip() {
+    for (name, schema) in reference_schemas() {
+        let parsed = Schema::from_json(&schema.to_json()).unwrap();
+        assert_eq!(parsed, schema, "JSON roundtrip changed schema for {}", name);
+    }
+    assert_eq!(
+        Schema::from_json(&Message::schema().to_json()).unwrap(),
+        Message::schema()
+    );
+}
+
+#[test]
+fn test_reference_schema_lookup() {
+    assert_eq!(reference_schema("Vec<u8>"), Some(Vec::<u8>::schema()));
+    assert_eq!(reference_schema("Person"), Some(Person::schema()));
+    assert_eq!(reference_schema("NotAType"), None);
+}
+
+#[test]
+fn test_reference_schemas_match_golden_encodings() {
+    // Every golden case decodes and re-encodes through its type's schema
+    for case in golden_cases() {
+        let schema = reference_schema(case.type_name)
+            .unwrap_or_else(|| panic!("{}: no reference schema for {}", case.name, case.type_name));
+        for config in KnownConfig::ALL {
+            let bytes = case.encode(config).unwrap();
+            let (value, bytes_read) = decode_value(&bytes, &schema, config)
+                .unwrap_or_else(|e| panic!("{} {}: {}", case.name, config, e));
+            assert_eq!(bytes_read, bytes.len(), "{} {}", case.name, config);
+            assert_eq!(encode_value(&value, &schema, config).unwrap(), bytes, "{} {}", case.name, config);
+        }
+    }
+    for name in ["NimSet64", "NimCharSet", "NimRange<-10, 1000>", "UserId", "Color", "NimOrderedTable<String, u32>", "NimChar"] {
+        assert!(golden_cases().iter().any(|case| case.type_name == name), "no golden case for {}", name);
+    }
+}
@@ -0,0 +1,62 @@
+use bincode_wrapper::snapshot::{diff_hex, parse_snapshot, render_hex, render_snapshot};
+
+// ============================================================================
+// Snapshot Format Tests
+// ============================================================================
+
+#[test]
+fn test_render_hex_layout() {
+    let bytes: Vec<u8> = b"Hello, bincode!\x00\x01".to_vec();
+    assert_eq!(
+        render_hex(&bytes),
+        "00000000  48 65 6c 6c 6f 2c 20 62 69 6e 63 6f 64 65 21 00  |Hello, bincode!.|\n\
+         00000010  01                                               |.|\n"
+    );
+    assert_eq!(render_hex(&[]), "");
+}
+
+#[test]
+fn test_parse_snapshot_roundtrip() {
+    let bytes: Vec<u8> = (0..=255u8).rev().collect();
+    let text = render_snapshot(&["value: reversed bytes", "config: none"], &bytes);
+    assert!(text.starts_with("# value: reversed bytes\n# config: none\n# 256 bytes\n"));
+    assert_eq!(parse_snapshot(&text).unwrap(), bytes);
+    // An ASCII column containing `|` or hex-looking text does not confuse the parser
+    assert_eq!(parse_snapshot(&render_snapshot(&[], b"|ab 12|")).unwrap(), b"|ab 12|");
+}
+
+#[test]
+fn test_parse_snapshot_rejects_bad_hex() {
+    let err = parse_snapshot("# comment\n00000000  01 zz\n").unwrap_err();
+    assert_eq!(err, "line 2: invalid hex byte 'zz'");
+}
+
+#[test]
+fn test_diff_marks_changed_lines() {
+    let expected: Vec<u8> = (0..20).collect();
+    let mut actual = expected.clone();
+    actual[17] = 0xff;
+    let diff = diff_hex(&expected, &actual);
+    let lines: Vec<&str> = diff.lines().collect();
+    assert_eq!(lines[0], "first difference at offset 0x11: expected 11, found ff");
+    assert!(lines[1].starts_with("  00000000  00 01 02"));
+    assert!(lines[2].starts_with("- 00000010  10 11 12 13"));
+    assert!(lines[3].starts_with("+ 00000010  10 ff 12 13"));
+
+    let diff = diff_hex(&expected[..4], &expected[..2]);
+    assert!(diff.starts_with("common prefix matches; expected 4 bytes, found 2\n"));
+}
+
+#[test]
+fn test_diff_collapses_identical_lines() {
+    let expected = vec![0u8; 16 * 100];
+    let mut actual = expected.clone();
+    actual[16 * 50] = 1;
+    let diff = diff_hex(&expected, &actual);
+    let lines: Vec<&str> = diff.lines().collect();
+    assert_eq!(lines[1], "  ... 47 identical lines");
+    assert!(lines[5].starts_with("- 00000320"));
+    assert!(lines[6].starts_with("+ 00000320  01"));
+    assert_eq!(lines[10], "  ... 46 identical lines");
+    assert_eq!(lines.len(), 11);
+}
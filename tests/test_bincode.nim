@@ -481,4 +481,78 @@ suite "Edge cases":
     expect BincodeError:
       discard deserialize(invalid, config)
 
+# ============================================================================
+# Bare Primitive Tests
+# ============================================================================
+
+suite "Bare primitives":
+  test "encode uint32 with fixed encoding uses natural width":
+    var stream = memoryOutput()
+    encodeUint(stream, 42'u32, standard())
+    check stream.getOutput() == @[byte(42), 0, 0, 0]
+
+  test "encode uint16 big-endian":
+    var stream = memoryOutput()
+    encodeUint(stream, 0x1234'u16, standard().withBigEndian())
+    check stream.getOutput() == @[byte(0x12), 0x34]
+
+  test "encode uint32 with variable encoding uses marker scheme":
+    let config = standard().withVariableIntEncoding()
+    var stream = memoryOutput()
+    encodeUint(stream, 250'u32, config)
+    encodeUint(stream, 251'u32, config)
+    check stream.getOutput() == @[byte(250), 0xfb, 251, 0]
+
+  test "encode int32 with variable encoding uses zigzag":
+    let config = standard().withVariableIntEncoding()
+    var stream = memoryOutput()
+    encodeInt(stream, -1'i32, config)
+    encodeInt(stream, 1'i32, config)
+    check stream.getOutput() == @[byte(1), 2]
+
+  test "decode values one after another":
+    let config = standard().withVariableIntEncoding()
+    var stream = memoryOutput()
+    encodeUint(stream, 70000'u32, config)
+    encodeInt(stream, -300'i64, config)
+    encodeBool(stream, true, config)
+    encodeFloat(stream, 1.5'f64, config)
+    serializeString(stream, "hi", config)
+    let data = stream.getOutput()
+
+    var pos = 0
+    check decodeUint[uint32](data, pos, config) == 70000'u32
+    check decodeInt[int64](data, pos, config) == -300'i64
+    check decodeBool(data, pos, config)
+    check decodeFloat[float64](data, pos, config) == 1.5
+    check decodeString(data, pos, config) == "hi"
+    check pos == data.len
+
+  test "reject invalid UTF-8 in decodeString":
+    # Overlong encoding of '/'
+    let data = @[byte(2), 0xc0, 0xaf]
+    var pos = 0
+    expect BincodeError:
+      discard decodeString(data, pos, standard().withVariableIntEncoding())
+
+  test "reject variable marker too wide for the type":
+    let config = standard().withVariableIntEncoding()
+    # 0xfc (u32 marker) is not valid for a u16
+    let data = @[byte(0xfc), 1, 0, 0, 0]
+    var pos = 0
+    expect BincodeError:
+      discard decodeUint[uint16](data, pos, config)
+
+  test "reject invalid bool":
+    let data = @[byte(2)]
+    var pos = 0
+    expect BincodeError:
+      discard decodeBool(data, pos)
+
+  test "reject truncated integer":
+    let data = @[byte(1), 2]
+    var pos = 0
+    expect BincodeError:
+      discard decodeUint[uint32](data, pos)
+
 {.pop.}
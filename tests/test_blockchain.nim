@@ -0,0 +1,177 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, getOutput, OutputStreamHandle
+import unittest2
+import std/os
+import nim_bincode
+import bincode_config
+import test_data
+import examples/signed_transaction_codec
+
+# Same samples as src/blockchain.rs; files are `<side>_chain_<name>_<config>.bin`
+const CaseNames = [
+  "hash", "hash_zero", "address", "u256", "u256_zero", "proof", "proof_empty",
+  "transaction",
+]
+const ConfigNames = ["variable", "fixed8"]
+
+type
+  Hash32 = array[32, byte]
+  Address = array[20, byte]
+  U256 = array[4, uint64]
+
+const SampleU256: U256 = [high(uint64), 0'u64, 251'u64, 1'u64 shl 63]
+
+func sampleHash(first: int): Hash32 =
+  for i in 0 ..< 32:
+    result[i] = byte((first + i) and 0xff)
+
+func sampleAddress(): Address =
+  for i in 0 ..< 20:
+    result[i] = byte(0xa0 + i)
+
+func sampleProof(len: int): seq[Hash32] =
+  for k in 0 ..< len:
+    result.add(sampleHash(32 * k))
+
+func sampleTransaction(): SignedTransaction =
+  SignedTransaction(
+    nonce: 9,
+    gasPrice: [20_000_000_000'u64, 0, 0, 0],
+    gasLimit: 21_000,
+    to: sampleAddress(),
+    value: [1_000_000_000_000_000_000'u64, 0, 0, 0],
+    data: @[0xa9'u8, 0x05, 0x9c, 0xbb],
+    v: 37,
+    r: sampleHash(0x40),
+    s: sampleHash(0x80),
+  )
+
+func chainConfig(name: string): BincodeConfig {.raises: [ValueError].} =
+  case name
+  of "variable":
+    standard().withVariableIntEncoding()
+  of "fixed8":
+    standard().withFixedIntEncoding(8)
+  else:
+    raise newException(ValueError, "unknown config: " & name)
+
+proc encodeArray[N: static int, T: uint8 | uint64](
+    stream: OutputStreamHandle, value: array[N, T], config: BincodeConfig
+) {.raises: [IOError].} =
+  ## Fixed-size array: the elements only, no length prefix
+  for item in value:
+    encodeUint(stream, item, config)
+
+func decodeArray[N: static int, T: uint8 | uint64](
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): array[N, T] {.raises: [BincodeError].} =
+  for i in 0 ..< N:
+    result[i] = decodeUint[T](data, pos, config)
+
+proc encodeProof(
+    stream: OutputStreamHandle, proof: seq[Hash32], config: BincodeConfig
+) {.raises: [IOError].} =
+  ## `Vec<[u8; 32]>`: a length prefix, then each hash
+  encodeLength(stream, proof.len.uint64, config)
+  for hash in proof:
+    encodeArray(stream, hash, config)
+
+func decodeProof(
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): seq[Hash32] {.raises: [BincodeError].} =
+  let length = decodeCollectionLength(data, pos, config)
+  for _ in 0 ..< length:
+    result.add(decodeArray[32, byte](data, pos, config))
+
+proc nimEncode(
+    name: string, config: BincodeConfig
+): seq[byte] {.raises: [BincodeError, IOError, ValueError].} =
+  var stream = memoryOutput()
+  case name
+  of "hash":
+    encodeArray(stream, sampleHash(0), config)
+  of "hash_zero":
+    encodeArray(stream, default(Hash32), config)
+  of "address":
+    encodeArray(stream, sampleAddress(), config)
+  of "u256":
+    encodeArray(stream, SampleU256, config)
+  of "u256_zero":
+    encodeArray(stream, default(U256), config)
+  of "proof":
+    encodeProof(stream, sampleProof(3), config)
+  of "proof_empty":
+    encodeProof(stream, sampleProof(0), config)
+  of "transaction":
+    encodeSignedTransaction(stream, sampleTransaction(), config)
+  else:
+    raise newException(ValueError, "unknown case: " & name)
+  stream.getOutput()
+
+func decodesToSample(
+    name: string, data: openArray[byte], config: BincodeConfig
+): bool {.raises: [BincodeError, ValueError].} =
+  ## Decode `data` as the case's type; true if every byte was used and the
+  ## value is the sample
+  var pos = 0
+  let matches =
+    case name
+    of "hash":
+      decodeArray[32, byte](data, pos, config) == sampleHash(0)
+    of "hash_zero":
+      decodeArray[32, byte](data, pos, config) == default(Hash32)
+    of "address":
+      decodeArray[20, byte](data, pos, config) == sampleAddress()
+    of "u256":
+      decodeArray[4, uint64](data, pos, config) == SampleU256
+    of "u256_zero":
+      decodeArray[4, uint64](data, pos, config) == default(U256)
+    of "proof":
+      decodeProof(data, pos, config) == sampleProof(3)
+    of "proof_empty":
+      decodeProof(data, pos, config).len == 0
+    of "transaction":
+      decodeSignedTransaction(data, pos, config) == sampleTransaction()
+    else:
+      raise newException(ValueError, "unknown case: " & name)
+  checkNoTrailingBytes(data.len, pos, 0)
+  matches
+
+suite "Blockchain primitives (Rust → Nim)":
+  test "Nim decodes the Rust hashes, addresses, U256 limbs, proofs and transaction":
+    for name in CaseNames:
+      for configName in ConfigNames:
+        let file = "rust_chain_" & name & "_" & configName & ".bin"
+        let data = cast[seq[byte]](readFile(testDataDir() / file))
+        let ok = decodesToSample(name, data, chainConfig(configName))
+        check ok
+        if not ok:
+          echo file, ": decoded value differs from the sample"
+
+  test "fixed arrays have no length prefix, proofs do":
+    for configName in ConfigNames:
+      let config = chainConfig(configName)
+      check nimEncode("hash", config).len == 32
+      check nimEncode("address", config).len == 20
+      check nimEncode("proof", config).len == (if configName == "fixed8": 8 else: 1) + 3 * 32
+
+suite "Blockchain primitives (Nim → Rust)":
+  test "Nim writes the same bytes as Rust":
+    for name in CaseNames:
+      for configName in ConfigNames:
+        let encoded = nimEncode(name, chainConfig(configName))
+        let rust = cast[seq[byte]](
+          readFile(testDataDir() / ("rust_chain_" & name & "_" & configName & ".bin"))
+        )
+        check encoded == rust
+        if encoded != rust:
+          echo name, " ", configName, ": Nim ", encoded, ", Rust ", rust
+        let file = "nim_chain_" & name & "_" & configName & ".bin"
+        writeFile(testDataDir() / file, encoded)
+        recordFile(file)
+
+{.pop.}
@@ -8,6 +8,8 @@ import unittest2
 import std/os
 import nim_bincode
 import bincode_config
+import cross_vectors # Generated from src/vectors.rs by `make nim-vectors`
+import test_data
 
 # Helper function to serialize using streaming API and return seq[byte]
 proc serializeToSeq(
@@ -17,8 +19,6 @@ proc serializeToSeq(
   serialize(stream, data, config)
   stream.getOutput()
 
-const TestDataDir = "target/test_data"
-
 # Compile-time defines to control which test suites run
 when defined(testVariable):
   const RUN_VARIABLE_TESTS = true
@@ -40,44 +40,12 @@ else:
 # Test Case Definitions
 # ============================================================================
 
-# Test files for variable encoding deserialization
-const DeserializeTestFilesVariable {.used.}: array[12, string] = [
-  "nim_var_001.bin", "nim_var_002.bin", "nim_var_003.bin", "nim_var_004.bin",
-  "nim_var_005.bin", "nim_var_006.bin", "nim_var_007.bin", "nim_var_008.bin",
-  "nim_var_009.bin", "nim_var_010.bin", "nim_var_011.bin", "nim_var_012.bin",
-]
-
-# Test files for fixed 8-byte encoding deserialization
-const DeserializeTestFilesFixed8 {.used.}: array[12, string] = [
-  "nim_fixed8_001.bin", "nim_fixed8_002.bin", "nim_fixed8_003.bin",
-  "nim_fixed8_004.bin", "nim_fixed8_005.bin", "nim_fixed8_006.bin",
-  "nim_fixed8_007.bin", "nim_fixed8_008.bin", "nim_fixed8_009.bin",
-  "nim_fixed8_010.bin", "nim_fixed8_011.bin", "nim_fixed8_012.bin",
-]
+# File names, payloads and configs come from cross_vectors.nim, generated from
+# the Rust vector list, so the two sides cannot drift apart.
 
-# Get expected data for deserialization tests
 func getExpectedData(): seq[seq[byte]] =
-  var data100 = newSeq[byte](100)
-  for i in 0 ..< data100.len:
-    data100[i] = byte(1)
-
-  return
-    @[
-      @[byte(1), 2, 3, 4, 5],
-      @[],
-      @[byte(0), 255, 128, 64],
-      cast[seq[byte]]("Hello, World!"),
-      @[byte(42)],
-      cast[seq[byte]]("Test with émojis 🚀"),
-      data100,
-      newSeq[byte](20 * 1024), # 20kB
-      newSeq[byte](250), # Just below 251 threshold (uses single byte)
-      newSeq[byte](251), # Just at 251 threshold (uses 0xfb + u16 LE)
-      newSeq[byte](65535),
-        # Just below 2^16 threshold (uses 0xfb + u16 LE: 3 + 65535 = 65538)
-      newSeq[byte](65536),
-        # Just at 2^16 threshold (uses 0xfc + u32 LE: 5 + 65536 = 65541)
-    ]
+  for vector in CrossVectors:
+    result.add(vector.payload)
 
 # ============================================================================
 # Helper Functions
@@ -94,18 +62,19 @@ proc serializeToFile(
     data: openArray[byte], filename: string, config: BincodeConfig = standard()
 ) {.raises: [BincodeError, IOError, OSError].} =
   ## Serialize data and write directly to file for Rust to read (no intermediate allocation)
-  createDir(TestDataDir)
-  let filePath = TestDataDir / filename
+  createDir(testDataDir())
+  let filePath = testDataDir() / filename
   var output = fileOutput(filePath, fmWrite)
   serialize(output, data, config)
   output.close()
+  recordFile(filename)
   echo "Serialized ", formatVecForLog(data), " to ", filename
 
 proc deserializeFromFile(
     filename: string, config: BincodeConfig = standard()
 ): seq[byte] {.raises: [BincodeError, IOError, OSError].} =
   ## Read file and deserialize data that was serialized by Rust
-  let filePath = TestDataDir / filename
+  let filePath = testDataDir() / filename
   let serialized = cast[seq[byte]](readFile(filePath))
   return deserialize(serialized, config)
 
@@ -115,109 +84,21 @@ proc deserializeFromFile(
 
 when RUN_VARIABLE_TESTS:
   suite "Rust serialize → Nim deserialize (variable encoding)":
-    test "deserialize rust_var_001.bin":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_001.bin", config)
-      echo "Deserialized ", formatVecForLog(deserialized), " from rust_var_001.bin"
-      check deserialized == @[byte(1), 2, 3, 4, 5]
-
-    test "deserialize rust_var_002.bin (empty)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_002.bin", config)
-      echo "Deserialized ",
-        formatVecForLog(deserialized), " from rust_var_002.bin (empty)"
-      check deserialized.len == 0
-
-    test "deserialize rust_var_003.bin":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_003.bin", config)
-      echo "Deserialized ", formatVecForLog(deserialized), " from rust_var_003.bin"
-      check deserialized == @[byte(0), 255, 128, 64]
-
-    test "deserialize rust_var_004.bin (100 bytes)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_004.bin", config)
-      echo "Deserialized ", deserialized.len, " bytes from rust_var_004.bin"
-      check deserialized.len == 100
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(1)
-
-    test "deserialize rust_var_005.bin (string)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_005.bin", config)
-      let text = cast[string](deserialized)
-      echo "Deserialized ",
-        formatVecForLog(deserialized), " (", text, ") from rust_var_005.bin"
-      check text == "Hello, World!"
-
-    test "deserialize rust_var_006.bin":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_006.bin", config)
-      echo "Deserialized ", formatVecForLog(deserialized), " from rust_var_006.bin"
-      check deserialized == @[byte(42)]
-
-    test "deserialize rust_var_007.bin (unicode)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_007.bin", config)
-      let text = cast[string](deserialized)
-      echo "Deserialized ",
-        formatVecForLog(deserialized), " (", text, ") from rust_var_007.bin"
-      check text == "Test with émojis 🚀"
-
-    test "deserialize rust_var_008.bin (20kB)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_008.bin", config)
-      echo "Deserialized ", deserialized.len, " bytes from rust_var_008.bin (20kB)"
-      check deserialized.len == 20 * 1024
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
-
-    test "deserialize rust_var_009.bin (just below 251 threshold, uses single byte)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_009.bin", config)
-      echo "Deserialized ",
-        deserialized.len,
-        " bytes from rust_var_009.bin (just below threshold, uses single byte)"
-      check deserialized.len == 250
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
-
-    test "deserialize rust_var_010.bin (just at threshold, uses 0xfb + u16 LE)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_010.bin", config)
-      echo "Deserialized ",
-        deserialized.len,
-        " bytes from rust_var_010.bin (just at threshold, uses 0xfb + u16 LE)"
-      check deserialized.len == 251
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
-
-    test "deserialize rust_var_011.bin (just below 2^16 threshold, uses 0xfb + u16)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_011.bin", config)
-      echo "Deserialized ",
-        deserialized.len,
-        " bytes from rust_var_011.bin (just below 2^16 threshold, uses 0xfb + u16)"
-      check deserialized.len == 65535
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
-
-    test "deserialize rust_var_012.bin (just at 2^16 threshold, uses 0xfc + u32)":
-      let config = standard().withVariableIntEncoding()
-      let deserialized = deserializeFromFile("rust_var_012.bin", config)
-      echo "Deserialized ",
-        deserialized.len,
-        " bytes from rust_var_012.bin (just at 2^16 threshold, uses 0xfc + u32)"
-      check deserialized.len == 65536
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
+    for i, vector in CrossVectors:
+      let filename = RustFiles[ccVariable][i]
+      test "deserialize " & filename & " (" & vector.description & ")":
+        let deserialized = deserializeFromFile(filename, bincodeConfig(ccVariable))
+        echo "Deserialized ", formatVecForLog(deserialized), " from ", filename
+        check deserialized.len == vector.length
+        check fnv1a64(deserialized) == vector.hash
+        check deserialized == vector.payload
 
 when RUN_VARIABLE_TESTS:
   suite "Nim serialize → Rust deserialize (variable encoding)":
     test "serialize all test cases":
       let expectedData = getExpectedData()
-      let config = standard().withVariableIntEncoding().withLimit(4294967305'u64)
-      for i, filename in DeserializeTestFilesVariable:
+      let config = bincodeConfig(ccVariable)
+      for i, filename in NimFiles[ccVariable]:
         serializeToFile(expectedData[i], filename, config)
         echo "Created ", filename, " with variable encoding for Rust to verify"
 
@@ -261,105 +142,21 @@ when RUN_VARIABLE_TESTS:
 
 when RUN_FIXED8_TESTS:
   suite "Rust serialize → Nim deserialize (fixed 8-byte)":
-    test "deserialize rust_fixed8_001.bin":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_001.bin", config)
-      echo "Deserialized ", formatVecForLog(deserialized), " from rust_fixed8_001.bin"
-      check deserialized == @[byte(1), 2, 3, 4, 5]
-
-    test "deserialize rust_fixed8_002.bin (empty)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_002.bin", config)
-      echo "Deserialized ",
-        formatVecForLog(deserialized), " from rust_fixed8_002.bin (empty)"
-      check deserialized.len == 0
-
-    test "deserialize rust_fixed8_003.bin":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_003.bin", config)
-      echo "Deserialized ", formatVecForLog(deserialized), " from rust_fixed8_003.bin"
-      check deserialized == @[byte(0), 255, 128, 64]
-
-    test "deserialize rust_fixed8_004.bin (100 bytes)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_004.bin", config)
-      echo "Deserialized ", deserialized.len, " bytes from rust_fixed8_004.bin"
-      check deserialized.len == 100
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(1)
-
-    test "deserialize rust_fixed8_005.bin (string)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_005.bin", config)
-      let text = cast[string](deserialized)
-      echo "Deserialized ",
-        formatVecForLog(deserialized), " (", text, ") from rust_fixed8_005.bin"
-      check text == "Hello, World!"
-
-    test "deserialize rust_fixed8_006.bin":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_006.bin", config)
-      echo "Deserialized ", formatVecForLog(deserialized), " from rust_fixed8_006.bin"
-      check deserialized == @[byte(42)]
-
-    test "deserialize rust_fixed8_007.bin (unicode)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_007.bin", config)
-      let text = cast[string](deserialized)
-      echo "Deserialized ",
-        formatVecForLog(deserialized), " (", text, ") from rust_fixed8_007.bin"
-      check text == "Test with émojis 🚀"
-
-    test "deserialize rust_fixed8_008.bin (20kB)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_008.bin", config)
-      echo "Deserialized ", deserialized.len, " bytes from rust_fixed8_008.bin (20kB)"
-      check deserialized.len == 20 * 1024
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
-
-    test "deserialize rust_fixed8_009.bin (just below 251 threshold)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_009.bin", config)
-      echo "Deserialized ",
-        deserialized.len, " bytes from rust_fixed8_009.bin (just below threshold)"
-      check deserialized.len == 250
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
-
-    test "deserialize rust_fixed8_010.bin (just at threshold)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_010.bin", config)
-      echo "Deserialized ",
-        deserialized.len, " bytes from rust_fixed8_010.bin (just at threshold)"
-      check deserialized.len == 251
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
-
-    test "deserialize rust_fixed8_011.bin (just below 2^16 threshold)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_011.bin", config)
-      echo "Deserialized ",
-        deserialized.len, " bytes from rust_fixed8_011.bin (just below 2^16 threshold)"
-      check deserialized.len == 65535
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
-
-    test "deserialize rust_fixed8_012.bin (just at 2^16 threshold)":
-      let config = standard().withFixedIntEncoding(8)
-      let deserialized = deserializeFromFile("rust_fixed8_012.bin", config)
-      echo "Deserialized ",
-        deserialized.len, " bytes from rust_fixed8_012.bin (just at 2^16 threshold)"
-      check deserialized.len == 65536
-      for i in 0 ..< deserialized.len:
-        check deserialized[i] == byte(0)
+    for i, vector in CrossVectors:
+      let filename = RustFiles[ccFixed8][i]
+      test "deserialize " & filename & " (" & vector.description & ")":
+        let deserialized = deserializeFromFile(filename, bincodeConfig(ccFixed8))
+        echo "Deserialized ", formatVecForLog(deserialized), " from ", filename
+        check deserialized.len == vector.length
+        check fnv1a64(deserialized) == vector.hash
+        check deserialized == vector.payload
 
 when RUN_FIXED8_TESTS:
   suite "Nim serialize → Rust deserialize (fixed 8-byte)":
     test "serialize all test cases":
       let expectedData = getExpectedData()
-      let config = standard().withFixedIntEncoding(8).withLimit(4294967305'u64)
-      for i, filename in DeserializeTestFilesFixed8:
+      let config = bincodeConfig(ccFixed8)
+      for i, filename in NimFiles[ccFixed8]:
         serializeToFile(expectedData[i], filename, config)
         echo "Created ", filename, " with fixed 8-byte encoding for Rust to verify"
 
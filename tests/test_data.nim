@@ -0,0 +1,33 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+## Test-data directory shared with the Rust tests (see src/test_data.rs).
+
+{.push raises: [], gcsafe.}
+
+import std/[os, times]
+
+# Directory shared with the Rust tests; the Makefile sets it once per run.
+# Without it, each run writes to its own temp directory,
+# `bincode-test-data-nim-<pid>` (the Rust tests use `bincode-test-data-rust-<pid>`).
+const TestDataDirEnv = "BINCODE_TEST_DATA_DIR"
+const ManifestFile = "MANIFEST.tsv"
+
+proc testDataDir*(): string =
+  let dir = getEnv(TestDataDirEnv)
+  if dir.len > 0:
+    dir
+  else:
+    getTempDir() / ("bincode-test-data-nim-" & $getCurrentProcessId())
+
+proc recordFile*(filename: string) {.raises: [IOError].} =
+  ## Append `filename` to the run manifest shared with the Rust tests
+  ## (file, producer, process id, unix time).
+  var manifest = open(testDataDir() / ManifestFile, fmAppend)
+  defer:
+    manifest.close()
+  manifest.write(
+    filename & "\tnim\t" & $getCurrentProcessId() & "\t" & $epochTime().int64 & "\n"
+  )
+
+{.pop.}
@@ -0,0 +1,50 @@
+use std::fs;
+
+use bincode_wrapper::test_data::{self, read_manifest, record, shared_dir, test_data_dir, ManifestEntry};
+
+// ============================================================================
+// Test Data Directory Tests
+// ============================================================================
+
+#[test]
+#[ignore = "needs BINCODE_TEST_DATA_DIR unset, which the Makefile always sets; run with `cargo test --test test_data -- --ignored`"]
+fn test_default_directory_is_private_to_the_process() {
+    assert!(shared_dir().is_none(), "unset {} to check the private directory", test_data::DIR_ENV);
+    let dir = test_data_dir();
+    assert_eq!(dir, test_data_dir(), "one directory per run");
+    assert_eq!(dir, std::env::temp_dir().join(format!("bincode-test-data-rust-{}", std::process::id())));
+}
+
+#[test]
+fn test_write_file_records_manifest_entry() {
+    let path = test_data::write_file("manifest_probe.bin", &[1, 2, 3]).unwrap();
+    assert_eq!(test_data::read_file("manifest_probe.bin").unwrap(), vec![1, 2, 3]);
+
+    let entries = read_manifest(path.parent().unwrap()).unwrap();
+    let entry = entries.iter().rev().find(|entry| entry.file == "manifest_probe.bin").unwrap();
+    assert_eq!(entry.producer, "rust");
+    assert_eq!(entry.pid, std::process::id());
+}
+
+#[test]
+fn test_manifest_parses_nim_entries() {
+    let dir = std::env::temp_dir().join(format!("bincode-manifest-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    assert_eq!(read_manifest(&dir).unwrap(), vec![]);
+
+    record(&dir, "rust_var_001.bin", "rust").unwrap();
+    fs::write(
+        dir.join(test_data::MANIFEST_FILE),
+        fs::read_to_string(dir.join(test_data::MANIFEST_FILE)).unwrap() + "nim_var_001.bin\tnim\t42\t1700000000\n",
+    )
+    .unwrap();
+
+    let entries = read_manifest(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].file, "rust_var_001.bin");
+    assert_eq!(
+        entries[1],
+        ManifestEntry { file: "nim_var_001.bin".to_string(), producer: "nim".to_string(), pid: 42, unix_time: 1700000000 }
+    );
+}
@@ -0,0 +1,85 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, getOutput
+import unittest2
+import std/[os, strutils]
+import nim_bincode
+import bincode_config
+import cross_vectors # fnv1a64
+import test_data
+
+# Written by `cargo test --test encode_limit test_write_encode_limit_vectors`;
+# columns documented in src/encode_limit.rs
+const LimitFile = "encode_limits.tsv"
+
+# FILL_BYTE in src/encode_limit.rs
+const FillByte = byte('a')
+
+func limitConfig(name: string, limit: uint64): BincodeConfig {.raises: [ValueError].} =
+  ## Nim config for a vector config name, limited to `limit`
+  let config =
+    case name
+    of "variable":
+      standard().withVariableIntEncoding()
+    of "fixed8":
+      standard().withFixedIntEncoding(8)
+    of "fixed8_be":
+      standard().withBigEndian().withFixedIntEncoding(8)
+    else:
+      raise newException(ValueError, "unknown config: " & name)
+  config.withLimit(limit)
+
+proc nimOutcome(
+    typeName: string, length: int, config: BincodeConfig
+): string {.raises: [IOError, ValueError].} =
+  ## Label in the vector format: `ok:<fnv1a64 of the encoding>` or `err:<message>`
+  var stream = memoryOutput()
+  try:
+    case typeName
+    of "Vec<u8>":
+      var data = newSeq[byte](length)
+      for i in 0 ..< length:
+        data[i] = FillByte
+      serialize(stream, data, config)
+    of "String":
+      serializeString(stream, repeat(char(FillByte), length), config)
+    else:
+      raise newException(ValueError, "unknown type: " & typeName)
+  except BincodeError as e:
+    return "err:" & e.msg
+  "ok:0x" & toHex(fnv1a64(stream.getOutput())).toLowerAscii()
+
+suite "Encode size limit (Rust → Nim)":
+  test "Nim accepts and refuses the same encodes as Rust":
+    var checked, mismatches = 0
+    for line in readFile(testDataDir() / LimitFile).splitLines():
+      if line.len == 0 or line.startsWith("#"):
+        continue
+      let columns = line.split('\t')
+      let (configName, typeName, limit, length, rustOutcome) = (
+        columns[0],
+        columns[1],
+        parseBiggestUInt(columns[2]).uint64,
+        parseInt(columns[3]),
+        columns[4],
+      )
+
+      let nim = nimOutcome(typeName, length, limitConfig(configName, limit))
+      inc checked
+      let agree =
+        if rustOutcome.startsWith("ok:"):
+          nim == rustOutcome
+        else:
+          nim.startsWith("err:")
+      if not agree:
+        inc mismatches
+        echo configName, " ", typeName, " limit ", limit, " len ", length, ": Rust ",
+          rustOutcome, ", Nim ", nim
+    echo "Checked ", checked, " encodes, ", mismatches, " mismatches"
+    check checked > 0
+    check mismatches == 0
+
+{.pop.}
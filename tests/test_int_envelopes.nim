@@ -0,0 +1,107 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, getOutput
+import unittest2
+import std/[os, strutils]
+import nim_bincode
+import bincode_config
+import test_data
+
+# Written by `cargo test --test envelope test_write_envelope_vectors`; columns
+# documented in src/envelope.rs
+const EnvelopeFile = "int_envelopes.tsv"
+
+type EnvelopeRow = object
+  typeName: string
+  configName: string
+  value: string
+  rustFile: string
+  nimFile: string
+
+func envelopeConfig(name: string): BincodeConfig {.raises: [ValueError].} =
+  ## Nim config for a harness config name
+  case name
+  of "variable":
+    standard().withVariableIntEncoding()
+  of "fixed8":
+    standard().withFixedIntEncoding(8)
+  of "variable_be":
+    standard().withBigEndian().withVariableIntEncoding()
+  of "fixed8_be":
+    standard().withBigEndian().withFixedIntEncoding(8)
+  else:
+    raise newException(ValueError, "unknown config: " & name)
+
+proc readRows(): seq[EnvelopeRow] {.raises: [IOError, ValueError].} =
+  for line in readFile(testDataDir() / EnvelopeFile).splitLines():
+    if line.len == 0 or line.startsWith("#"):
+      continue
+    let columns = line.split('\t')
+    result.add(
+      EnvelopeRow(
+        typeName: columns[0],
+        configName: columns[1],
+        value: columns[2],
+        rustFile: columns[3],
+        nimFile: columns[4],
+      )
+    )
+
+proc decodeToString(
+    data: openArray[byte], typeName: string, config: BincodeConfig
+): string {.raises: [BincodeError, ValueError].} =
+  ## Decode with the helper for `typeName`, formatted like the value column
+  case typeName
+  of "i32":
+    $deserializeInt32(data, config)
+  of "u32":
+    $deserializeUint32(data, config)
+  of "i64":
+    $deserializeInt64(data, config)
+  else:
+    raise newException(ValueError, "unknown type: " & typeName)
+
+proc encodeValue(
+    row: EnvelopeRow, config: BincodeConfig
+): seq[byte] {.raises: [IOError, ValueError].} =
+  ## Encode the row's value with the helper for its type
+  var stream = memoryOutput()
+  case row.typeName
+  of "i32":
+    serializeInt32(stream, parseInt(row.value).int32, config)
+  of "u32":
+    serializeUint32(stream, parseBiggestUInt(row.value).uint32, config)
+  of "i64":
+    serializeInt64(stream, parseBiggestInt(row.value).int64, config)
+  else:
+    raise newException(ValueError, "unknown type: " & row.typeName)
+  stream.getOutput()
+
+suite "Integer envelopes (Rust → Nim)":
+  test "deserializeInt32/Uint32/Int64 read the Rust IntEnvelope files":
+    var checked = 0
+    for row in readRows():
+      let data = cast[seq[byte]](readFile(testDataDir() / row.rustFile))
+      let decoded = decodeToString(data, row.typeName, envelopeConfig(row.configName))
+      check decoded == row.value
+      if decoded != row.value:
+        echo row.rustFile, ": expected ", row.value, ", got ", decoded
+      inc checked
+    echo "Decoded ", checked, " Rust envelopes"
+    check checked > 0
+
+suite "Integer envelopes (Nim → Rust)":
+  test "serializeInt32/Uint32/Int64 write the same bytes as IntEnvelope":
+    for row in readRows():
+      let encoded = encodeValue(row, envelopeConfig(row.configName))
+      let rust = cast[seq[byte]](readFile(testDataDir() / row.rustFile))
+      check encoded == rust
+      if encoded != rust:
+        echo row.nimFile, ": Nim ", encoded, ", Rust ", rust
+      writeFile(testDataDir() / row.nimFile, encoded)
+      recordFile(row.nimFile)
+
+{.pop.}
@@ -0,0 +1,75 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import unittest2
+import std/os
+import nim_bincode
+import bincode_config
+import test_data
+
+# Written by `cargo test --test length_prefix test_write_length_prefix_table`;
+# layout documented in src/length_prefix.rs
+const TableFile = "length_prefixes.bin"
+const TableMagic = "LPT1"
+
+# Same order as TABLE_CONFIGS on the Rust side
+const TableConfigNames = ["variable", "fixed8", "fixed8_be"]
+
+func tableConfigs(): array[3, BincodeConfig] =
+  [
+    standard().withVariableIntEncoding(),
+    standard().withFixedIntEncoding(8),
+    standard().withBigEndian().withFixedIntEncoding(8),
+  ]
+
+type TableReader = object
+  data: seq[byte]
+  pos: int
+
+proc take(reader: var TableReader, n: int): seq[byte] {.raises: [ValueError].} =
+  if reader.pos + n > reader.data.len:
+    raise newException(ValueError, "table truncated at offset " & $reader.pos)
+  result = reader.data[reader.pos ..< reader.pos + n]
+  reader.pos += n
+
+proc takeU64(reader: var TableReader): uint64 {.raises: [ValueError].} =
+  let bytes = reader.take(8)
+  for i in 0 ..< 8:
+    result = result or (uint64(bytes[i]) shl (8 * i))
+
+proc takeByte(reader: var TableReader): int {.raises: [ValueError].} =
+  int(reader.take(1)[0])
+
+suite "Length prefix table (Rust → Nim)":
+  test "encodeLength and decodeLength match every row":
+    var reader = TableReader(data: cast[seq[byte]](readFile(testDataDir() / TableFile)))
+    check cast[string](reader.take(TableMagic.len)) == TableMagic
+
+    let configCount = reader.takeByte()
+    check configCount == TableConfigNames.len
+    for name in TableConfigNames:
+      check cast[string](reader.take(reader.takeByte())) == name
+
+    let configs = tableConfigs()
+    let rowCount = reader.takeU64()
+    var failures = 0
+    for _ in 0'u64 ..< rowCount:
+      let length = reader.takeU64()
+      for i, config in configs:
+        let prefix = reader.take(reader.takeByte())
+        let encoded = encodeLength(length, config)
+        let (decoded, consumed) = decodeLength(prefix, config)
+        if encoded != prefix or decoded != length or consumed != prefix.len:
+          inc failures
+          if failures <= 10:
+            echo "length ", length, " (", TableConfigNames[i], "): Rust wrote ", prefix,
+              ", Nim wrote ", encoded, ", Nim decoded ", decoded, " from ", consumed,
+              " bytes"
+    check reader.pos == reader.data.len
+    echo "Checked ", rowCount, " lengths x ", configs.len, " configs, ", failures,
+      " mismatches"
+    check failures == 0
+
+{.pop.}
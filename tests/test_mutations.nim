@@ -0,0 +1,101 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, getOutput
+import unittest2
+import std/[os, strutils]
+import nim_bincode
+import bincode_config
+import cross_vectors # fnv1a64
+import test_data
+
+# Written by `cargo test --test mutation test_write_mutation_corpus`; columns
+# and labels are documented in src/mutation.rs
+const CorpusFile = "mutations.tsv"
+const GoldenDir = currentSourcePath().parentDir() / "golden"
+
+# DECODE_LIMIT in src/golden.rs
+const DecodeLimit = 1048576'u64
+
+func mutationConfig(name: string): BincodeConfig {.raises: [ValueError].} =
+  ## Nim config for a corpus config name. `variable_be` is not replayed: Nim
+  ## always writes variable-length markers little-endian.
+  let config =
+    case name
+    of "variable":
+      standard().withVariableIntEncoding()
+    of "fixed8":
+      standard().withFixedIntEncoding(8)
+    of "fixed8_be":
+      standard().withBigEndian().withFixedIntEncoding(8)
+    else:
+      raise newException(ValueError, "config not replayed: " & name)
+  config.withLimit(DecodeLimit)
+
+func applyMutation(
+    bytes: seq[byte], label: string
+): seq[byte] {.raises: [ValueError].} =
+  ## Apply a mutation label such as `flip:0:7`, `truncate:12` or `insert:1:251`
+  let parts = label.split(':')
+  case parts[0]
+  of "flip":
+    result = bytes
+    let offset = parseInt(parts[1])
+    result[offset] = result[offset] xor byte(1 shl parseInt(parts[2]))
+  of "truncate":
+    result = bytes[0 ..< parseInt(parts[1])]
+  of "insert":
+    let offset = parseInt(parts[1])
+    result = bytes[0 ..< offset] & @[byte(parseInt(parts[2]))] & bytes[offset ..^ 1]
+  else:
+    raise newException(ValueError, "unknown mutation: " & label)
+
+proc nimOutcome(
+    data: seq[byte], typeName: string, config: BincodeConfig
+): string {.raises: [IOError, ValueError].} =
+  ## Label in the corpus format: `ok:<fnv1a64 of re-encoding>` or `err:<message>`
+  var stream = memoryOutput()
+  try:
+    case typeName
+    of "Vec<u8>":
+      serialize(stream, deserialize(data, config), config)
+    of "String":
+      serializeString(stream, deserializeString(data, config), config)
+    else:
+      raise newException(ValueError, "type not replayed: " & typeName)
+  except BincodeError as e:
+    return "err:" & e.msg
+  "ok:0x" & toHex(fnv1a64(stream.getOutput())).toLowerAscii()
+
+suite "Mutation corpus (Rust → Nim)":
+  test "Nim accepts and rejects the same mutants as Rust":
+    var replayed, mismatches = 0
+    for line in readFile(testDataDir() / CorpusFile).splitLines():
+      if line.len == 0 or line.startsWith("#"):
+        continue
+      let columns = line.split('\t')
+      let (source, typeName, configName, mutation, rustOutcome) =
+        (columns[0], columns[1], columns[2], columns[3], columns[4])
+      if typeName notin ["Vec<u8>", "String"] or configName == "variable_be":
+        continue
+
+      let original = cast[seq[byte]](readFile(GoldenDir / source))
+      let mutant = applyMutation(original, mutation)
+      let nim = nimOutcome(mutant, typeName, mutationConfig(configName))
+      inc replayed
+      let agree =
+        if rustOutcome.startsWith("ok:"):
+          nim == rustOutcome
+        else:
+          nim.startsWith("err:")
+      if not agree:
+        inc mismatches
+        if mismatches <= 20:
+          echo source, " ", mutation, ": Rust ", rustOutcome, ", Nim ", nim
+    echo "Replayed ", replayed, " mutants, ", mismatches, " mismatches"
+    check replayed > 0
+    check mismatches == 0
+
+{.pop.}
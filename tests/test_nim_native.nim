@@ -0,0 +1,213 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, getOutput, OutputStreamHandle
+import unittest2
+import std/[os, tables]
+import nim_bincode
+import bincode_config
+
+# The `nim_*` cases of the golden corpus fix the layout of each Nim-native
+# type; the mapping is documented in src/nim_native.rs
+const GoldenDir = currentSourcePath().parentDir() / "golden"
+const CaseNames = [
+  "nim_set64", "nim_char_set", "nim_range", "nim_distinct", "nim_enum_hole",
+  "nim_ordered_table", "nim_char",
+]
+const ConfigNames = ["variable", "fixed8"]
+
+type
+  Small = range[0 .. 63]
+  Percent = range[-10 .. 1000]
+  UserId = distinct uint64
+  Color = enum
+    red = 1
+    green = 5
+    blue = 10
+
+const
+  SampleSet: set[Small] = {0.Small, 1, 7, 63}
+  SampleCharSet: set[char] = {'\0', 'A', 'z', '\xff'}
+  SamplePercent: Percent = -10
+  SampleUserId = UserId(300)
+  SampleColor = blue
+  SampleChar = '\xe9'
+
+func `==`(a, b: UserId): bool {.borrow.}
+
+func sampleTable(): OrderedTable[string, uint32] =
+  result["zeta"] = 1
+  result["alpha"] = 2
+
+func nativeConfig(name: string): BincodeConfig {.raises: [ValueError].} =
+  case name
+  of "variable":
+    standard().withVariableIntEncoding()
+  of "fixed8":
+    standard().withFixedIntEncoding(8)
+  else:
+    raise newException(ValueError, "unknown config: " & name)
+
+proc encodeSet(
+    stream: OutputStreamHandle, value: set[Small], config: BincodeConfig
+) {.raises: [IOError].} =
+  ## `set[0..63]`: a u64 with bit `i` set for element `i`
+  var mask = 0'u64
+  for e in value:
+    mask = mask or (1'u64 shl e)
+  encodeUint(stream, mask, config)
+
+func decodeSet(
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): set[Small] {.raises: [BincodeError].} =
+  let mask = decodeUint[uint64](data, pos, config)
+  for e in Small.low .. Small.high:
+    if (mask and (1'u64 shl e)) != 0:
+      result.incl(e)
+
+proc encodeCharSet(
+    stream: OutputStreamHandle, value: set[char], config: BincodeConfig
+) {.raises: [IOError].} =
+  ## `set[char]`: 32 bytes, bit `i mod 8` of byte `i div 8`, no length prefix
+  var bits: array[32, byte]
+  for c in value:
+    bits[ord(c) div 8] = bits[ord(c) div 8] or byte(1 shl (ord(c) mod 8))
+  for b in bits:
+    encodeUint(stream, b, config)
+
+func decodeCharSet(
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): set[char] {.raises: [BincodeError].} =
+  for i in 0 ..< 32:
+    let b = decodeUint[uint8](data, pos, config)
+    for bit in 0 ..< 8:
+      if (b and byte(1 shl bit)) != 0:
+        result.incl(char(8 * i + bit))
+
+func decodePercent(
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): Percent {.raises: [BincodeError].} =
+  ## The base i64, rejected outside the range
+  let value = decodeInt[int64](data, pos, config)
+  if value notin Percent.low.int64 .. Percent.high.int64:
+    raise newException(BincodeError, "Value outside range")
+  Percent(value)
+
+func decodeColor(
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): Color {.raises: [BincodeError].} =
+  ## The ordinal as a u32; holes are rejected
+  case decodeUint[uint32](data, pos, config)
+  of 1:
+    red
+  of 5:
+    green
+  of 10:
+    blue
+  else:
+    raise newException(BincodeError, "Invalid enum ordinal")
+
+proc encodeTable(
+    stream: OutputStreamHandle,
+    value: OrderedTable[string, uint32],
+    config: BincodeConfig,
+) {.raises: [BincodeError, IOError].} =
+  ## `OrderedTable`: a length prefix, then key and value in insertion order
+  encodeLength(stream, value.len.uint64, config)
+  for key, item in value:
+    serializeString(stream, key, config)
+    encodeUint(stream, item, config)
+
+func decodeTable(
+    data: openArray[byte], pos: var int, config: BincodeConfig
+): OrderedTable[string, uint32] {.raises: [BincodeError].} =
+  let length = decodeCollectionLength(data, pos, config)
+  for _ in 0 ..< length:
+    let key = decodeString(data, pos, config)
+    if result.hasKey(key):
+      raise newException(BincodeError, "Duplicate key")
+    result[key] = decodeUint[uint32](data, pos, config)
+
+proc nimEncode(
+    name: string, config: BincodeConfig
+): seq[byte] {.raises: [BincodeError, IOError, ValueError].} =
+  var stream = memoryOutput()
+  case name
+  of "nim_set64":
+    encodeSet(stream, SampleSet, config)
+  of "nim_char_set":
+    encodeCharSet(stream, SampleCharSet, config)
+  of "nim_range":
+    encodeInt(stream, int64(SamplePercent), config)
+  of "nim_distinct":
+    encodeUint(stream, uint64(SampleUserId), config)
+  of "nim_enum_hole":
+    encodeUint(stream, uint32(ord(SampleColor)), config)
+  of "nim_ordered_table":
+    encodeTable(stream, sampleTable(), config)
+  of "nim_char":
+    encodeUint(stream, uint8(ord(SampleChar)), config)
+  else:
+    raise newException(ValueError, "unknown case: " & name)
+  stream.getOutput()
+
+func decodesToSample(
+    name: string, data: openArray[byte], config: BincodeConfig
+): bool {.raises: [BincodeError, ValueError].} =
+  ## Decode `data` as the case's type; true if every byte was used and the
+  ## value is the sample
+  var pos = 0
+  let matches =
+    case name
+    of "nim_set64":
+      decodeSet(data, pos, config) == SampleSet
+    of "nim_char_set":
+      decodeCharSet(data, pos, config) == SampleCharSet
+    of "nim_range":
+      decodePercent(data, pos, config) == SamplePercent
+    of "nim_distinct":
+      UserId(decodeUint[uint64](data, pos, config)) == SampleUserId
+    of "nim_enum_hole":
+      decodeColor(data, pos, config) == SampleColor
+    of "nim_ordered_table":
+      decodeTable(data, pos, config) == sampleTable()
+    of "nim_char":
+      char(decodeUint[uint8](data, pos, config)) == SampleChar
+    else:
+      raise newException(ValueError, "unknown case: " & name)
+  checkNoTrailingBytes(data.len, pos, 0)
+  matches
+
+suite "Nim-native type mappings (golden corpus)":
+  test "Nim writes the golden bytes for each native type":
+    for name in CaseNames:
+      for configName in ConfigNames:
+        let golden =
+          cast[seq[byte]](readFile(GoldenDir / configName / (name & ".bin")))
+        let encoded = nimEncode(name, nativeConfig(configName))
+        check encoded == golden
+        if encoded != golden:
+          echo name, " ", configName, ": Nim ", encoded, ", golden ", golden
+
+  test "Nim decodes the golden bytes to the sample values":
+    for name in CaseNames:
+      for configName in ConfigNames:
+        let golden =
+          cast[seq[byte]](readFile(GoldenDir / configName / (name & ".bin")))
+        check decodesToSample(name, golden, nativeConfig(configName))
+
+  test "holes, out-of-range values and duplicate keys are rejected":
+    let config = nativeConfig("variable")
+    var pos = 0
+    expect BincodeError:
+      discard decodeColor([2'u8], pos, config)
+    pos = 0
+    expect BincodeError:
+      discard decodePercent([21'u8], pos, config) # zigzag -11
+    pos = 0
+    expect BincodeError:
+      discard decodeTable([2'u8, 1, 0x61, 1, 1, 0x61, 2], pos, config)
+
+{.pop.}
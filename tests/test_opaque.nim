@@ -0,0 +1,71 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, getOutput
+import unittest2
+import std/[os, strutils]
+import nim_bincode
+import bincode_config
+import test_data
+import examples/person_codec
+
+# Same values as `person_cases`/`string_cases` in tests/opaque.rs; files are
+# `<side>_opaque_<name>.bin`
+let personCases = [
+  ("person_alice", Person(name: "Alice", age: 30'u32, email: "alice@example.com")),
+  ("person_empty", Person(name: "", age: 0'u32, email: "")),
+  ("person_unicode", Person(name: "Zoë 🚀", age: high(uint32), email: "zoë@例え.jp")),
+]
+
+let stringCases = [("string_hello", "Hello, World!"), ("string_long", repeat('x', 300))]
+
+proc personToBytes(value: Person): seq[byte] {.raises: [BincodeError, IOError].} =
+  serializePerson(value, standard())
+
+proc personFromBytes(data: openArray[byte]): Person {.raises: [BincodeError].} =
+  deserializePerson(data, standard())
+
+proc stringToBytes(value: string): seq[byte] {.raises: [BincodeError, IOError].} =
+  var stream = memoryOutput()
+  serializeString(stream, value, standard())
+  stream.getOutput()
+
+proc stringFromBytes(data: openArray[byte]): string {.raises: [BincodeError].} =
+  deserializeString(data, standard())
+
+proc rustFile(name: string): seq[byte] {.raises: [IOError].} =
+  cast[seq[byte]](readFile(testDataDir() / "rust_opaque_" & name & ".bin"))
+
+proc writeNimFile(name: string, data: seq[byte]) {.raises: [IOError].} =
+  let file = "nim_opaque_" & name & ".bin"
+  writeFile(testDataDir() / file, data)
+  recordFile(file)
+
+suite "Opaque values (Rust → Nim)":
+  test "deserializeType reads Rust Opaque<Person> files":
+    for (name, expected) in personCases:
+      let decoded = deserializeType(rustFile(name), personFromBytes)
+      check decoded == expected
+      if decoded != expected:
+        echo name, ": expected ", expected, ", got ", decoded
+
+  test "deserializeType reads Rust Opaque<String> files":
+    for (name, expected) in stringCases:
+      check deserializeType(rustFile(name), stringFromBytes) == expected
+
+suite "Opaque values (Nim → Rust)":
+  test "serializeType writes the same bytes as Opaque<Person>":
+    for (name, value) in personCases:
+      let encoded = serializeType(value, personToBytes)
+      check encoded == rustFile(name)
+      writeNimFile(name, encoded)
+
+  test "serializeType writes the same bytes as Opaque<String>":
+    for (name, value) in stringCases:
+      let encoded = serializeType(value, stringToBytes)
+      check encoded == rustFile(name)
+      writeNimFile(name, encoded)
+
+{.pop.}
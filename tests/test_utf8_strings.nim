@@ -0,0 +1,92 @@
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Copyright (c) Status Research & Development GmbH
+
+{.push raises: [], gcsafe.}
+
+import faststreams # Uses: memoryOutput, getOutput
+import unittest2
+import std/[os, strutils]
+import nim_bincode
+import bincode_config
+import cross_vectors # fnv1a64
+import test_data
+
+# Written by `cargo test --test utf8 test_write_utf8_vectors`; columns
+# documented in src/utf8.rs
+const Utf8File = "utf8_strings.tsv"
+
+type Utf8Row = object
+  name: string
+  configName: string
+  payload: seq[byte]
+  rustOutcome: string
+
+func utf8Config(name: string): BincodeConfig {.raises: [ValueError].} =
+  ## Nim config for a vector config name. Payloads are under 251 bytes, so
+  ## the varint prefix is one byte under either byte order.
+  case name
+  of "variable":
+    standard().withVariableIntEncoding()
+  of "fixed8":
+    standard().withFixedIntEncoding(8)
+  of "variable_be":
+    standard().withBigEndian().withVariableIntEncoding()
+  of "fixed8_be":
+    standard().withBigEndian().withFixedIntEncoding(8)
+  else:
+    raise newException(ValueError, "unknown config: " & name)
+
+proc readRows(): seq[Utf8Row] {.raises: [IOError, ValueError].} =
+  for line in readFile(testDataDir() / Utf8File).splitLines():
+    if line.len == 0 or line.startsWith("#"):
+      continue
+    let columns = line.split('\t')
+    result.add(
+      Utf8Row(
+        name: columns[0],
+        configName: columns[1],
+        payload: cast[seq[byte]](parseHexStr(columns[2])),
+        rustOutcome: columns[3],
+      )
+    )
+
+proc nimOutcome(row: Utf8Row): string {.raises: [IOError, ValueError].} =
+  ## Label in the vector format: `ok:<fnv1a64 of re-encoding>` or `err:<message>`
+  let config = utf8Config(row.configName)
+  var input = memoryOutput()
+  var output = memoryOutput()
+  try:
+    serialize(input, row.payload, config)
+    serializeString(output, deserializeString(input.getOutput(), config), config)
+  except BincodeError as e:
+    return "err:" & e.msg
+  "ok:0x" & toHex(fnv1a64(output.getOutput())).toLowerAscii()
+
+suite "UTF-8 strings (Rust → Nim)":
+  test "deserializeString agrees with Rust on valid edge cases":
+    var checked = 0
+    for row in readRows():
+      if not row.rustOutcome.startsWith("ok:"):
+        continue
+      let nim = nimOutcome(row)
+      check nim == row.rustOutcome
+      if nim != row.rustOutcome:
+        echo row.name, " ", row.configName, ": Rust ", row.rustOutcome, ", Nim ", nim
+      inc checked
+    echo "Checked ", checked, " valid strings"
+    check checked > 0
+
+  test "deserializeString rejects invalid UTF-8 like Rust":
+    var invalid = 0
+    for row in readRows():
+      if row.rustOutcome != "err:Utf8":
+        continue
+      let nim = nimOutcome(row)
+      check nim.startsWith("err:")
+      if not nim.startsWith("err:"):
+        echo row.name, " ", row.configName, ": Rust ", row.rustOutcome, ", Nim ", nim
+      inc invalid
+    echo "Checked ", invalid, " invalid strings"
+    check invalid > 0
+
+{.pop.}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use bincode::{Decode, Encode};
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::reference::Person;
+use bincode_wrapper::schema::{BincodeSchema, Schema};
+use bincode_wrapper::transcode::{bincode_to_json, json_to_bincode, TranscodeError};
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Encode, Decode, BincodeSchema)]
+enum Command {
+    Ping,
+    Move(i16, i16),
+    Rename { name: String },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, BincodeSchema)]
+struct Envelope {
+    id: u64,
+    big: u128,
+    ratio: f32,
+    flag: bool,
+    initial: char,
+    payload: Vec<u8>,
+    commands: Vec<Command>,
+    labels: BTreeMap<u16, String>,
+    reply: Option<Option<u32>>,
+    pair: (i8, i64),
+    digest: [u8; 4],
+}
+
+fn sample() -> Envelope {
+    Envelope {
+        id: 300,
+        big: u128::MAX,
+        ratio: 0.5,
+        flag: true,
+        initial: 'é',
+        payload: vec![1, 2, 3],
+        commands: vec![Command::Ping, Command::Move(-1, 7000), Command::Rename { name: "x".to_string() }],
+        labels: BTreeMap::from([(1, "one".to_string()), (256, "two".to_string())]),
+        reply: Some(None),
+        pair: (-128, i64::MIN),
+        digest: [0xde, 0xad, 0xbe, 0xef],
+    }
+}
+
+fn sample_json() -> serde_json::Value {
+    json!({
+        "id": 300,
+        "big": "340282366920938463463374607431768211455",
+        "ratio": 0.5,
+        "flag": true,
+        "initial": "é",
+        "payload": [1, 2, 3],
+        "commands": ["Ping", {"Move": {"0": -1, "1": 7000}}, {"Rename": {"name": "x"}}],
+        "labels": [[1, "one"], [256, "two"]],
+        "reply": [null],
+        "pair": [-128, i64::MIN],
+        "digest": [0xde, 0xad, 0xbe, 0xef]
+    })
+}
+
+// ============================================================================
+// Roundtrip Tests
+// ============================================================================
+
+#[test]
+fn test_rust_bytes_to_json_for_every_config() {
+    for config in KnownConfig::ALL {
+        let bytes = config.encode(&sample()).unwrap();
+        let json = bincode_to_json(&bytes, &Envelope::schema(), config).unwrap();
+        assert_eq!(json, sample_json(), "config {}", config);
+    }
+}
+
+#[test]
+fn test_json_to_rust_bytes_for_every_config() {
+    for config in KnownConfig::ALL {
+        let bytes = json_to_bincode(&sample_json(), &Envelope::schema(), config).unwrap();
+        assert_eq!(bytes, config.encode(&sample()).unwrap(), "config {}", config);
+        let (decoded, _): (Envelope, usize) = config.decode(&bytes).unwrap();
+        assert_eq!(decoded, sample());
+    }
+}
+
+#[test]
+fn test_struct_fields_keep_declaration_order() {
+    let person = Person { name: "Ada".to_string(), age: 36, email: "ada@example.com".to_string() };
+    let bytes = KnownConfig::Variable.encode(&person).unwrap();
+    let json = bincode_to_json(&bytes, &Person::schema(), KnownConfig::Variable).unwrap();
+    assert_eq!(json.to_string(), r#"{"name":"Ada","age":36,"email":"ada@example.com"}"#);
+}
+
+#[test]
+fn test_non_finite_floats_become_strings() {
+    let bytes = KnownConfig::Fixed8.encode(&(f64::NAN, f64::NEG_INFINITY)).unwrap();
+    let schema = <(f64, f64)>::schema();
+    let json = bincode_to_json(&bytes, &schema, KnownConfig::Fixed8).unwrap();
+    assert_eq!(json, json!(["NaN", "-inf"]));
+    assert_eq!(json_to_bincode(&json, &schema, KnownConfig::Fixed8).unwrap(), bytes);
+}
+
+#[test]
+fn test_nullable_option_items_stay_distinct_from_none() {
+    let cases = [
+        (KnownConfig::Variable.encode(&None::<()>).unwrap(), Option::<()>::schema(), json!(null)),
+        (KnownConfig::Variable.encode(&Some(())).unwrap(), Option::<()>::schema(), json!([null])),
+        (KnownConfig::Variable.encode(&Some(None::<u8>)).unwrap(), Option::<Option<u8>>::schema(), json!([null])),
+        (KnownConfig::Variable.encode(&Some(Some(()))).unwrap(), Option::<Option<()>>::schema(), json!([[null]])),
+    ];
+    for (bytes, schema, expected) in cases {
+        let json = bincode_to_json(&bytes, &schema, KnownConfig::Variable).unwrap();
+        assert_eq!(json, expected, "{:?}", bytes);
+        assert_eq!(json_to_bincode(&json, &schema, KnownConfig::Variable).unwrap(), bytes, "{}", json);
+    }
+}
+
+// ============================================================================
+// Error Tests
+// ============================================================================
+
+#[test]
+fn test_trailing_bytes_are_rejected() {
+    let mut bytes = KnownConfig::Variable.encode(&7u32).unwrap();
+    bytes.push(0);
+    let Err(TranscodeError::TrailingBytes { bytes_read: 1, len: 2 }) =
+        bincode_to_json(&bytes, &Schema::U32, KnownConfig::Variable)
+    else {
+        panic!("trailing byte should be reported");
+    };
+}
+
+#[test]
+fn test_truncated_blob_is_a_decode_error() {
+    let bytes = KnownConfig::Fixed8.encode(&"hello".to_string()).unwrap();
+    let result = bincode_to_json(&bytes[..bytes.len() - 1], &Schema::String, KnownConfig::Fixed8);
+    assert!(matches!(result, Err(TranscodeError::Decode(_))));
+}
+
+#[test]
+fn test_corrupt_string_length_is_a_decode_error() {
+    // Claims a 4 GiB string in 9 bytes
+    let bytes = [0xfd, 0, 0, 0, 0, 1, 0, 0, 0];
+    let result = bincode_to_json(&bytes, &Schema::String, KnownConfig::Variable);
+    assert!(matches!(result, Err(TranscodeError::Decode(_))));
+}
+
+#[test]
+fn test_json_errors_report_the_path() {
+    let mut json = sample_json();
+    json["commands"][1]["Move"]["1"] = json!(40000);
+    let Err(e) = json_to_bincode(&json, &Envelope::schema(), KnownConfig::Variable) else {
+        panic!("out-of-range i16 should be rejected");
+    };
+    assert_eq!(e.to_string(), "$.commands[1].Move.1: expected an integer in -32768..=32767, found 40000");
+
+    let Err(e) = json_to_bincode(&json!({"name": "Ada", "age": 1}), &Person::schema(), KnownConfig::Variable) else {
+        panic!("missing field should be rejected");
+    };
+    assert_eq!(e.to_string(), "$.email: missing field");
+}
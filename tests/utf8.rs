@@ -0,0 +1,76 @@
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::mutation::Outcome;
+use bincode_wrapper::test_data;
+use bincode_wrapper::utf8::{render_tsv, string_outcome, utf8_vectors, INVALID_UTF8, UTF8_FILE, VALID_UTF8};
+
+// ============================================================================
+// Invalid UTF-8 Tests
+// ============================================================================
+
+#[test]
+fn test_invalid_payloads_fail_with_utf8_error() {
+    for &(name, payload) in INVALID_UTF8 {
+        assert!(std::str::from_utf8(payload).is_err(), "{} is valid UTF-8", name);
+        for config in KnownConfig::ALL {
+            let bytes = config.encode(&payload.to_vec()).unwrap();
+            assert_eq!(string_outcome(&bytes, config), Outcome::Rejected { kind: "Utf8".to_string() }, "{} {}", name, config);
+            // The same bytes are a valid Vec<u8>: only String checks the payload
+            assert_eq!(config.decode::<Vec<u8>>(&bytes).unwrap().0, payload);
+        }
+    }
+}
+
+#[test]
+fn test_utf8_error_reported_before_trailing_bytes() {
+    let mut bytes = KnownConfig::Variable.encode(&vec![0xed, 0xa0, 0x80]).unwrap();
+    bytes.push(0x00);
+    assert_eq!(string_outcome(&bytes, KnownConfig::Variable).to_string(), "err:Utf8");
+}
+
+// ============================================================================
+// Valid Edge Case Tests
+// ============================================================================
+
+#[test]
+fn test_valid_edge_cases_roundtrip() {
+    for &(name, payload) in VALID_UTF8 {
+        let text = std::str::from_utf8(payload).unwrap();
+        for config in KnownConfig::ALL {
+            let bytes = config.encode(&text.to_string()).unwrap();
+            assert_eq!(bytes, config.encode(&payload.to_vec()).unwrap(), "{} {}", name, config);
+            let (decoded, bytes_read) = config.decode::<String>(&bytes).unwrap();
+            assert_eq!((decoded.as_str(), bytes_read), (text, bytes.len()), "{} {}", name, config);
+        }
+    }
+}
+
+#[test]
+fn test_highest_code_points_decode() {
+    let (name, payload) = VALID_UTF8.iter().find(|(name, _)| *name == "uffff").unwrap();
+    assert_eq!(std::str::from_utf8(payload).unwrap().chars().collect::<Vec<_>>(), ['\u{ffff}'], "{}", name);
+    let (name, payload) = VALID_UTF8.iter().find(|(name, _)| *name == "u10ffff").unwrap();
+    assert_eq!(std::str::from_utf8(payload).unwrap().chars().collect::<Vec<_>>(), [char::MAX], "{}", name);
+}
+
+// ============================================================================
+// Vector Tests
+// ============================================================================
+
+#[test]
+fn test_utf8_vectors_labels() {
+    let vectors = utf8_vectors();
+    assert_eq!(vectors.len(), KnownConfig::ALL.len() * (INVALID_UTF8.len() + VALID_UTF8.len()));
+    let accepted = vectors.iter().filter(|vector| vector.outcome.is_accepted()).count();
+    assert_eq!(accepted, KnownConfig::ALL.len() * VALID_UTF8.len());
+    let tsv = render_tsv(&vectors);
+    assert!(tsv.contains("surrogate_high\tvariable\teda080\terr:Utf8\n"), "{}", tsv);
+    assert!(tsv.contains("empty\tfixed8\t\tok:0x"));
+}
+
+/// Step 1 of `make test-utf8`: the Nim side replays this file
+#[test]
+fn test_write_utf8_vectors() {
+    let vectors = utf8_vectors();
+    let path = test_data::write_file(UTF8_FILE, render_tsv(&vectors).as_bytes()).unwrap();
+    println!("Wrote {} UTF-8 vectors to {}", vectors.len(), path.display());
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+use bincode_wrapper::config::KnownConfig;
+use bincode_wrapper::reference::Person;
+use bincode_wrapper::schema::{BincodeSchema, Schema};
+use bincode_wrapper::value::{decode_value, encode_value, BincodeValue, MAX_ZERO_SIZE_ITEMS};
+
+#[derive(Debug, PartialEq, Encode, Decode, BincodeSchema)]
+enum Event {
+    Joined { user: Person },
+    Left(u32),
+    Tick,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, BincodeSchema)]
+struct Batch {
+    digest: [u8; 4],
+    events: Vec<Event>,
+    counts: BTreeMap<String, i64>,
+    note: Option<String>,
+    point: (f32, char),
+}
+
+fn person() -> Person {
+    Person { name: "Ada".to_string(), age: 36, email: "ada@example.com".to_string() }
+}
+
+fn person_value() -> BincodeValue {
+    BincodeValue::Struct {
+        name: "Person".to_string(),
+        fields: vec![
+            ("name".to_string(), BincodeValue::String("Ada".to_string())),
+            ("age".to_string(), BincodeValue::U32(36)),
+            ("email".to_string(), BincodeValue::String("ada@example.com".to_string())),
+        ],
+    }
+}
+
+fn batch() -> Batch {
+    Batch {
+        digest: [1, 2, 3, 4],
+        events: vec![Event::Joined { user: person() }, Event::Left(7), Event::Tick],
+        counts: BTreeMap::from([("a".to_string(), -1), ("b".to_string(), 1 << 40)]),
+        note: None,
+        point: (1.5, 'λ'),
+    }
+}
+
+// ============================================================================
+// Decode Tests
+// ============================================================================
+
+#[test]
+fn test_decode_struct_value() {
+    for config in KnownConfig::ALL {
+        let bytes = config.encode(&person()).unwrap();
+        let (value, bytes_read) = decode_value(&bytes, &Person::schema(), config).unwrap();
+        assert_eq!(value, person_value(), "config {}", config);
+        assert_eq!(bytes_read, bytes.len());
+    }
+}
+
+#[test]
+fn test_decode_enum_and_containers() {
+    let bytes = KnownConfig::Variable.encode(&batch()).unwrap();
+    let (value, _) = decode_value(&bytes, &Batch::schema(), KnownConfig::Variable).unwrap();
+
+    let BincodeValue::Struct { fields, .. } = value else {
+        panic!("expected a struct, got {:?}", value);
+    };
+    assert_eq!(fields[0], ("digest".to_string(), BincodeValue::Bytes(vec![1, 2, 3, 4])));
+    let BincodeValue::Seq(events) = &fields[1].1 else {
+        panic!("expected a seq, got {:?}", fields[1].1);
+    };
+    assert_eq!(
+        events[1],
+        BincodeValue::Enum {
+            name: "Event".to_string(),
+            variant: "Left".to_string(),
+            index: 1,
+            fields: vec![("0".to_string(), BincodeValue::U32(7))],
+        }
+    );
+    assert_eq!(
+        fields[2].1,
+        BincodeValue::Map(vec![
+            (BincodeValue::String("a".to_string()), BincodeValue::I64(-1)),
+            (BincodeValue::String("b".to_string()), BincodeValue::I64(1 << 40)),
+        ])
+    );
+    assert_eq!(fields[3].1, BincodeValue::Option(None));
+    assert_eq!(fields[4].1, BincodeValue::Tuple(vec![BincodeValue::F32(1.5), BincodeValue::Char('λ')]));
+}
+
+#[test]
+fn test_decode_reports_bytes_read_and_errors() {
+    let mut bytes = KnownConfig::Fixed8.encode(&vec![9u8, 8, 7]).unwrap();
+    let schema = Vec::<u8>::schema();
+    bytes.push(0xff);
+    let (value, bytes_read) = decode_value(&bytes, &schema, KnownConfig::Fixed8).unwrap();
+    assert_eq!(value, BincodeValue::Bytes(vec![9, 8, 7]));
+    assert_eq!(bytes_read, bytes.len() - 1);
+
+    assert!(decode_value(&bytes[..5], &schema, KnownConfig::Fixed8).is_err());
+    assert!(decode_value(&[2], &Option::<u8>::schema(), KnownConfig::Variable).is_err());
+}
+
+#[test]
+fn test_zero_size_items_are_bounded() {
+    // 5 bytes claiming 2^28 units: nothing bounds the loop but the item count
+    let bomb = [0xfc, 0, 0, 0, 0x10];
+    let unit_seq = Schema::Seq { item: Box::new(Schema::Unit) };
+    let result = decode_value(&bomb, &unit_seq, KnownConfig::Variable);
+    assert!(matches!(result, Err(DecodeError::LimitExceeded)), "{:?}", result);
+    let empty_tuple_map = BTreeMap::<(), ()>::schema();
+    assert!(matches!(decode_value(&bomb, &empty_tuple_map, KnownConfig::Variable), Err(DecodeError::LimitExceeded)));
+
+    let at_limit = KnownConfig::Variable.encode(&vec![(); MAX_ZERO_SIZE_ITEMS]).unwrap();
+    let (value, bytes_read) = decode_value(&at_limit, &Vec::<()>::schema(), KnownConfig::Variable).unwrap();
+    assert_eq!((value, bytes_read), (BincodeValue::Seq(vec![BincodeValue::Unit; MAX_ZERO_SIZE_ITEMS]), at_limit.len()));
+}
+
+#[test]
+fn test_corrupt_string_length_runs_out_of_input() {
+    // 9 bytes claiming a 4 GiB string: must fail on input, not abort on allocation
+    let bomb = [0xfd, 0, 0, 0, 0, 1, 0, 0, 0];
+    for schema in [String::schema(), Vec::<u8>::schema()] {
+        let result = decode_value(&bomb, &schema, KnownConfig::Variable);
+        assert!(matches!(result, Err(DecodeError::UnexpectedEnd { .. })), "{:?}", result);
+    }
+    assert!(matches!(
+        decode_value(&[2, 0xc3, 0x28], &String::schema(), KnownConfig::Variable),
+        Err(DecodeError::Utf8 { .. })
+    ));
+}
+
+// ============================================================================
+// Encode Tests
+// ============================================================================
+
+#[test]
+fn test_encode_value_matches_rust_for_every_config() {
+    for config in KnownConfig::ALL {
+        let bytes = config.encode(&batch()).unwrap();
+        let (value, _) = decode_value(&bytes, &Batch::schema(), config).unwrap();
+        assert_eq!(encode_value(&value, &Batch::schema(), config).unwrap(), bytes, "config {}", config);
+    }
+}
+
+#[test]
+fn test_encode_value_accepts_seq_of_u8() {
+    let seq = BincodeValue::Seq(vec![BincodeValue::U8(1), BincodeValue::U8(2)]);
+    let bytes = encode_value(&seq, &Vec::<u8>::schema(), KnownConfig::Variable).unwrap();
+    assert_eq!(bytes, vec![2, 1, 2]);
+}
+
+#[test]
+fn test_encode_value_reports_mismatch_path() {
+    let BincodeValue::Struct { name, mut fields } = person_value() else { unreachable!() };
+    fields[1].1 = BincodeValue::I32(36);
+    let value = BincodeValue::Struct { name, fields };
+
+    let Err(EncodeError::OtherString(message)) = encode_value(&value, &Person::schema(), KnownConfig::Variable)
+    else {
+        panic!("mismatched field should be rejected");
+    };
+    assert_eq!(message, "$.age: expected u32, found i32");
+
+    let short = BincodeValue::Bytes(vec![0; 3]);
+    let Err(EncodeError::OtherString(message)) = encode_value(&short, &<[u8; 4]>::schema(), KnownConfig::Variable)
+    else {
+        panic!("short array should be rejected");
+    };
+    assert_eq!(message, "$: expected array of 4 u8, found 3 bytes");
+
+    let schema = Schema::Seq { item: Box::new(Schema::String) };
+    assert!(encode_value(&BincodeValue::Seq(vec![BincodeValue::Unit]), &schema, KnownConfig::Fixed8).is_err());
+}
@@ -0,0 +1,71 @@
+use std::fs;
+
+use bincode_wrapper::vectors::{
+    fnv1a64, nim_vector_module, seeded_bytes, splitmix64, Payload, CROSS_FIXED8, CROSS_VARIABLE, CROSS_VECTORS,
+};
+
+// ============================================================================
+// Vector List Tests
+// ============================================================================
+
+#[test]
+fn test_fnv1a64_reference_values() {
+    assert_eq!(fnv1a64(b""), 0xcbf29ce484222325);
+    assert_eq!(fnv1a64(b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(fnv1a64(b"foobar"), 0x85944171f73967e8);
+}
+
+#[test]
+fn test_vector_files_are_numbered_from_one() {
+    assert_eq!(CROSS_VARIABLE.rust_file(0), "rust_var_001.bin");
+    assert_eq!(CROSS_VARIABLE.nim_file(11), "nim_var_012.bin");
+    assert_eq!(CROSS_FIXED8.rust_file(9), "rust_fixed8_010.bin");
+}
+
+#[test]
+fn test_payloads_cover_length_thresholds() {
+    let lengths: Vec<usize> = CROSS_VECTORS.iter().map(|vector| vector.payload.len()).collect();
+    for threshold in [250, 251, 65535, 65536] {
+        assert!(lengths.contains(&threshold), "no vector of length {}", threshold);
+    }
+    assert_eq!(Payload::Fill { byte: 7, len: 3 }.bytes(), vec![7, 7, 7]);
+}
+
+#[test]
+fn test_splitmix64_reference_values() {
+    let mut state = 0;
+    assert_eq!(splitmix64(&mut state), 0xe220a8397b1dcdaf);
+    assert_eq!(splitmix64(&mut state), 0x6e789e6aa1b965f4);
+    assert_eq!(seeded_bytes(0, 10), vec![0xaf, 0xcd, 0x1d, 0x7b, 0x39, 0xa8, 0x20, 0xe2, 0xf4, 0x65]);
+}
+
+#[test]
+fn test_boundary_vectors_are_position_dependent() {
+    for vector in CROSS_VECTORS.iter().filter(|vector| vector.payload.len() >= 250) {
+        let data = vector.data();
+        // A one-byte shift of the payload must not match it
+        assert_ne!(data[1..], data[..data.len() - 1], "{} is shift-invariant", vector.description);
+        assert!(data.iter().any(|&b| b != 0), "{} is all zeros", vector.description);
+    }
+}
+
+// ============================================================================
+// Generated Nim Module Tests
+// ============================================================================
+
+#[test]
+fn test_nim_module_lists_hash_of_every_vector() {
+    let module = nim_vector_module();
+    assert!(module.contains(&format!("const CrossVectors*: array[{}, CrossVector]", CROSS_VECTORS.len())));
+    for vector in CROSS_VECTORS {
+        assert!(module.contains(&format!("hash: {:#018x}'u64", fnv1a64(&vector.data()))));
+    }
+    assert!(module.contains("    \"nim_fixed8_012.bin\",\n"));
+    assert!(module.contains("  of ccVariable:\n    standard().withVariableIntEncoding().withLimit(CrossSizeLimit)\n"));
+}
+
+#[test]
+fn test_committed_cross_vectors_is_up_to_date() {
+    let committed = fs::read_to_string("tests/cross_vectors.nim").unwrap();
+    assert_eq!(committed, nim_vector_module(), "cross_vectors.nim is stale, run `make nim-vectors`");
+}